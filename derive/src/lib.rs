@@ -0,0 +1,182 @@
+//! Derive macros for the [`nrbf`](https://docs.rs/nrbf) crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericParam, Ident, Lifetime, LitStr, Path, parse_macro_input};
+
+/// Derive `nrbf::value::NrbfClass` and `nrbf::value::FromValue` for a struct with named fields.
+///
+/// # Container attributes
+///
+/// - `#[nrbf(class = "Contoso.Order")]`: the full name of the .NET class, defaults to the struct name.
+/// - `#[nrbf(assembly = "Contoso.Model")]`: the simple name of the assembly, any assembly is accepted if missing.
+///
+/// # Field attributes
+///
+/// - `#[nrbf(rename = "m_name")]`: the member name, defaults to the field name.
+/// - `#[nrbf(property = "Name")]`: the backing field of an auto-property, i.e. `<Name>k__BackingField`. Without a
+///   value, the field name converted to PascalCase is used.
+/// - `#[nrbf(default)]`: use `Default::default()` if the member is missing.
+/// - `#[nrbf(skip)]`: do not read a member, always use `Default::default()`.
+/// - `#[nrbf(with = "path::to::function")]`: convert the member with a function of type
+///   `fn(&Value<'i>) -> Result<T, FromValueError>`.
+#[proc_macro_derive(NrbfClass, attributes(nrbf))]
+pub fn derive_nrbf_class(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  derive(input).unwrap_or_else(|err| err.to_compile_error()).into()
+}
+
+struct Field {
+  ident: Ident,
+  member: String,
+  default: bool,
+  skip: bool,
+  with: Option<Path>,
+}
+
+fn derive(input: DeriveInput) -> syn::Result<TokenStream2> {
+  let mut class = input.ident.to_string();
+  let mut assembly = None;
+
+  for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("nrbf")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("class") {
+        class = meta.value()?.parse::<LitStr>()?.value();
+      } else if meta.path.is_ident("assembly") {
+        assembly = Some(meta.value()?.parse::<LitStr>()?.value());
+      } else {
+        return Err(meta.error("expected `class` or `assembly`"))
+      }
+
+      Ok(())
+    })?;
+  }
+
+  let Data::Struct(data) = &input.data else {
+    return Err(syn::Error::new_spanned(&input.ident, "`NrbfClass` can only be derived for structs"))
+  };
+  let Fields::Named(named) = &data.fields else {
+    return Err(syn::Error::new_spanned(&input.ident, "`NrbfClass` can only be derived for structs with named fields"))
+  };
+
+  let fields = named.named.iter().map(field).collect::<syn::Result<Vec<_>>>()?;
+
+  // The lifetime of the input, either the only lifetime parameter of the struct or a new one.
+  let lifetime = match input.generics.params.iter().collect::<Vec<_>>().as_slice() {
+    [] => None,
+    [GenericParam::Lifetime(param)] => Some(param.lifetime.clone()),
+    _ => {
+      return Err(syn::Error::new_spanned(
+        &input.generics,
+        "`NrbfClass` can only be derived for structs with at most one lifetime parameter",
+      ))
+    },
+  };
+  let impl_lifetime = lifetime.clone().unwrap_or_else(|| Lifetime::new("'i", Span::call_site()));
+
+  let ident = &input.ident;
+  let ty = match &lifetime {
+    Some(lifetime) => quote!(#ident<#lifetime>),
+    None => quote!(#ident),
+  };
+
+  let assembly = match assembly {
+    Some(assembly) => quote!(::core::option::Option::Some(#assembly)),
+    None => quote!(::core::option::Option::None),
+  };
+
+  let field_values = fields.iter().map(|Field { ident, member, default, skip, with }| {
+    let value = if *skip {
+      quote!(::core::default::Default::default())
+    } else if let Some(with) = with {
+      if *default {
+        quote! {
+          match object.members.get(#member) {
+            ::core::option::Option::Some(_) => object.convert_member_with(#member, #with)?,
+            ::core::option::Option::None => ::core::default::Default::default(),
+          }
+        }
+      } else {
+        quote!(object.convert_member_with(#member, #with)?)
+      }
+    } else if *default {
+      quote!(object.convert_optional_member(#member)?.unwrap_or_default())
+    } else {
+      quote!(object.convert_member(#member)?)
+    };
+
+    quote!(#ident: #value)
+  });
+
+  Ok(quote! {
+    impl<#impl_lifetime> ::nrbf::value::NrbfClass<#impl_lifetime> for #ty {
+      const CLASS: &'static str = #class;
+      const ASSEMBLY: ::core::option::Option<&'static str> = #assembly;
+
+      fn from_object(
+        object: &::nrbf::value::Object<#impl_lifetime>,
+      ) -> ::core::result::Result<Self, ::nrbf::value::FromValueError> {
+        object.expect_class_in(Self::CLASS, Self::ASSEMBLY)?;
+
+        ::core::result::Result::Ok(Self { #(#field_values,)* })
+      }
+    }
+
+    impl<#impl_lifetime> ::nrbf::value::FromValue<#impl_lifetime> for #ty {
+      fn from_value(
+        value: &::nrbf::Value<#impl_lifetime>,
+      ) -> ::core::result::Result<Self, ::nrbf::value::FromValueError> {
+        match value {
+          ::nrbf::Value::Object(object) => <Self as ::nrbf::value::NrbfClass<#impl_lifetime>>::from_object(object),
+          value => ::core::result::Result::Err(::nrbf::value::FromValueError::unexpected_type(#class, value)),
+        }
+      }
+    }
+  })
+}
+
+fn field(field: &syn::Field) -> syn::Result<Field> {
+  let ident = field.ident.clone().expect("named field");
+  let mut member = ident.to_string().trim_start_matches("r#").to_owned();
+  let (mut default, mut skip, mut with) = (false, false, None);
+
+  for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("nrbf")) {
+    attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("rename") {
+        member = meta.value()?.parse::<LitStr>()?.value();
+      } else if meta.path.is_ident("property") {
+        let property = match meta.value() {
+          Ok(value) => value.parse::<LitStr>()?.value(),
+          Err(_) => pascal_case(&ident.to_string()),
+        };
+        member = format!("<{}>k__BackingField", property);
+      } else if meta.path.is_ident("default") {
+        default = true;
+      } else if meta.path.is_ident("skip") {
+        skip = true;
+      } else if meta.path.is_ident("with") {
+        with = Some(meta.value()?.parse::<LitStr>()?.parse::<Path>()?);
+      } else {
+        return Err(meta.error("expected `rename`, `property`, `default`, `skip` or `with`"))
+      }
+
+      Ok(())
+    })?;
+  }
+
+  Ok(Field { ident, member, default, skip, with })
+}
+
+/// Convert a snake_case field name to PascalCase, e.g. `order_id` to `OrderId`.
+fn pascal_case(name: &str) -> String {
+  name
+    .trim_start_matches("r#")
+    .split('_')
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      chars.next().map(|c| c.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+    })
+    .collect()
+}
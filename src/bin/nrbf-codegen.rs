@@ -0,0 +1,39 @@
+//! Generate Rust types from NRBF sample files.
+//!
+//! Usage: `nrbf-codegen <FILE>...`
+//!
+//! Requires the `cli` feature, e.g. `cargo install nrbf --features cli`.
+//!
+//! The generated source code is written to standard output.
+
+use std::{env, fs, process::ExitCode};
+
+use nrbf::codegen::Generator;
+
+fn main() -> ExitCode {
+  let paths = env::args_os().skip(1).collect::<Vec<_>>();
+  if paths.is_empty() {
+    eprintln!("Usage: nrbf-codegen <FILE>...");
+    return ExitCode::FAILURE
+  }
+
+  let mut generator = Generator::new();
+
+  for path in paths {
+    let bytes = match fs::read(&path) {
+      Ok(bytes) => bytes,
+      Err(err) => {
+        eprintln!("Failed to read {}: {}", path.to_string_lossy(), err);
+        return ExitCode::FAILURE
+      },
+    };
+
+    if let Err(err) = generator.add_sample(&bytes) {
+      eprintln!("Failed to parse {}: {}", path.to_string_lossy(), err);
+      return ExitCode::FAILURE
+    }
+  }
+
+  print!("{}", generator.generate());
+  ExitCode::SUCCESS
+}
@@ -14,7 +14,7 @@ use crate::{
   MethodCall, MethodReturn, RemotingMessage, Value,
   common::{AdditionalTypeInfo, MemberTypeInfo},
   data_type::LengthPrefixedString,
-  enumeration::BinaryType,
+  enumeration::{BinaryType, PrimitiveType},
   error::{Error, ErrorInner, error_position},
   record::{
     ArraySingleObject, ArraySinglePrimitive, ArraySingleString, BinaryArray, BinaryLibrary, BinaryMethodCall,
@@ -23,6 +23,7 @@ use crate::{
     ObjectNullMultiple, ObjectNullMultiple256, SerializationHeader, SystemClassWithMembers,
     SystemClassWithMembersAndTypes,
   },
+  schema::{MemberType, Schema},
   value::Object,
 };
 
@@ -56,6 +57,8 @@ pub struct BinaryParser<'i> {
   binary_libraries: BTreeMap<NonZeroU32, LengthPrefixedString<'i>>,
   classes: BTreeMap<NonZeroU32, Class<'i>>,
   objects: BTreeMap<NonZeroU32, Value<'i>>,
+  strings: BTreeMap<NonZeroU32, &'i str>,
+  schema: Option<Schema<'i>>,
 }
 
 macro_rules! alt_mut {
@@ -75,14 +78,26 @@ macro_rules! alt_mut {
 }
 
 impl<'i> BinaryParser<'i> {
-  fn parse_binary_library(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (), Error<'i>> {
-    let (input, binary_library) = opt(BinaryLibrary::parse).parse(input)?;
+  /// Parses the `BinaryLibrary` records preceding a record.
+  ///
+  /// A library ID may only be defined once, but the same record may be parsed again when backtracking.
+  fn parse_binary_library(&mut self, mut input: &'i [u8]) -> IResult<&'i [u8], (), Error<'i>> {
+    while let (rest, Some(binary_library)) = opt(BinaryLibrary::parse).parse(input)? {
+      input = rest;
 
-    if let Some(binary_library) = binary_library {
       let library_id = binary_library.library_id();
+      let library_name = binary_library.library_name.as_str();
 
-      if self.binary_libraries.insert(library_id, binary_library.library_name).is_some() {
-        return Err(nom::Err::Failure(error_position!(input, DuplicateLibraryId)))
+      match self.binary_libraries.insert(library_id, binary_library.library_name) {
+        Some(previous) if previous.as_str() != library_name => {
+          return Err(nom::Err::Failure(error_position!(input, DuplicateLibraryId)))
+        },
+        Some(_) => (),
+        None => {
+          if let Some(schema) = &mut self.schema {
+            schema.add_library(library_id.get(), library_name);
+          }
+        },
       }
     }
 
@@ -104,9 +119,6 @@ impl<'i> BinaryParser<'i> {
           |primitive| ValueOrRef::Value(primitive.into_value()),
         )
         .parse(input)?,
-        (BinaryType::String, None) => {
-          map(BinaryObjectString::parse, |s| ValueOrRef::Value(Value::String(s.as_str()))).parse(input)?
-        },
         (BinaryType::Object, None) => return self.parse_member_reference(input, None),
         (BinaryType::SystemClass, Some(AdditionalTypeInfo::SystemClass(class_name))) => {
           if let Ok((input, (_, object))) = self.parse_classes(input) {
@@ -116,7 +128,7 @@ impl<'i> BinaryParser<'i> {
 
             (input, ValueOrRef::Value(Value::Object(object)))
           } else {
-            Self::parse_null_object(input)?
+            Self::parse_object_reference(input)?
           }
         },
         (BinaryType::Class, Some(AdditionalTypeInfo::Class(class_type_info))) => {
@@ -135,34 +147,26 @@ impl<'i> BinaryParser<'i> {
 
             (input, ValueOrRef::Value(Value::Object(object)))
           } else {
-            Self::parse_null_object(input)?
+            Self::parse_object_reference(input)?
           }
         },
         (BinaryType::ObjectArray, None) => return self.parse_member_reference(input, None),
-        (BinaryType::StringArray, None) => alt((
-          map(BinaryObjectString::parse, |s| ValueOrRef::Value(Value::String(s.as_str()))),
-          map(
-            |input| MemberReference::parse(input),
-            |member_reference| ValueOrRef::Ref(RefId(member_reference.id_ref)),
-          ),
-          Self::parse_null_object,
-        ))
-        .parse(input)?,
+        (BinaryType::String | BinaryType::StringArray, None) => {
+          alt((|input| self.parse_string(input), Self::parse_object_reference)).parse(input)?
+        },
         (BinaryType::PrimitiveArray, Some(AdditionalTypeInfo::Primitive(_primitive_type))) => {
-          map(|input| MemberReference::parse(input), |member_reference| ValueOrRef::Ref(RefId(member_reference.id_ref)))
-            .parse(input)?
+          Self::parse_object_reference(input)?
         },
         _ => unreachable!(),
       }
     } else {
-      alt((
+      alt_mut!(input =>
         map(|input| MemberPrimitiveTyped::parse(input), |primitive| ValueOrRef::Value(primitive.into_value())),
         map(|input| MemberReference::parse(input), |member_reference| ValueOrRef::Ref(RefId(member_reference.id_ref))),
-        map(BinaryObjectString::parse, |s| ValueOrRef::Value(Value::String(s.as_str()))),
+        |input| self.parse_string(input),
         Self::parse_null_object,
         map(|input| self.parse_classes(input), |(_, object)| ValueOrRef::Value(Value::Object(object))),
-      ))
-      .parse(input)?
+      )?
     };
 
     Ok((input, object))
@@ -207,6 +211,8 @@ impl<'i> BinaryParser<'i> {
         ValueOrRef::Ref(id) => {
           if let Some(value) = self.objects.remove(&id.0) {
             members2.push(value);
+          } else if let Some(s) = self.strings.get(&id.0) {
+            members2.push(Value::String(s));
           } else {
             let member2;
             (input, member2) = verify(|input| self.parse_referenceable(input), |id2| id2.0 == id.0).parse(input)?;
@@ -233,6 +239,10 @@ impl<'i> BinaryParser<'i> {
     let (input, (object_id, class)) = match ClassWithId::parse(input) {
       Ok((input, class_with_id)) => {
         if let Some(class) = self.classes.get(&class_with_id.metadata_id()) {
+          if let Some(schema) = &mut self.schema {
+            schema.add_class_with_id(class_with_id.object_id().get(), class_with_id.metadata_id().get());
+          }
+
           (input, (class_with_id.object_id(), class.clone()))
         } else {
           return Err(nom::Err::Failure(error_position!(err_input, MissingMetadataId)))
@@ -246,11 +256,16 @@ impl<'i> BinaryParser<'i> {
           (class.object_id(), Class::SystemClassWithMembersAndTypes(class))
         }),
       ))
-      .parse(input)?,
+      .parse(input)
+      .inspect(|(_, (object_id, class))| {
+        if let Some(schema) = &mut self.schema {
+          schema.add_class(object_id.get(), class);
+        }
+      })?,
       Err(err) => return Err(err),
     };
 
-    if self.classes.contains_key(&object_id) {
+    if self.classes.insert(object_id, class.clone()).is_some() {
       return Err(nom::Err::Failure(error_position!(input, DuplicateObjectId)))
     }
 
@@ -312,6 +327,11 @@ impl<'i> BinaryParser<'i> {
   fn parse_array_single_object(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Vec<Value<'i>>), Error<'i>> {
     let (mut input, array_single_object) = ArraySingleObject::parse(input)?;
 
+    if let Some(schema) = &mut self.schema {
+      let array_info = &array_single_object.array_info;
+      schema.add_array(array_info.object_id().get(), array_info.len(), MemberType::Object);
+    }
+
     let mut members = vec![];
 
     let len = array_single_object.array_info.len();
@@ -338,8 +358,26 @@ impl<'i> BinaryParser<'i> {
   }
 
   /// 2.7 Binary Record Grammar - `ArraySinglePrimitive *(MemberPrimitiveUnTyped)`
-  fn parse_array_single_primitive(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Vec<Value<'i>>), Error<'i>> {
+  ///
+  /// Byte arrays are borrowed from the input as a whole.
+  fn parse_array_single_primitive(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Value<'i>), Error<'i>> {
     let (input, array_single_primitive) = ArraySinglePrimitive::parse(input)?;
+    let object_id = array_single_primitive.object_id();
+
+    if let Some(schema) = &mut self.schema {
+      let element_type = MemberType::Primitive(array_single_primitive.primitive_type);
+      schema.add_array(object_id.get(), array_single_primitive.array_info.len(), element_type);
+    }
+
+    if array_single_primitive.primitive_type == PrimitiveType::Byte {
+      let len = array_single_primitive.array_info.len();
+      if input.len() < len {
+        return Err(nom::Err::Failure(error_position!(input, ExpectedPrimitive(PrimitiveType::Byte))))
+      }
+
+      let (bytes, input) = input.split_at(len);
+      return Ok((input, (RefId(object_id), Value::Bytes(bytes))))
+    }
 
     let (input, members) = count(
       map(
@@ -350,14 +388,18 @@ impl<'i> BinaryParser<'i> {
     )
     .parse(input)?;
 
-    let object_id = array_single_primitive.object_id();
-    Ok((input, (RefId(object_id), members)))
+    Ok((input, (RefId(object_id), Value::Array(members))))
   }
 
   /// 2.7 Binary Record Grammar - `ArraySingleString *(BinaryObjectString/MemberReference/nullObject)`
   fn parse_array_single_string(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Vec<Value<'i>>), Error<'i>> {
     let (mut input, array_single_string) = ArraySingleString::parse(input)?;
 
+    if let Some(schema) = &mut self.schema {
+      let array_info = &array_single_string.array_info;
+      schema.add_array(array_info.object_id().get(), array_info.len(), MemberType::String);
+    }
+
     let mut members = vec![];
 
     let len = array_single_string.array_info.len();
@@ -384,28 +426,51 @@ impl<'i> BinaryParser<'i> {
   }
 
   /// 2.7 Binary Record Grammar - `BinaryArray *(memberReference)`
-  fn parse_binary_array(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Vec<Value<'i>>), Error<'i>> {
+  ///
+  /// Byte arrays are borrowed from the input, multi-dimensional byte arrays as one slice per row.
+  fn parse_binary_array(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Value<'i>), Error<'i>> {
     let err_input = input;
 
     let (input, binary_array) = BinaryArray::parse(input)?;
 
+    if let Some(schema) = &mut self.schema {
+      schema.add_binary_array(&binary_array);
+    }
+
     let member_count = match binary_array.lengths.iter().copied().try_fold(1usize, |acc, n| acc.checked_mul(n)) {
       Some(member_count) => member_count,
       None => return Err(nom::Err::Failure(error_position!(err_input, InvalidLength))),
     };
-    let (input, members) = count(
-      |input| {
-        self.parse_member_reference(input, Some((binary_array.type_enum, binary_array.additional_type_info.as_ref())))
-      },
-      member_count,
-    )
-    .parse(input)?;
-
-    let (input, members) = self.resolve_members(input, members)?;
     let object_id = binary_array.object_id();
 
-    let mut members = members;
-    for l in binary_array.lengths.into_iter().skip(1).rev() {
+    let (input, mut members, dimensions) = if binary_array.type_enum == BinaryType::Primitive
+      && binary_array.additional_type_info == Some(AdditionalTypeInfo::Primitive(PrimitiveType::Byte))
+    {
+      if input.len() < member_count {
+        return Err(nom::Err::Failure(error_position!(input, ExpectedPrimitive(PrimitiveType::Byte))))
+      }
+
+      let (bytes, input) = input.split_at(member_count);
+      match binary_array.lengths.split_last() {
+        Some((&row_len, dimensions)) if !dimensions.is_empty() => {
+          (input, bytes.chunks(row_len.max(1)).map(Value::Bytes).collect::<Vec<_>>(), dimensions)
+        },
+        _ => return Ok((input, (RefId(object_id), Value::Bytes(bytes)))),
+      }
+    } else {
+      let (input, members) = count(
+        |input| {
+          self.parse_member_reference(input, Some((binary_array.type_enum, binary_array.additional_type_info.as_ref())))
+        },
+        member_count,
+      )
+      .parse(input)?;
+
+      let (input, members) = self.resolve_members(input, members)?;
+      (input, members, binary_array.lengths.as_slice())
+    };
+
+    for &l in dimensions.iter().skip(1).rev() {
       let mut members2 = vec![];
 
       while !members.is_empty() {
@@ -415,17 +480,17 @@ impl<'i> BinaryParser<'i> {
       members = members2;
     }
 
-    Ok((input, (RefId(object_id), members)))
+    Ok((input, (RefId(object_id), Value::Array(members))))
   }
 
   /// 2.7 Binary Record Grammar - `Arrays`
-  fn parse_arrays(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Vec<Value<'i>>), Error<'i>> {
+  fn parse_arrays(&mut self, input: &'i [u8]) -> IResult<&'i [u8], (RefId, Value<'i>), Error<'i>> {
     let (input, ()) = self.parse_binary_library(input)?;
 
     alt_mut!(input =>
-      |input| self.parse_array_single_object(input),
+      map(|input| self.parse_array_single_object(input), |(object_id, array)| (object_id, Value::Array(array))),
       |input| self.parse_array_single_primitive(input),
-      |input| self.parse_array_single_string(input),
+      map(|input| self.parse_array_single_string(input), |(object_id, array)| (object_id, Value::Array(array))),
       |input| self.parse_binary_array(input),
     )
   }
@@ -437,13 +502,13 @@ impl<'i> BinaryParser<'i> {
         |input| self.parse_classes(input),
         |(object_id, object)| (object_id, Value::Object(object)),
       ),
-      map(
-        |input| self.parse_arrays(input),
-        |(object_id, array)| (object_id, Value::Array(array)),
-      ),
+      |input| self.parse_arrays(input),
       map(
         BinaryObjectString::parse,
-        |s| (RefId(s.object_id()), Value::String(s.as_str())),
+        |s| {
+          self.strings.insert(s.object_id(), s.as_str());
+          (RefId(s.object_id()), Value::String(s.as_str()))
+        },
       ),
     )?;
 
@@ -451,6 +516,23 @@ impl<'i> BinaryParser<'i> {
     Ok((input, object_id))
   }
 
+  /// Parses a string written inline, keeping it for later member references to the same string.
+  fn parse_string(&mut self, input: &'i [u8]) -> IResult<&'i [u8], ValueOrRef<'i>, Error<'i>> {
+    let (input, s) = BinaryObjectString::parse(input)?;
+    self.strings.insert(s.object_id(), s.as_str());
+
+    Ok((input, ValueOrRef::Value(Value::String(s.as_str()))))
+  }
+
+  /// Parses a reference to an object which is not written inline, or a null object.
+  fn parse_object_reference(input: &'i [u8]) -> IResult<&'i [u8], ValueOrRef<'i>, Error<'i>> {
+    alt((
+      map(|input| MemberReference::parse(input), |member_reference| ValueOrRef::Ref(RefId(member_reference.id_ref))),
+      Self::parse_null_object,
+    ))
+    .parse(input)
+  }
+
   /// 2.7 Binary Record Grammar - `nullObject`
   fn parse_null_object(input: &'i [u8]) -> IResult<&'i [u8], ValueOrRef<'i>, Error<'i>> {
     alt((
@@ -495,12 +577,11 @@ impl<'i> BinaryParser<'i> {
           return Ok(Some(call_array))
         }
       } else if message_enum.intersects(MessageFlags::ARGS_IN_ARRAY) {
-        if let Some(mut call_array) = call_array {
-          if !call_array.is_empty() {
-            if let Value::Array(args) = call_array.remove(0) {
-              return Ok(Some(args))
-            }
-          }
+        if let Some(mut call_array) = call_array
+          && !call_array.is_empty()
+          && let Value::Array(args) = call_array.remove(0)
+        {
+          return Ok(Some(args))
         }
       } else {
         return Ok(binary_method_call.args.map(|v| v.into_values()))
@@ -533,32 +614,52 @@ impl<'i> BinaryParser<'i> {
 
     let (input, call_array) = opt(|input| self.parse_call_array(input, root_id)).parse(input)?;
 
-    let parse_args = |message_enum: MessageFlags| {
-      if message_enum.intersects(MessageFlags::ARGS_IS_ARRAY) {
-        if let Some(call_array) = call_array {
-          return Ok(Some(call_array.clone()))
+    let message_enum = binary_method_return.message_enum;
+
+    // 2.2.3.4 `MethodReturnCallArray`
+    let (return_value, args, exception) = if message_enum.intersects(MessageFlags::ARGS_IS_ARRAY) {
+      match call_array {
+        Some(call_array) => (binary_method_return.return_value.map(|v| v.into_value()), Some(call_array), None),
+        None => return Err(nom::Err::Failure(error_position!(input, InvalidArgs))),
+      }
+    } else {
+      let mut call_array = call_array.unwrap_or_default().into_iter();
+
+      let return_value = if message_enum.intersects(MessageFlags::RETURN_VALUE_IN_ARRAY) {
+        match call_array.next() {
+          Some(return_value) => Some(return_value),
+          None => return Err(nom::Err::Failure(error_position!(input, InvalidCallArray))),
         }
-      } else if message_enum.intersects(MessageFlags::ARGS_IN_ARRAY) {
-        if let Some(mut call_array) = call_array {
-          if !call_array.is_empty() {
-            if let Value::Array(args) = call_array.remove(0) {
-              return Ok(Some(args))
-            }
-          }
+      } else {
+        binary_method_return.return_value.map(|v| v.into_value())
+      };
+
+      let args = if message_enum.intersects(MessageFlags::ARGS_IN_ARRAY) {
+        match call_array.next() {
+          Some(Value::Array(args)) => Some(args),
+          _ => return Err(nom::Err::Failure(error_position!(input, InvalidArgs))),
         }
       } else {
-        return Ok(binary_method_return.args.map(|v| v.into_values()))
-      }
+        binary_method_return.args.map(|v| v.into_values())
+      };
 
-      Err(nom::Err::Failure(error_position!(input, InvalidArgs)))
-    };
+      let exception = if message_enum.intersects(MessageFlags::EXCEPTION_IN_ARRAY) {
+        match call_array.next() {
+          Some(exception) => Some(exception),
+          None => return Err(nom::Err::Failure(error_position!(input, InvalidCallArray))),
+        }
+      } else {
+        None
+      };
 
-    let args = parse_args(binary_method_return.message_enum)?;
+      (return_value, args, exception)
+    };
 
     let method_return = MethodReturn {
-      return_value: binary_method_return.return_value.map(|v| v.into_value()),
+      return_value,
       call_context: binary_method_return.call_context.map(|c| c.as_str()),
       args,
+      exception,
     };
 
     Ok((input, method_return))
@@ -636,4 +737,15 @@ impl<'i> BinaryParser<'i> {
       nom::Err::Error(err) | nom::Err::Failure(err) => err,
     })
   }
+
+  /// Extracts the [`Schema`] of a [`RemotingMessage`] from bytes.
+  pub fn deserialize_schema(mut self, input: &'i [u8]) -> Result<Schema<'i>, Error<'i>> {
+    self.schema = Some(Schema::default());
+    self.parse_remoting_message(input).map_err(|err| match err {
+      nom::Err::Incomplete(_) => Error { input, inner: ErrorInner::Eof },
+      nom::Err::Error(err) | nom::Err::Failure(err) => err,
+    })?;
+
+    Ok(self.schema.unwrap_or_default())
+  }
 }
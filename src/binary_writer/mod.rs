@@ -0,0 +1,533 @@
+use std::{collections::HashMap, mem};
+
+use crate::{
+  MethodCall, MethodReturn, RemotingMessage, Value,
+  enumeration::{BinaryType, PrimitiveType},
+  record::{MessageFlags, RecordType},
+  schema::MemberType,
+  value::Object,
+};
+
+/// An array which is referenced by a `MemberReference` and written after the referencing record.
+type DeferredArray<'a, 'i> = (i32, &'a Value<'i>);
+
+#[derive(Debug, Default)]
+pub struct BinaryWriter {
+  output: Vec<u8>,
+  last_id: i32,
+  binary_libraries: HashMap<String, i32>,
+  /// The object IDs of written class metadata, keyed by the metadata following the object ID.
+  classes: HashMap<Vec<u8>, i32>,
+}
+
+impl BinaryWriter {
+  fn next_id(&mut self) -> i32 {
+    self.last_id += 1;
+    self.last_id
+  }
+
+  fn write_u8(&mut self, n: u8) {
+    self.output.push(n);
+  }
+
+  fn write_i32(&mut self, n: i32) {
+    self.output.extend(n.to_le_bytes());
+  }
+
+  fn write_record_type(&mut self, record_type: RecordType) {
+    self.write_u8(record_type as u8);
+  }
+
+  /// 2.1.1.6 `LengthPrefixedString`
+  fn write_length_prefixed_string(&mut self, s: &str) {
+    let mut len = s.len();
+
+    loop {
+      let len_part = (len & 0b01111111) as u8;
+      len >>= 7;
+
+      if len == 0 {
+        self.write_u8(len_part);
+        break
+      }
+
+      self.write_u8(len_part | 0b10000000);
+    }
+
+    self.output.extend(s.as_bytes());
+  }
+
+  /// 2.6.1 `SerializationHeaderRecord`
+  fn write_serialization_header(&mut self, root_id: i32, header_id: i32) {
+    self.write_record_type(RecordType::SerializedStreamHeader);
+    self.write_i32(root_id);
+    self.write_i32(header_id);
+    self.write_i32(1);
+    self.write_i32(0);
+  }
+
+  /// 2.5.2 `MemberPrimitiveUnTyped`
+  fn write_primitive_untyped(&mut self, value: &Value<'_>) {
+    match value {
+      Value::Boolean(v) => self.write_u8(u8::from(*v)),
+      Value::Byte(v) => self.write_u8(*v),
+      Value::Char(v) => self.output.extend(v.encode_utf8(&mut [0; 4]).as_bytes()),
+      Value::Decimal(v) => self.write_length_prefixed_string(&(v.0).0.to_string()),
+      Value::Double(v) => self.output.extend(v.to_le_bytes()),
+      Value::Int16(v) => self.output.extend(v.to_le_bytes()),
+      Value::Int32(v) => self.output.extend(v.to_le_bytes()),
+      Value::Int64(v) => self.output.extend(v.to_le_bytes()),
+      Value::SByte(v) => self.output.extend(v.to_le_bytes()),
+      Value::Single(v) => self.output.extend(v.to_le_bytes()),
+      Value::TimeSpan(v) => self.output.extend(v.value().to_le_bytes()),
+      Value::DateTime(v) => self.output.extend(i64::from(v.0).to_le_bytes()),
+      Value::UInt16(v) => self.output.extend(v.to_le_bytes()),
+      Value::UInt32(v) => self.output.extend(v.to_le_bytes()),
+      Value::UInt64(v) => self.output.extend(v.to_le_bytes()),
+      Value::Object(_) | Value::Array(_) | Value::Bytes(_) | Value::String(_) | Value::Null => unreachable!(),
+    }
+  }
+
+  /// 2.2.2.1 `ValueWithCode`
+  fn write_value_with_code(&mut self, value: &Value<'_>) {
+    match value {
+      Value::String(s) => {
+        self.write_u8(PrimitiveType::String as u8);
+        self.write_length_prefixed_string(s);
+      },
+      Value::Null => self.write_u8(PrimitiveType::Null as u8),
+      value => {
+        let primitive_type = primitive_type(value).unwrap();
+        self.write_u8(primitive_type as u8);
+        self.write_primitive_untyped(value);
+      },
+    }
+  }
+
+  /// 2.2.2.2 `StringValueWithCode`
+  fn write_string_value_with_code(&mut self, s: &str) {
+    self.write_u8(PrimitiveType::String as u8);
+    self.write_length_prefixed_string(s);
+  }
+
+  /// 2.2.2.3 `ArrayOfValueWithCode`
+  fn write_array_of_value_with_code(&mut self, values: &[Value<'_>]) {
+    self.write_i32(values.len() as i32);
+
+    for value in values {
+      self.write_value_with_code(value);
+    }
+  }
+
+  /// 2.6.2 `BinaryLibrary`
+  fn library_id(&mut self, library: &str) -> i32 {
+    if let Some(library_id) = self.binary_libraries.get(library) {
+      return *library_id
+    }
+
+    let library_id = self.next_id();
+    self.binary_libraries.insert(library.to_owned(), library_id);
+
+    self.write_record_type(RecordType::BinaryLibrary);
+    self.write_i32(library_id);
+    self.write_length_prefixed_string(library);
+
+    library_id
+  }
+
+  /// 2.7 Binary Record Grammar - `memberReference`
+  fn write_member_reference<'a, 'i>(&mut self, value: &'a Value<'i>, deferred: &mut Vec<DeferredArray<'a, 'i>>) {
+    match value {
+      Value::Object(object) => {
+        let object_id = self.next_id();
+        self.write_class(object_id, object);
+      },
+      Value::Array(_) | Value::Bytes(_) => {
+        let object_id = self.next_id();
+        self.write_record_type(RecordType::MemberReference);
+        self.write_i32(object_id);
+        deferred.push((object_id, value));
+      },
+      Value::String(s) => {
+        let object_id = self.next_id();
+        self.write_record_type(RecordType::BinaryObjectString);
+        self.write_i32(object_id);
+        self.write_length_prefixed_string(s);
+      },
+      Value::Null => self.write_record_type(RecordType::ObjectNull),
+      value => {
+        self.write_record_type(RecordType::MemberPrimitiveTyped);
+        self.write_u8(primitive_type(value).unwrap() as u8);
+        self.write_primitive_untyped(value);
+      },
+    }
+  }
+
+  /// 2.3.1.2 `MemberTypeInfo` type of a member value, writing the `BinaryLibrary` of its class if
+  /// needed.
+  fn member_type<'v>(&mut self, value: &Value<'v>) -> MemberType<'v> {
+    match value {
+      Value::Object(Object { class, library: Some(library), .. }) => {
+        MemberType::Class { name: class, library_id: self.library_id(library) as u32 }
+      },
+      Value::Object(Object { class, library: None, .. }) => MemberType::SystemClass(class),
+      Value::Array(array) => array_primitive_type(array).map_or(MemberType::ObjectArray, MemberType::PrimitiveArray),
+      Value::Bytes(_) => MemberType::PrimitiveArray(PrimitiveType::Byte),
+      Value::String(_) => MemberType::String,
+      Value::Null => MemberType::Object,
+      value => MemberType::Primitive(primitive_type(value).unwrap()),
+    }
+  }
+
+  /// 2.3.1.2 `MemberTypeInfo`
+  fn write_member_type_info(&mut self, member_types: &[MemberType<'_>]) {
+    for member_type in member_types {
+      self.write_u8(match member_type {
+        MemberType::Primitive(_) => BinaryType::Primitive,
+        MemberType::String => BinaryType::String,
+        MemberType::Object => BinaryType::Object,
+        MemberType::SystemClass(_) => BinaryType::SystemClass,
+        MemberType::Class { .. } => BinaryType::Class,
+        MemberType::ObjectArray => BinaryType::ObjectArray,
+        MemberType::StringArray => BinaryType::StringArray,
+        MemberType::PrimitiveArray(_) => BinaryType::PrimitiveArray,
+      } as u8);
+    }
+
+    for member_type in member_types {
+      match member_type {
+        MemberType::Primitive(primitive_type) | MemberType::PrimitiveArray(primitive_type) => {
+          self.write_u8(*primitive_type as u8)
+        },
+        MemberType::SystemClass(class) => self.write_length_prefixed_string(class),
+        MemberType::Class { name, library_id } => {
+          self.write_length_prefixed_string(name);
+          self.write_i32(*library_id as i32);
+        },
+        MemberType::String | MemberType::Object | MemberType::ObjectArray | MemberType::StringArray => (),
+      }
+    }
+  }
+
+  fn write_deferred_arrays(&mut self, deferred: Vec<DeferredArray<'_, '_>>) {
+    for (object_id, array) in deferred {
+      match array {
+        Value::Bytes(bytes) => self.write_bytes(object_id, bytes),
+        Value::Array(array) => self.write_array(object_id, array),
+        _ => unreachable!(),
+      }
+    }
+  }
+
+  /// 2.7 Binary Record Grammar - `Classes`
+  ///
+  /// Objects are written as `SystemClassWithMembersAndTypes` or `ClassWithMembersAndTypes` records
+  /// with the member types inferred from the member values, or as a `ClassWithId` record if an
+  /// object with the same metadata was written before. Members are sorted by name so the output is
+  /// deterministic.
+  fn write_class(&mut self, object_id: i32, object: &Object<'_>) {
+    let library_id = object.library.map(|library| self.library_id(library));
+
+    let mut members = object.members.iter().collect::<Vec<_>>();
+    members.sort_by_key(|(name, _)| *name);
+
+    let member_types = members.iter().map(|(_, member)| self.member_type(member)).collect::<Vec<_>>();
+
+    // 2.3.1.1 `ClassInfo` without the object ID, 2.3.1.2 `MemberTypeInfo` and the library ID
+    let output = mem::take(&mut self.output);
+    self.write_length_prefixed_string(object.class);
+    self.write_i32(members.len() as i32);
+    for (member_name, _) in &members {
+      self.write_length_prefixed_string(member_name);
+    }
+    self.write_member_type_info(&member_types);
+    if let Some(library_id) = library_id {
+      self.write_i32(library_id);
+    }
+    let metadata = mem::replace(&mut self.output, output);
+
+    if let Some(&metadata_id) = self.classes.get(&metadata) {
+      self.write_record_type(RecordType::ClassWithId);
+      self.write_i32(object_id);
+      self.write_i32(metadata_id);
+    } else {
+      if library_id.is_some() {
+        self.write_record_type(RecordType::ClassWithMembersAndTypes);
+      } else {
+        self.write_record_type(RecordType::SystemClassWithMembersAndTypes);
+      }
+
+      self.write_i32(object_id);
+      self.output.extend(&metadata);
+      self.classes.insert(metadata, object_id);
+    }
+
+    let mut deferred = vec![];
+    for ((_, member), member_type) in members.into_iter().zip(member_types) {
+      match member_type {
+        MemberType::Primitive(_) => self.write_primitive_untyped(member),
+        _ => self.write_member_reference(member, &mut deferred),
+      }
+    }
+    self.write_deferred_arrays(deferred);
+  }
+
+  /// 2.7 Binary Record Grammar - `ArraySinglePrimitive *(MemberPrimitiveUnTyped)` for a byte array
+  fn write_bytes(&mut self, object_id: i32, bytes: &[u8]) {
+    self.write_record_type(RecordType::ArraySinglePrimitive);
+    self.write_i32(object_id);
+    self.write_i32(bytes.len() as i32);
+    self.write_u8(PrimitiveType::Byte as u8);
+    self.output.extend(bytes);
+  }
+
+  /// 2.7 Binary Record Grammar - `Arrays`
+  ///
+  /// Arrays consisting only of primitive values of the same type are written as an `ArraySinglePrimitive`
+  /// record, all others as an `ArraySingleObject` record.
+  fn write_array(&mut self, object_id: i32, array: &[Value<'_>]) {
+    if let Some(primitive_type) = array_primitive_type(array) {
+      self.write_record_type(RecordType::ArraySinglePrimitive);
+      self.write_i32(object_id);
+      self.write_i32(array.len() as i32);
+      self.write_u8(primitive_type as u8);
+
+      for value in array {
+        self.write_primitive_untyped(value);
+      }
+
+      return
+    }
+
+    self.write_record_type(RecordType::ArraySingleObject);
+    self.write_i32(object_id);
+    self.write_i32(array.len() as i32);
+
+    let mut deferred = vec![];
+    for value in array {
+      self.write_member_reference(value, &mut deferred);
+    }
+    self.write_deferred_arrays(deferred);
+  }
+
+  /// 2.7 Binary Record Grammar - `referenceable`
+  fn write_root_value(&mut self, value: &Value<'_>) {
+    match value {
+      Value::Null => self.write_serialization_header(0, -1),
+      Value::Array(array) => {
+        let object_id = self.next_id();
+        self.write_serialization_header(object_id, -1);
+        self.write_array(object_id, array);
+      },
+      Value::Bytes(bytes) => {
+        let object_id = self.next_id();
+        self.write_serialization_header(object_id, -1);
+        self.write_bytes(object_id, bytes);
+      },
+      Value::String(s) => {
+        let object_id = self.next_id();
+        self.write_serialization_header(object_id, -1);
+        self.write_record_type(RecordType::BinaryObjectString);
+        self.write_i32(object_id);
+        self.write_length_prefixed_string(s);
+      },
+      Value::Object(object) => {
+        let object_id = self.next_id();
+        self.write_serialization_header(object_id, -1);
+        self.write_class(object_id, object);
+      },
+      value => {
+        let object_id = self.next_id();
+        self.write_serialization_header(object_id, -1);
+
+        // Primitive values are boxed, e.g. `System.Int32` with an `m_value` member.
+        let boxed =
+          Object { class: system_class_name(value), library: None, members: [("m_value", value.clone())].into() };
+        self.write_class(object_id, &boxed);
+      },
+    }
+  }
+
+  /// Write the call array for a method call or return, if it is not empty.
+  fn write_call_array(&mut self, object_id: i32, call_array: &[&Value<'_>]) {
+    if !call_array.is_empty() {
+      self.write_record_type(RecordType::ArraySingleObject);
+      self.write_i32(object_id);
+      self.write_i32(call_array.len() as i32);
+
+      let mut deferred = vec![];
+      for value in call_array {
+        self.write_member_reference(value, &mut deferred);
+      }
+      self.write_deferred_arrays(deferred);
+    }
+  }
+
+  /// 2.7 Binary Record Grammar - `methodCall`
+  fn write_method_call(&mut self, method_call: &MethodCall<'_>) {
+    let MethodCall { method_name, type_name, call_context, args } = method_call;
+
+    let mut message_enum = context_flags(*call_context);
+
+    let call_array = match args {
+      None => {
+        message_enum |= MessageFlags::NO_ARGS;
+        vec![]
+      },
+      Some(args) if args.iter().all(is_value_with_code) => {
+        message_enum |= MessageFlags::ARGS_INLINE;
+        vec![]
+      },
+      Some(args) => {
+        message_enum |= MessageFlags::ARGS_IS_ARRAY;
+        args.iter().collect()
+      },
+    };
+
+    let root_id = if call_array.is_empty() { 0 } else { self.next_id() };
+    self.write_serialization_header(root_id, -1);
+
+    self.write_record_type(RecordType::MethodCall);
+    self.write_i32(message_enum.bits());
+    self.write_string_value_with_code(method_name);
+    self.write_string_value_with_code(type_name);
+    if let Some(call_context) = call_context {
+      self.write_string_value_with_code(call_context);
+    }
+    if let Some(args) = args
+      && message_enum.intersects(MessageFlags::ARGS_INLINE)
+    {
+      self.write_array_of_value_with_code(args);
+    }
+
+    self.write_call_array(root_id, &call_array);
+  }
+
+  /// 2.7 Binary Record Grammar - `methodReturn`
+  fn write_method_return(&mut self, method_return: &MethodReturn<'_>) {
+    let MethodReturn { return_value, call_context, args, exception } = method_return;
+
+    let mut message_enum = context_flags(*call_context);
+    let mut call_array = vec![];
+
+    if let Some(exception) = exception {
+      message_enum |= MessageFlags::EXCEPTION_IN_ARRAY;
+      call_array.push(exception);
+    } else {
+      match return_value {
+        None => message_enum |= MessageFlags::RETURN_VALUE_VOID,
+        Some(return_value) if is_value_with_code(return_value) => message_enum |= MessageFlags::RETURN_VALUE_INLINE,
+        Some(return_value) => {
+          message_enum |= MessageFlags::RETURN_VALUE_IN_ARRAY;
+          call_array.push(return_value);
+        },
+      }
+
+      match args {
+        None => message_enum |= MessageFlags::NO_ARGS,
+        Some(args) if args.iter().all(is_value_with_code) => message_enum |= MessageFlags::ARGS_INLINE,
+        Some(_) => message_enum |= MessageFlags::ARGS_IN_ARRAY,
+      }
+    }
+
+    // The output arguments are contained in the call array as a single array.
+    let args_array;
+    if message_enum.intersects(MessageFlags::ARGS_IN_ARRAY)
+      && let Some(args) = args
+    {
+      args_array = Value::Array(args.clone());
+      call_array.push(&args_array);
+    }
+
+    let root_id = if call_array.is_empty() { 0 } else { self.next_id() };
+    self.write_serialization_header(root_id, -1);
+
+    self.write_record_type(RecordType::MethodReturn);
+    self.write_i32(message_enum.bits());
+    if message_enum.intersects(MessageFlags::RETURN_VALUE_INLINE)
+      && let Some(return_value) = return_value
+    {
+      self.write_value_with_code(return_value);
+    }
+    if let Some(call_context) = call_context {
+      self.write_string_value_with_code(call_context);
+    }
+    if message_enum.intersects(MessageFlags::ARGS_INLINE)
+      && let Some(args) = args
+    {
+      self.write_array_of_value_with_code(args);
+    }
+
+    self.write_call_array(root_id, &call_array);
+  }
+
+  /// Serializes a [`RemotingMessage`] into bytes.
+  pub fn serialize(mut self, remoting_message: &RemotingMessage<'_>) -> Vec<u8> {
+    match remoting_message {
+      RemotingMessage::MethodCall(method_call) => self.write_method_call(method_call),
+      RemotingMessage::MethodReturn(method_return) => self.write_method_return(method_return),
+      RemotingMessage::Value(value) => self.write_root_value(value),
+    }
+
+    self.write_record_type(RecordType::MessageEnd);
+
+    self.output
+  }
+}
+
+fn context_flags(call_context: Option<&str>) -> MessageFlags {
+  if call_context.is_some() { MessageFlags::CONTEXT_INLINE } else { MessageFlags::NO_CONTEXT }
+}
+
+/// Whether a value can be written as a `ValueWithCode`.
+fn is_value_with_code(value: &Value<'_>) -> bool {
+  matches!(value, Value::String(_) | Value::Null) || primitive_type(value).is_some()
+}
+
+fn primitive_type(value: &Value<'_>) -> Option<PrimitiveType> {
+  Some(match value {
+    Value::Boolean(_) => PrimitiveType::Boolean,
+    Value::Byte(_) => PrimitiveType::Byte,
+    Value::Char(_) => PrimitiveType::Char,
+    Value::Decimal(_) => PrimitiveType::Decimal,
+    Value::Double(_) => PrimitiveType::Double,
+    Value::Int16(_) => PrimitiveType::Int16,
+    Value::Int32(_) => PrimitiveType::Int32,
+    Value::Int64(_) => PrimitiveType::Int64,
+    Value::SByte(_) => PrimitiveType::SByte,
+    Value::Single(_) => PrimitiveType::Single,
+    Value::TimeSpan(_) => PrimitiveType::TimeSpan,
+    Value::DateTime(_) => PrimitiveType::DateTime,
+    Value::UInt16(_) => PrimitiveType::UInt16,
+    Value::UInt32(_) => PrimitiveType::UInt32,
+    Value::UInt64(_) => PrimitiveType::UInt64,
+    Value::Object(_) | Value::Array(_) | Value::Bytes(_) | Value::String(_) | Value::Null => return None,
+  })
+}
+
+/// The primitive type of an array consisting only of primitive values of the same type.
+fn array_primitive_type(array: &[Value<'_>]) -> Option<PrimitiveType> {
+  let primitive_type = array.first().and_then(primitive_type)?;
+  array.iter().all(|value| self::primitive_type(value) == Some(primitive_type)).then_some(primitive_type)
+}
+
+fn system_class_name(value: &Value<'_>) -> &'static str {
+  match value {
+    Value::Boolean(_) => "System.Boolean",
+    Value::Byte(_) => "System.Byte",
+    Value::Char(_) => "System.Char",
+    Value::Decimal(_) => "System.Decimal",
+    Value::Double(_) => "System.Double",
+    Value::Int16(_) => "System.Int16",
+    Value::Int32(_) => "System.Int32",
+    Value::Int64(_) => "System.Int64",
+    Value::SByte(_) => "System.SByte",
+    Value::Single(_) => "System.Single",
+    Value::TimeSpan(_) => "System.TimeSpan",
+    Value::DateTime(_) => "System.DateTime",
+    Value::UInt16(_) => "System.UInt16",
+    Value::UInt32(_) => "System.UInt32",
+    Value::UInt64(_) => "System.UInt64",
+    Value::Object(_) | Value::Array(_) | Value::Bytes(_) | Value::String(_) | Value::Null => unreachable!(),
+  }
+}
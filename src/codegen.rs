@@ -0,0 +1,623 @@
+//! Generation of Rust types from sample payloads.
+//!
+//! The types of members are inferred from the values seen across all samples. Members which are
+//! null or missing in any sample become `Option`s, members holding different classes become enums
+//! with variants chosen by class name, and well-known collections become `Vec`s and `HashMap`s.
+//!
+//! Members which are null in every sample get the type declared in the `MemberTypeInfo` of samples
+//! added with [`Generator::add_sample`], or the type argument of a `Nullable<T>`.
+//!
+//! # Example
+//!
+//! ```
+//! use nrbf::{RemotingMessage, Value, codegen::Generator, value::Object};
+//!
+//! let order = Object {
+//!   class: "Contoso.Order",
+//!   library: Some("Contoso.Model"),
+//!   members: [("<Id>k__BackingField", Value::Int32(7)), ("note", Value::Null)].into(),
+//! };
+//!
+//! let mut generator = Generator::new();
+//! generator.add_value(&Value::Object(order));
+//!
+//! let source = generator.generate();
+//! assert!(source.contains("pub struct Order {"));
+//! assert!(source.contains("  #[serde(rename = \"<Id>k__BackingField\")]\n  pub id: i32,"));
+//! assert!(source.contains("  pub note: Option<serde::de::IgnoredAny>,"));
+//! ```
+
+use std::{
+  collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+  fmt::Write,
+};
+
+use crate::{
+  Error, MemberNames, RemotingMessage, Value,
+  schema::{MemberType, PrimitiveType},
+  value::{Object, full_type_name},
+};
+
+/// Rust keywords which need to be escaped as raw identifiers.
+const KEYWORDS: &[&str] = &[
+  "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+  "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "static",
+  "struct", "trait", "true", "try", "type", "unsafe", "use", "where", "while", "yield",
+];
+
+/// Classes which deserialize as strings.
+const STRING_CLASSES: &[&str] = &[
+  "System.Decimal",
+  "System.Guid",
+  "System.DateTimeOffset",
+  "System.Version",
+  "System.Uri",
+  "System.Globalization.CultureInfo",
+  "System.Drawing.Color",
+];
+
+/// The types seen for a member or an array element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct TypeShape {
+  /// Whether a null value was seen.
+  nullable: bool,
+  /// The Rust types of the primitive values seen.
+  primitives: BTreeSet<&'static str>,
+  /// The classes seen.
+  classes: BTreeSet<String>,
+  /// The element type of the arrays and sequences seen.
+  array: Option<Box<TypeShape>>,
+  /// The key and value types of the maps seen.
+  map: Option<Box<(TypeShape, TypeShape)>>,
+  /// The item types of the tuples seen.
+  tuple: Option<Vec<TypeShape>>,
+}
+
+impl TypeShape {
+  fn primitive(ty: &'static str) -> Self {
+    Self { primitives: BTreeSet::from([ty]), ..Default::default() }
+  }
+
+  fn array(element: TypeShape) -> Self {
+    Self { array: Some(Box::new(element)), ..Default::default() }
+  }
+
+  /// Get the shape of a declared member type, or `None` if it does not tell the type.
+  fn declared(member_type: &MemberType<'_>) -> Option<Self> {
+    Some(match member_type {
+      MemberType::Primitive(primitive_type) => Self::primitive(primitive_type_name(*primitive_type)?),
+      MemberType::String => Self::primitive("String"),
+      MemberType::SystemClass(class) => Self::system_class(class)?,
+      MemberType::Class { name, .. } => Self { classes: BTreeSet::from([(*name).to_owned()]), ..Default::default() },
+      MemberType::StringArray => Self::array(Self::primitive("String")),
+      MemberType::PrimitiveArray(primitive_type) => Self::array(Self::primitive(primitive_type_name(*primitive_type)?)),
+      MemberType::Object | MemberType::ObjectArray => return None,
+    })
+  }
+
+  /// Get the shape of a system class from its name, e.g. `i32` for `System.Nullable`1[[System.Int32, mscorlib]]`.
+  fn system_class(class: &str) -> Option<Self> {
+    if STRING_CLASSES.contains(&class) {
+      return Some(Self::primitive("String"))
+    }
+
+    if let Some(primitive) = system_primitive(class) {
+      return Some(Self::primitive(primitive))
+    }
+
+    let (definition, argument) = class.split_once("[[")?;
+    let argument = Self::system_class(full_type_name(argument.strip_suffix("]]")?))?;
+
+    match definition {
+      "System.Nullable`1" => Some(Self { nullable: true, ..argument }),
+      "System.Collections.Generic.List`1"
+      | "System.Collections.Generic.HashSet`1"
+      | "System.Collections.Generic.SortedSet`1"
+      | "System.Collections.Generic.LinkedList`1"
+      | "System.Collections.Generic.Queue`1"
+      | "System.Collections.Generic.Stack`1" => Some(Self::array(argument)),
+      _ => None,
+    }
+  }
+
+  fn merge(&mut self, other: TypeShape) {
+    self.nullable |= other.nullable;
+    self.primitives.extend(other.primitives);
+    self.classes.extend(other.classes);
+
+    if let Some(array) = other.array {
+      match &mut self.array {
+        Some(element) => element.merge(*array),
+        None => self.array = Some(array),
+      }
+    }
+
+    if let Some(map) = other.map {
+      match &mut self.map {
+        Some(entry) => {
+          entry.0.merge(map.0);
+          entry.1.merge(map.1);
+        },
+        None => self.map = Some(map),
+      }
+    }
+
+    if let Some(tuple) = other.tuple {
+      match &mut self.tuple {
+        Some(items) if items.len() == tuple.len() => {
+          items.iter_mut().zip(tuple).for_each(|(item, other)| item.merge(other))
+        },
+        // Tuples of different lengths cannot be represented by a single type.
+        Some(_) => {
+          self.primitives.insert("serde::de::IgnoredAny");
+        },
+        None => self.tuple = Some(tuple),
+      }
+    }
+  }
+
+  fn kinds(&self) -> usize {
+    self.primitives.len()
+      + usize::from(!self.classes.is_empty())
+      + usize::from(self.array.is_some())
+      + usize::from(self.map.is_some())
+      + usize::from(self.tuple.is_some())
+  }
+}
+
+/// The members seen for a class.
+#[derive(Debug, Clone, Default)]
+struct ClassShape {
+  /// The library names seen.
+  libraries: BTreeSet<String>,
+  /// The number of instances seen.
+  instances: usize,
+  /// The member types and the number of instances containing the member.
+  members: BTreeMap<String, (TypeShape, usize)>,
+  /// The member types declared in the `MemberTypeInfo` of the samples.
+  declared: BTreeMap<String, TypeShape>,
+}
+
+/// A generator of Rust types with serde attributes from sample payloads.
+#[derive(Debug, Clone, Default)]
+pub struct Generator {
+  classes: BTreeMap<String, ClassShape>,
+}
+
+impl Generator {
+  /// Create a generator without any samples.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Add a sample payload.
+  ///
+  /// The arguments and return values of method calls and returns are added as well. The member types
+  /// declared in the payload are used for members which are null in every sample.
+  pub fn add_sample<'i>(&mut self, bytes: &'i [u8]) -> Result<(), Error<'i>> {
+    match RemotingMessage::parse(bytes)? {
+      RemotingMessage::Value(value) => self.add_value(&value),
+      RemotingMessage::MethodCall(method_call) => method_call.args.iter().flatten().for_each(|arg| self.add_value(arg)),
+      RemotingMessage::MethodReturn(method_return) => {
+        method_return.return_value.iter().chain(method_return.args.iter().flatten()).for_each(|v| self.add_value(v))
+      },
+    }
+
+    for class in crate::schema(bytes)?.classes {
+      let Some(shape) = self.classes.get_mut(class.name) else { continue };
+
+      for member in class.members {
+        if let Some(declared) = member.member_type.as_ref().and_then(TypeShape::declared) {
+          shape.declared.entry(member.name.to_owned()).or_default().merge(declared);
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Add a sample value.
+  pub fn add_value(&mut self, value: &Value<'_>) {
+    self.observe(value);
+  }
+
+  /// Infer the type of a value, recording the classes it contains.
+  fn observe(&mut self, value: &Value<'_>) -> TypeShape {
+    let primitive = TypeShape::primitive;
+
+    match value {
+      Value::Object(object) => self.observe_object(object),
+      Value::Array(values) => self.observe_array(values.iter()),
+      Value::Bytes(_) => TypeShape::array(primitive("u8")),
+      Value::Boolean(_) => primitive("bool"),
+      Value::Byte(_) => primitive("u8"),
+      Value::Char(_) => primitive("char"),
+      Value::Decimal(_) | Value::DateTime(_) | Value::String(_) => primitive("String"),
+      Value::Double(_) => primitive("f64"),
+      Value::Int16(_) => primitive("i16"),
+      Value::Int32(_) => primitive("i32"),
+      Value::Int64(_) | Value::TimeSpan(_) => primitive("i64"),
+      Value::SByte(_) => primitive("i8"),
+      Value::Single(_) => primitive("f32"),
+      Value::UInt16(_) => primitive("u16"),
+      Value::UInt32(_) => primitive("u32"),
+      Value::UInt64(_) => primitive("u64"),
+      Value::Null => TypeShape { nullable: true, ..Default::default() },
+    }
+  }
+
+  fn observe_array<'a, 'i: 'a>(&mut self, values: impl Iterator<Item = &'a Value<'i>>) -> TypeShape {
+    let mut element = TypeShape::default();
+    for value in values {
+      element.merge(self.observe(value));
+    }
+
+    TypeShape::array(element)
+  }
+
+  /// Infer the element type of the items of a collection, starting at `head` and wrapping around
+  /// like in a `Queue`, and taking `len` items if given.
+  fn observe_items(&mut self, items: Option<&Value<'_>>, head: usize, len: Option<usize>) -> TypeShape {
+    match items {
+      Some(Value::Array(items)) => {
+        let len = len.unwrap_or(items.len()).min(items.len());
+        self.observe_array(items.iter().cycle().skip(head).take(len))
+      },
+      Some(value @ Value::Bytes(_)) => self.observe(value),
+      _ => TypeShape::array(TypeShape::default()),
+    }
+  }
+
+  fn observe_map<'a, 'i: 'a>(&mut self, entries: impl Iterator<Item = (&'a Value<'i>, &'a Value<'i>)>) -> TypeShape {
+    let (mut key, mut value) = (TypeShape::default(), TypeShape::default());
+    for (k, v) in entries {
+      key.merge(self.observe(k));
+      value.merge(self.observe(v));
+    }
+
+    TypeShape { map: Some(Box::new((key, value))), ..Default::default() }
+  }
+
+  fn observe_object(&mut self, object: &Object<'_>) -> TypeShape {
+    let Object { class, library, members } = object;
+    let class_name = class.split_once('`').map_or(*class, |(name, _)| name);
+    let array = |name| match members.get(name) {
+      Some(Value::Array(items)) => items.as_slice(),
+      _ => &[],
+    };
+    let size = |name| match members.get(name) {
+      Some(Value::Int32(size)) => usize::try_from(*size).unwrap_or_default(),
+      _ => 0,
+    };
+
+    if class_name == "System.Nullable" {
+      return match members.get("value") {
+        Some(value) if members.get("hasValue") == Some(&Value::Boolean(true)) => self.observe(value),
+        _ => TypeShape::system_class(class).unwrap_or(TypeShape { nullable: true, ..Default::default() }),
+      }
+    }
+
+    if members.len() == 1
+      && let Some(value) = members.get("value__").or(if library.is_none() { members.get("m_value") } else { None })
+    {
+      return self.observe(value)
+    }
+
+    if STRING_CLASSES.contains(&class_name) {
+      return TypeShape { primitives: BTreeSet::from(["String"]), ..Default::default() }
+    }
+
+    match class_name {
+      "System.Collections.Generic.List" | "System.Collections.ArrayList" => {
+        return self.observe_items(members.get("_items"), 0, Some(size("_size")))
+      },
+      "System.Collections.Generic.HashSet" => return self.observe_array(array("Elements").iter()),
+      "System.Collections.Generic.SortedSet" => return self.observe_array(array("Items").iter()),
+      "System.Collections.Generic.LinkedList" => return self.observe_array(array("Data").iter()),
+      "System.Collections.Queue" | "System.Collections.Generic.Queue" => {
+        return self.observe_items(members.get("_array"), size("_head"), Some(size("_size")))
+      },
+      "System.Collections.Stack" | "System.Collections.Generic.Stack" => {
+        return self.observe_items(members.get("_array"), 0, Some(size("_size")))
+      },
+      "System.Collections.Hashtable" => return self.observe_map(array("Keys").iter().zip(array("Values"))),
+      "System.Collections.Generic.SortedList" => {
+        return self.observe_map(array("keys").iter().zip(array("values")).take(size("_size")))
+      },
+      "System.Collections.Generic.Dictionary" => {
+        let pairs = array("KeyValuePairs").iter().filter_map(|pair| match pair {
+          Value::Object(pair) => pair.members.get("key").zip(pair.members.get("value")),
+          _ => None,
+        });
+        return self.observe_map(pairs.collect::<Vec<_>>().into_iter())
+      },
+      "System.Collections.Generic.KeyValuePair" | "System.Tuple" | "System.ValueTuple" => {
+        let prefix = match class_name {
+          "System.Collections.Generic.KeyValuePair" => None,
+          "System.Tuple" => Some("m_Item"),
+          _ => Some("Item"),
+        };
+        let items = match prefix {
+          None => vec![members.get("key"), members.get("value")],
+          Some(prefix) => (1..=7).map(|i| members.get(format!("{}{}", prefix, i).as_str())).collect(),
+        };
+
+        let tuple = items.into_iter().flatten().map(|item| self.observe(item)).collect::<Vec<_>>();
+        return TypeShape { tuple: Some(tuple), ..Default::default() }
+      },
+      _ => (),
+    }
+
+    let member_shapes =
+      members.iter().map(|(name, value)| ((*name).to_owned(), self.observe(value))).collect::<Vec<_>>();
+
+    let shape = self.classes.entry((*class).to_owned()).or_default();
+    shape.instances += 1;
+    shape.libraries.extend(library.map(str::to_owned));
+    for (name, member_shape) in member_shapes {
+      let (member, count) = shape.members.entry(name).or_default();
+      member.merge(member_shape);
+      *count += 1;
+    }
+
+    TypeShape { classes: BTreeSet::from([(*class).to_owned()]), ..Default::default() }
+  }
+
+  /// Generate Rust source code for all classes seen in the samples.
+  pub fn generate(&self) -> String {
+    let names = type_names(self.classes.keys().map(String::as_str));
+    let mut enums = Vec::new();
+    let mut source = String::new();
+
+    let uses_map = self.classes.values().flat_map(|class| class.members.values()).any(|(shape, _)| uses_map(shape));
+    if uses_map {
+      source.push_str("use std::collections::HashMap;\n\n");
+    }
+    source.push_str("use serde::Deserialize;\n");
+
+    for (class, shape) in &self.classes {
+      let name = &names[class.as_str()];
+
+      source.push('\n');
+      writeln!(source, "/// `{}`", class).unwrap();
+      for library in &shape.libraries {
+        writeln!(source, "///\n/// Library: `{}`", library).unwrap();
+      }
+      source.push_str("#[derive(Debug, Clone, PartialEq, Deserialize)]\n");
+      writeln!(source, "pub struct {} {{", name).unwrap();
+
+      let fields = field_names(shape.members.keys().map(String::as_str));
+      for (member, (member_shape, count)) in &shape.members {
+        let field = &fields[member.as_str()];
+        let missing = *count < shape.instances;
+
+        // Use the declared type if only nulls were seen, ignoring classes which were never seen.
+        let declared = shape.declared.get(member).filter(|_| member_shape.kinds() == 0).map(|declared| {
+          let mut declared = TypeShape { nullable: member_shape.nullable, ..declared.clone() };
+          declared.classes.retain(|class| names.contains_key(class.as_str()));
+          declared
+        });
+        let member_shape = declared.as_ref().unwrap_or(member_shape);
+
+        let mut context =
+          TypeContext { names: &names, enums: &mut enums, enum_name: format!("{}{}", name, pascal_case(field)) };
+        let mut ty = context.rust_type(member_shape);
+        if missing && !member_shape.nullable {
+          ty = format!("Option<{}>", ty);
+        }
+
+        let mut attributes = Vec::new();
+        if field.trim_start_matches("r#") != member {
+          attributes.push(format!("rename = \"{}\"", member.escape_default()));
+        }
+        if missing {
+          attributes.push(String::from("default"));
+        }
+        if !attributes.is_empty() {
+          writeln!(source, "  #[serde({})]", attributes.join(", ")).unwrap();
+        }
+        writeln!(source, "  pub {}: {},", field, ty).unwrap();
+      }
+
+      source.push_str("}\n");
+    }
+
+    for (name, variants) in enums {
+      source.push('\n');
+      source.push_str("#[derive(Debug, Clone, PartialEq, Deserialize)]\n");
+      writeln!(source, "pub enum {} {{", name).unwrap();
+      for (class, variant) in variants {
+        writeln!(source, "  #[serde(rename = \"{}\")]", class_name(&class)).unwrap();
+        writeln!(source, "  {}({}),", variant, variant).unwrap();
+      }
+      source.push_str("}\n");
+    }
+
+    source
+  }
+}
+
+/// The state used while rendering a member type.
+struct TypeContext<'a> {
+  names: &'a HashMap<&'a str, String>,
+  enums: &'a mut Vec<(String, Vec<(String, String)>)>,
+  enum_name: String,
+}
+
+impl TypeContext<'_> {
+  fn rust_type(&mut self, shape: &TypeShape) -> String {
+    let ty = match shape.kinds() {
+      0 => String::from("serde::de::IgnoredAny"),
+      1 if shape.classes.len() > 1 => {
+        let variants = shape.classes.iter().map(|class| (class.clone(), self.names[class.as_str()].clone())).collect();
+        self.enums.push((self.enum_name.clone(), variants));
+        self.enum_name.clone()
+      },
+      1 => {
+        if let Some(primitive) = shape.primitives.first() {
+          (*primitive).to_owned()
+        } else if let Some(class) = shape.classes.first() {
+          self.names[class.as_str()].clone()
+        } else if let Some(element) = &shape.array {
+          self.enum_name.push_str("Item");
+          format!("Vec<{}>", self.rust_type(element))
+        } else if let Some(entry) = &shape.map {
+          self.enum_name.push_str("Entry");
+          format!("HashMap<{}, {}>", self.rust_type(&entry.0), self.rust_type(&entry.1))
+        } else if let Some(items) = &shape.tuple {
+          let items = items.iter().map(|item| self.rust_type(item)).collect::<Vec<_>>();
+          if items.len() == 1 { format!("({},)", items[0]) } else { format!("({})", items.join(", ")) }
+        } else {
+          unreachable!()
+        }
+      },
+      // Values of different kinds cannot be represented by a single type.
+      _ => String::from("serde::de::IgnoredAny"),
+    };
+
+    if shape.nullable { format!("Option<{}>", ty) } else { ty }
+  }
+}
+
+/// Get the Rust type of a primitive type, or `None` for `Null` and `String`.
+fn primitive_type_name(primitive_type: PrimitiveType) -> Option<&'static str> {
+  Some(match primitive_type {
+    PrimitiveType::Boolean => "bool",
+    PrimitiveType::Byte => "u8",
+    PrimitiveType::Char => "char",
+    PrimitiveType::Decimal | PrimitiveType::DateTime => "String",
+    PrimitiveType::Double => "f64",
+    PrimitiveType::Int16 => "i16",
+    PrimitiveType::Int32 => "i32",
+    PrimitiveType::Int64 | PrimitiveType::TimeSpan => "i64",
+    PrimitiveType::SByte => "i8",
+    PrimitiveType::Single => "f32",
+    PrimitiveType::UInt16 => "u16",
+    PrimitiveType::UInt32 => "u32",
+    PrimitiveType::UInt64 => "u64",
+    PrimitiveType::Null | PrimitiveType::String => return None,
+  })
+}
+
+/// Get the Rust type of a primitive system class, e.g. `i32` for `System.Int32`.
+fn system_primitive(class: &str) -> Option<&'static str> {
+  Some(match class {
+    "System.Boolean" => "bool",
+    "System.Byte" => "u8",
+    "System.Char" => "char",
+    "System.Decimal" | "System.DateTime" | "System.String" => "String",
+    "System.Double" => "f64",
+    "System.Int16" => "i16",
+    "System.Int32" => "i32",
+    "System.Int64" | "System.TimeSpan" => "i64",
+    "System.SByte" => "i8",
+    "System.Single" => "f32",
+    "System.UInt16" => "u16",
+    "System.UInt32" => "u32",
+    "System.UInt64" => "u64",
+    _ => return None,
+  })
+}
+
+fn uses_map(shape: &TypeShape) -> bool {
+  shape.map.is_some() || shape.array.as_deref().is_some_and(uses_map) || shape.tuple.iter().flatten().any(uses_map)
+}
+
+/// Get the class name without generic arguments, e.g. `Contoso.Box`1`.
+fn class_name(class: &str) -> &str {
+  class.split_once('[').map_or(class, |(name, _)| name)
+}
+
+/// Choose unique type names for classes, e.g. `Order` for `Contoso.Model.Order`.
+///
+/// Namespaces are prepended to names which would be ambiguous otherwise.
+fn type_names<'a>(classes: impl Iterator<Item = &'a str>) -> HashMap<&'a str, String> {
+  let classes = classes.collect::<Vec<_>>();
+
+  let mut names = HashMap::new();
+  let mut taken = HashSet::new();
+
+  for class in &classes {
+    let mut name = String::new();
+
+    for segment in segments(class).iter().rev() {
+      name.insert_str(0, segment);
+
+      let ambiguous =
+        classes.iter().filter(|other| *other != class).any(|other| segments_end_with(&segments(other), &name));
+      if !ambiguous {
+        break
+      }
+    }
+
+    let mut unique = name.clone();
+    let mut i = 2;
+    while !taken.insert(unique.clone()) {
+      unique = format!("{}{}", name, i);
+      i += 1;
+    }
+
+    names.insert(*class, unique);
+  }
+
+  names
+}
+
+/// Split a class name into PascalCase segments, e.g. `["Contoso", "Order", "Line"]` for `Contoso.Order+Line`.
+fn segments(class: &str) -> Vec<String> {
+  class_name(class).split(['.', '+']).map(|segment| pascal_case(segment.split('`').next().unwrap_or(segment))).collect()
+}
+
+/// Check whether the name is made of the trailing segments of a class name.
+fn segments_end_with(segments: &[String], name: &str) -> bool {
+  let mut suffix = String::new();
+  segments.iter().rev().any(|segment| {
+    suffix.insert_str(0, segment);
+    suffix == name
+  })
+}
+
+/// Choose unique snake case field names for members.
+fn field_names<'a>(members: impl Iterator<Item = &'a str>) -> HashMap<&'a str, String> {
+  let mut names = HashMap::new();
+  let mut taken = HashSet::new();
+
+  for member in members {
+    let mut name = MemberNames::SnakeCase
+      .apply(member)
+      .chars()
+      .map(|c| if c.is_alphanumeric() || c == '_' { c.to_ascii_lowercase() } else { '_' })
+      .collect::<String>();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+      name.insert(0, '_');
+    }
+
+    let mut unique = name.clone();
+    let mut i = 2;
+    while !taken.insert(unique.clone()) {
+      unique = format!("{}_{}", name, i);
+      i += 1;
+    }
+
+    if KEYWORDS.contains(&unique.as_str()) {
+      unique.insert_str(0, "r#");
+    }
+
+    names.insert(member, unique);
+  }
+
+  names
+}
+
+/// Convert a name to PascalCase, e.g. `order_id` to `OrderId`.
+fn pascal_case(name: &str) -> String {
+  name
+    .trim_start_matches("r#")
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let mut chars = part.chars();
+      chars.next().map(|c| c.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+    })
+    .collect()
+}
@@ -0,0 +1,326 @@
+//! Compatibility checks between the schemas of two payload versions.
+//!
+//! Changes are classified following the version tolerant serialization of `BinaryFormatter`:
+//! unknown members are ignored, and members missing from a payload are only accepted if they are
+//! marked with `[OptionalField]`. Adding a member is therefore non-breaking as long as it is marked
+//! as optional, while removing a member or changing its type breaks readers of the old version.
+//! Assemblies are resolved by their simple name, so changing an assembly version is non-breaking,
+//! whereas moving a class to another namespace or assembly requires a `SerializationBinder` and is
+//! breaking. A class is only considered moved if it keeps its simple name and its members, other
+//! missing classes are reported as removed and added.
+
+use std::{borrow::Cow, collections::BTreeMap, fmt};
+
+use crate::{
+  schema::{ClassMetadata, MemberType, Schema},
+  type_registry::assembly_version,
+};
+
+/// The name of the system library.
+const SYSTEM_LIBRARY: &str = "mscorlib";
+
+/// Compare the schema of an old payload with the schema of a new payload.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use nrbf::{RemotingMessage, Value, compat::Change, value::Object};
+///
+/// let order = |library, members: Vec<(&'static str, Value<'static>)>| {
+///   RemotingMessage::Value(Value::Object(Object {
+///     class: "Contoso.Order",
+///     library: Some(library),
+///     members: HashMap::from_iter(members),
+///   }))
+///   .to_bytes()
+/// };
+///
+/// let old = order("Contoso, Version=1.0.0.0", vec![("id", Value::Int32(1)), ("note", Value::Null)]);
+/// let new = order("Contoso, Version=2.0.0.0", vec![("id", Value::Int32(1)), ("total", Value::Double(2.5))]);
+///
+/// let changes = nrbf::compat::compare(&nrbf::schema(&old).unwrap(), &nrbf::schema(&new).unwrap());
+/// assert_eq!(
+///   changes.iter().map(|change| (change.to_string(), change.is_breaking())).collect::<Vec<_>>(),
+///   vec![
+///     (String::from("assembly Contoso changed version from 1.0.0.0 to 2.0.0.0"), false),
+///     (String::from("Contoso.Order: member note removed"), true),
+///     (String::from("Contoso.Order: member total added"), false),
+///   ],
+/// );
+/// assert!(matches!(changes[1], Change::MemberRemoved { member: "note", .. }));
+/// ```
+pub fn compare<'a>(old: &Schema<'a>, new: &Schema<'a>) -> Vec<Change<'a>> {
+  let mut changes = Vec::new();
+
+  let old_versions = assembly_versions(old);
+  let new_versions = assembly_versions(new);
+  for (assembly, old_version) in old_versions {
+    if let Some(&new_version) = new_versions.get(assembly)
+      && old_version != new_version
+    {
+      changes.push(Change::AssemblyVersionChanged { assembly, old_version, new_version });
+    }
+  }
+
+  let mut old_classes = classes(old);
+  let mut new_classes = classes(new);
+
+  let mut pairs = Vec::new();
+  old_classes.retain(|key, old_class| match new_classes.remove(key) {
+    Some(new_class) => {
+      pairs.push((*old_class, new_class));
+      false
+    },
+    None => true,
+  });
+
+  // Classes which are missing from one version are moved if they have the same simple name and the
+  // same members.
+  let is_moved = |old_class: &ClassMetadata<'_>, new_class: &ClassMetadata<'_>| {
+    simple_name(old_class.name) == simple_name(new_class.name)
+      && old_class.members.len() == new_class.members.len()
+      && old_class.members.iter().all(|member| new_class.member(member.name).is_some())
+  };
+  old_classes.retain(|_, old_class| {
+    let moved = new_classes.iter().find(|(_, new_class)| is_moved(old_class, new_class)).map(|(key, _)| key.clone());
+
+    match moved.and_then(|key| new_classes.remove(&key)) {
+      Some(new_class) => {
+        changes.push(Change::ClassRenamed {
+          old_class: old_class.name,
+          old_assembly: assembly_name(old, old_class),
+          new_class: new_class.name,
+          new_assembly: assembly_name(new, new_class),
+        });
+        pairs.push((*old_class, new_class));
+        false
+      },
+      None => true,
+    }
+  });
+
+  for old_class in old_classes.into_values() {
+    changes.push(Change::ClassRemoved { class: old_class.name, assembly: assembly_name(old, old_class) });
+  }
+
+  for new_class in new_classes.into_values() {
+    changes.push(Change::ClassAdded { class: new_class.name, assembly: assembly_name(new, new_class) });
+  }
+
+  for (old_class, new_class) in pairs {
+    let class = new_class.name;
+
+    for old_member in &old_class.members {
+      match new_class.member(old_member.name) {
+        Some(new_member) => {
+          if let (Some(old_type), Some(new_type)) = (&old_member.member_type, &new_member.member_type)
+            && !same_type(old, old_type, new, new_type)
+          {
+            changes.push(Change::MemberTypeChanged {
+              class,
+              member: new_member.name,
+              old_type: old_type.clone(),
+              new_type: new_type.clone(),
+            });
+          }
+        },
+        None => changes.push(Change::MemberRemoved { class, member: old_member.name }),
+      }
+    }
+
+    for new_member in &new_class.members {
+      if old_class.member(new_member.name).is_none() {
+        changes.push(Change::MemberAdded { class, member: new_member.name });
+      }
+    }
+  }
+
+  changes
+}
+
+/// A change between two payload versions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<'a> {
+  /// A member was added to a class.
+  MemberAdded {
+    /// The class name.
+    class: &'a str,
+    /// The member name.
+    member: &'a str,
+  },
+  /// A member was removed from a class.
+  MemberRemoved {
+    /// The class name.
+    class: &'a str,
+    /// The member name.
+    member: &'a str,
+  },
+  /// The `BinaryType` or primitive type of a member changed.
+  ///
+  /// Library IDs of [`MemberType::Class`] types refer to the libraries of the respective schema.
+  MemberTypeChanged {
+    /// The class name.
+    class: &'a str,
+    /// The member name.
+    member: &'a str,
+    /// The old member type.
+    old_type: MemberType<'a>,
+    /// The new member type.
+    new_type: MemberType<'a>,
+  },
+  /// A class was added.
+  ClassAdded {
+    /// The class name.
+    class: &'a str,
+    /// The simple name of the assembly.
+    assembly: &'a str,
+  },
+  /// A class was removed.
+  ClassRemoved {
+    /// The class name.
+    class: &'a str,
+    /// The simple name of the assembly.
+    assembly: &'a str,
+  },
+  /// A class was moved to another namespace or assembly, keeping its simple name and members.
+  ClassRenamed {
+    /// The old class name.
+    old_class: &'a str,
+    /// The simple name of the old assembly.
+    old_assembly: &'a str,
+    /// The new class name.
+    new_class: &'a str,
+    /// The simple name of the new assembly.
+    new_assembly: &'a str,
+  },
+  /// The version of an assembly changed.
+  AssemblyVersionChanged {
+    /// The simple name of the assembly.
+    assembly: &'a str,
+    /// The old version.
+    old_version: &'a str,
+    /// The new version.
+    new_version: &'a str,
+  },
+}
+
+impl Change<'_> {
+  /// Check whether the change prevents payloads of one version from being read by the other version.
+  ///
+  /// Added members are assumed to be marked with `[OptionalField]`.
+  pub fn is_breaking(&self) -> bool {
+    match self {
+      Self::MemberAdded { .. } | Self::ClassAdded { .. } | Self::AssemblyVersionChanged { .. } => false,
+      Self::MemberRemoved { .. }
+      | Self::MemberTypeChanged { .. }
+      | Self::ClassRemoved { .. }
+      | Self::ClassRenamed { .. } => true,
+    }
+  }
+}
+
+impl fmt::Display for Change<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::MemberAdded { class, member } => write!(f, "{}: member {} added", class, member),
+      Self::MemberRemoved { class, member } => write!(f, "{}: member {} removed", class, member),
+      Self::MemberTypeChanged { class, member, old_type, new_type } => {
+        write!(f, "{}: member {} changed type from {:?} to {:?}", class, member, old_type, new_type)
+      },
+      Self::ClassAdded { class, assembly } => write!(f, "class {} in {} added", class, assembly),
+      Self::ClassRemoved { class, assembly } => write!(f, "class {} in {} removed", class, assembly),
+      Self::ClassRenamed { old_class, old_assembly, new_class, new_assembly } => {
+        write!(f, "class {} in {} renamed to {} in {}", old_class, old_assembly, new_class, new_assembly)
+      },
+      Self::AssemblyVersionChanged { assembly, old_version, new_version } => {
+        write!(f, "assembly {} changed version from {} to {}", assembly, old_version, new_version)
+      },
+    }
+  }
+}
+
+/// Get the versions of all libraries by their simple name.
+fn assembly_versions<'a>(schema: &Schema<'a>) -> BTreeMap<&'a str, &'a str> {
+  schema
+    .libraries
+    .iter()
+    .filter_map(|library| Some((simple_assembly_name(library.name), assembly_version(library.name)?)))
+    .collect()
+}
+
+/// Get the classes of a schema by their name without versions and the simple name of their assembly.
+fn classes<'s, 'a>(schema: &'s Schema<'a>) -> BTreeMap<(Cow<'a, str>, &'a str), &'s ClassMetadata<'a>> {
+  let mut classes = BTreeMap::new();
+
+  for class in &schema.classes {
+    classes.entry((unversioned(class.name), assembly_name(schema, class))).or_insert(class);
+  }
+
+  classes
+}
+
+/// Get the simple name of the assembly of a class.
+fn assembly_name<'a>(schema: &Schema<'a>, class: &ClassMetadata<'a>) -> &'a str {
+  match class.library_id {
+    Some(library_id) => library_name(schema, library_id).unwrap_or_default(),
+    None => SYSTEM_LIBRARY,
+  }
+}
+
+/// Get the simple name of the assembly of a library.
+fn library_name<'a>(schema: &Schema<'a>, library_id: u32) -> Option<&'a str> {
+  schema.library(library_id).map(|library| simple_assembly_name(library.name))
+}
+
+/// Get the simple name of an assembly, e.g. `Contoso.Model` for `Contoso.Model, Version=1.0.0.0`.
+fn simple_assembly_name(assembly: &str) -> &str {
+  assembly.split(',').next().unwrap_or(assembly).trim()
+}
+
+/// Get the class name without its namespace, e.g. `Order` for `Contoso.Model.Order`.
+fn simple_name(class: &str) -> &str {
+  let name = class.split_once('[').map_or(class, |(name, _)| name);
+  name.rsplit(['.', '+']).next().unwrap_or(name)
+}
+
+/// Remove the version, culture and public key token from the assemblies of generic type arguments.
+fn unversioned(class: &str) -> Cow<'_, str> {
+  if !class.contains('[') {
+    return Cow::Borrowed(class)
+  }
+
+  let mut name = String::with_capacity(class.len());
+  for (i, part) in class.split(',').enumerate() {
+    let trimmed = part.trim_start();
+    if ["Version=", "Culture=", "PublicKeyToken="].iter().any(|prefix| trimmed.starts_with(prefix)) {
+      // Keep the closing brackets of the type argument.
+      name.push_str(trimmed.trim_start_matches(|c| c != ']'));
+    } else {
+      if i > 0 {
+        name.push(',');
+      }
+      name.push_str(part);
+    }
+  }
+
+  Cow::Owned(name)
+}
+
+/// Compare member types, resolving class types by their assembly instead of their library ID.
+fn same_type(old: &Schema<'_>, old_type: &MemberType<'_>, new: &Schema<'_>, new_type: &MemberType<'_>) -> bool {
+  match (old_type, new_type) {
+    (
+      MemberType::Class { name: old_name, library_id: old_library_id },
+      MemberType::Class { name: new_name, library_id: new_library_id },
+    ) => {
+      unversioned(old_name) == unversioned(new_name)
+        && library_name(old, *old_library_id) == library_name(new, *new_library_id)
+    },
+    (MemberType::SystemClass(old_name), MemberType::SystemClass(new_name)) => {
+      unversioned(old_name) == unversioned(new_name)
+    },
+    (old_type, new_type) => old_type == new_type,
+  }
+}
@@ -24,77 +24,17 @@ impl Decimal {
       Err(nom::Err::Failure(error_position!(err_input, ExpectedPrimitive(PrimitiveType::Decimal))))
     }
   }
-}
-
-#[cfg(feature = "serde")]
-impl<'de> serde::Deserialize<'de> for Decimal {
-  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-  where
-    D: serde::de::Deserializer<'de>,
-  {
-    use serde::de::{self, Error, MapAccess, Unexpected};
-
-    struct Visitor;
-
-    impl<'de> de::Visitor<'de> for Visitor {
-      type Value = rust_decimal::Decimal;
-
-      fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("Decimal")
-      }
-
-      fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
-      where
-        A: MapAccess<'de>,
-      {
-        use serde::Deserialize;
-
-        #[derive(Deserialize)]
-        enum Value {
-          Flags(i32),
-          Hi32(u32),
-          Lo64(u64),
-        }
-
-        let mut flags = None;
-        let mut hi32 = None;
-        let mut lo64 = None;
-        let mut values = 0;
-
-        while let Some((key, value)) = map.next_entry::<&str, Value>()? {
-          match (key, value) {
-            ("_flags", Value::Flags(v)) if flags.is_none() => {
-              flags = Some(v);
-            },
-            ("_hi32", Value::Hi32(v)) if hi32.is_none() => {
-              hi32 = Some(v);
-            },
-            ("_lo32", Value::Lo64(v)) if lo64.is_none() => {
-              lo64 = Some(v);
-            },
-            _ => break,
-          }
-
-          values += 1;
-        }
-
-        if values == 3 {
-          if let (Some(flags), Some(hi32), Some(lo64)) = (flags, hi32, lo64) {
-            let mut n = (i128::from(hi32) << 64) | i128::from(lo64);
-            let scale = (flags >> 16) as u8;
-
-            if (flags & (0x80000000u32 as i32)) != 0 {
-              n = -n;
-            }
-
-            return Ok(rust_decimal::Decimal::from_i128_with_scale(n, scale.into()))
-          }
-        }
 
-        Err(Error::invalid_type(Unexpected::Map, &self))
-      }
+  /// Create a decimal from the fields of a `System.Decimal`, i.e. the sign and scale `flags`
+  /// and the upper 32 and lower 64 bits of the 96-bit integer.
+  ///
+  /// Returns `None` if the scale is out of range.
+  pub fn from_parts(flags: i32, hi: u32, lo: u64) -> Option<Self> {
+    let scale = (flags as u32 >> 16) & 0xFF;
+    if scale > rust_decimal::Decimal::MAX_SCALE {
+      return None
     }
 
-    deserializer.deserialize_map(Visitor).map(Self)
+    Some(Self(rust_decimal::Decimal::from_parts(lo as u32, (lo >> 32) as u32, hi, flags < 0, scale)))
   }
 }
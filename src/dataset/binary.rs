@@ -0,0 +1,247 @@
+//! The binary remoting format, i.e. `SerializationFormat.Binary`.
+//!
+//! The schema of each table of a data set is serialized into a nested NRBF stream, while the rows
+//! of all tables are stored in the data set itself as one array per column.
+
+use std::collections::HashMap;
+
+use super::{DataColumn, DataRow, DataRowState, DataSet, DataSetError, DataTable, Field};
+use crate::{
+  RemotingMessage, Value,
+  value::{Decimal, Guid, Object, full_type_name},
+};
+
+pub(super) const TABLE_COUNT: &str = "DataSet.Tables.Count";
+pub(super) const COLUMN_COUNT: &str = "DataTable.Columns.Count";
+
+type Members<'o, 'i> = &'o HashMap<&'i str, Value<'i>>;
+
+pub(super) fn data_set(object: &Object<'_>) -> Result<DataSet, DataSetError> {
+  let members = &object.members;
+
+  let tables = (0..count_member(members, TABLE_COUNT)?)
+    .map(|i| {
+      let name = format!("DataSet.Tables_{}", i);
+      let bytes = byte_array_member(members, &name)?;
+
+      // The bytes are the whole buffer of a `MemoryStream`, which is padded with zeros after the
+      // `MessageEnd` record.
+      let len = bytes.iter().rposition(|b| *b != 0).map_or(0, |i| i + 1);
+
+      let Ok(RemotingMessage::Value(Value::Object(table))) = RemotingMessage::parse(&bytes[..len]) else {
+        return Err(DataSetError::InvalidMember(name))
+      };
+
+      let (name, columns) = table_schema(&table.members)?;
+      let rows = table_rows(members, i, &columns)?;
+      Ok(DataTable { name, columns, rows })
+    })
+    .collect::<Result<_, _>>()?;
+
+  Ok(DataSet { name: str_member(members, "DataSet.DataSetName")?.to_owned(), tables })
+}
+
+pub(super) fn data_table(object: &Object<'_>) -> Result<DataTable, DataSetError> {
+  let (name, columns) = table_schema(&object.members)?;
+  let rows = table_rows(&object.members, 0, &columns)?;
+  Ok(DataTable { name, columns, rows })
+}
+
+/// Read the name and columns serialized by `DataTable.SerializeTableSchema`.
+fn table_schema(members: Members<'_, '_>) -> Result<(String, Vec<DataColumn>), DataSetError> {
+  let columns = (0..count_member(members, COLUMN_COUNT)?)
+    .map(|i| {
+      let prefix = format!("DataTable.DataColumn_{}", i);
+
+      // .NET Framework serializes a `System.Type`, while .NET only serializes its name.
+      let data_type = match members.get(format!("{}.DataType", prefix).as_str()) {
+        Some(Value::Object(data_type)) => match data_type.members.get("Data") {
+          Some(Value::String(name)) => name,
+          _ => return Err(DataSetError::InvalidMember(format!("{}.DataType", prefix))),
+        },
+        _ => str_member(members, &format!("{}.DataType_AssemblyQualifiedName", prefix))?,
+      };
+
+      let allow_null = match members.get(format!("{}.AllowDBNull", prefix).as_str()) {
+        Some(Value::Boolean(allow_null)) => *allow_null,
+        _ => true,
+      };
+
+      Ok(DataColumn {
+        name: str_member(members, &format!("{}.ColumnName", prefix))?.to_owned(),
+        data_type: full_type_name(data_type).to_owned(),
+        allow_null,
+      })
+    })
+    .collect::<Result<_, _>>()?;
+
+  Ok((str_member(members, "DataTable.TableName")?.to_owned(), columns))
+}
+
+/// Read the rows serialized by `DataTable.SerializeTableData`.
+///
+/// Each row state is encoded in three bits: two for the state (`00` unchanged, `01` added,
+/// `10` modified and `11` deleted) and one for a pending edit. Each row occupies one record for
+/// its original values, if any, one for its current values, if they differ, and one for its
+/// pending edit, if any.
+fn table_rows(members: Members<'_, '_>, index: usize, columns: &[DataColumn]) -> Result<Vec<DataRow>, DataSetError> {
+  let prefix = format!("DataTable_{}", index);
+
+  let row_count = count_member(members, &format!("{}.Rows.Count", prefix))?;
+  let row_states = bit_array(member(members, &format!("{}.RowStates", prefix))?, &format!("{}.RowStates", prefix))?;
+
+  let records_name = format!("{}.Records", prefix);
+  let records = array_list(member(members, &records_name)?, &records_name)?;
+  let null_bits_name = format!("{}.NullBits", prefix);
+  let null_bits = array_list(member(members, &null_bits_name)?, &null_bits_name)?
+    .iter()
+    .map(|bits| bit_array(bits, &null_bits_name))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  if row_count > 0 && (records.len() != columns.len() || null_bits.len() != columns.len()) {
+    return Err(DataSetError::InvalidMember(records_name))
+  }
+
+  let record = |record: usize| {
+    records
+      .iter()
+      .zip(&null_bits)
+      .map(|(store, null_bits)| {
+        let field = match store {
+          _ if null_bits.get(record).copied().unwrap_or(false) => Some(Field::Null),
+          Value::Array(store) => store.get(record).and_then(field),
+          Value::Bytes(store) => store.get(record).map(|b| Field::Byte(*b)),
+          _ => None,
+        };
+
+        field.ok_or_else(|| DataSetError::InvalidMember(records_name.clone()))
+      })
+      .collect::<Result<Vec<_>, _>>()
+  };
+
+  let mut next_record = 0;
+  let mut rows = Vec::with_capacity(row_count);
+
+  for i in 0..row_count {
+    let bit = |offset| row_states.get(i * 3 + offset).copied().unwrap_or(false);
+
+    let state = match (bit(0), bit(1)) {
+      (false, false) => DataRowState::Unchanged,
+      (false, true) => DataRowState::Added,
+      (true, false) => DataRowState::Modified,
+      (true, true) => DataRowState::Deleted,
+    };
+
+    let original = match state {
+      DataRowState::Added => None,
+      _ => {
+        next_record += 1;
+        Some(record(next_record - 1)?)
+      },
+    };
+
+    let current = match state {
+      DataRowState::Unchanged => original.clone(),
+      DataRowState::Added | DataRowState::Modified => {
+        next_record += 1;
+        Some(record(next_record - 1)?)
+      },
+      DataRowState::Deleted => None,
+    };
+
+    // Skip the record of a pending edit.
+    if bit(2) {
+      next_record += 1;
+    }
+
+    rows.push(DataRow { state, current, original });
+  }
+
+  Ok(rows)
+}
+
+/// Convert an element of a column store.
+fn field(value: &Value<'_>) -> Option<Field> {
+  Some(match value {
+    Value::Null => Field::Null,
+    Value::Boolean(v) => Field::Boolean(*v),
+    Value::Byte(v) => Field::Byte(*v),
+    Value::Char(v) => Field::Char(*v),
+    Value::DateTime(v) => Field::DateTime(*v),
+    Value::Decimal(v) => Field::Decimal(v.to_rust_decimal()),
+    Value::Double(v) => Field::Double(*v),
+    Value::Int16(v) => Field::Int16(*v),
+    Value::Int32(v) => Field::Int32(*v),
+    Value::Int64(v) => Field::Int64(*v),
+    Value::SByte(v) => Field::SByte(*v),
+    Value::Single(v) => Field::Single(*v),
+    Value::String(v) => Field::String((*v).to_owned()),
+    Value::TimeSpan(v) => Field::TimeSpan(*v),
+    Value::UInt16(v) => Field::UInt16(*v),
+    Value::UInt32(v) => Field::UInt32(*v),
+    Value::UInt64(v) => Field::UInt64(*v),
+    Value::Bytes(v) => Field::Bytes(v.to_vec()),
+    Value::Array(_) => return None,
+    Value::Object(object) => match object.class {
+      "System.Guid" => Field::Guid(Guid::try_from(object).ok()?),
+      "System.Decimal" => Field::Decimal(Decimal::try_from(object).ok()?.to_rust_decimal()),
+      _ => return None,
+    },
+  })
+}
+
+fn member<'o, 'i>(members: Members<'o, 'i>, name: &str) -> Result<&'o Value<'i>, DataSetError> {
+  members.get(name).ok_or_else(|| DataSetError::MissingMember(name.to_owned()))
+}
+
+fn str_member<'i>(members: Members<'_, 'i>, name: &str) -> Result<&'i str, DataSetError> {
+  match member(members, name)? {
+    Value::String(s) => Ok(s),
+    _ => Err(DataSetError::InvalidMember(name.to_owned())),
+  }
+}
+
+fn count_member(members: Members<'_, '_>, name: &str) -> Result<usize, DataSetError> {
+  match member(members, name)? {
+    Value::Int32(count) => usize::try_from(*count).map_err(|_| DataSetError::InvalidMember(name.to_owned())),
+    _ => Err(DataSetError::InvalidMember(name.to_owned())),
+  }
+}
+
+fn byte_array_member<'o>(members: Members<'o, '_>, name: &str) -> Result<&'o [u8], DataSetError> {
+  match member(members, name)? {
+    Value::Bytes(bytes) => Ok(bytes),
+    _ => Err(DataSetError::InvalidMember(name.to_owned())),
+  }
+}
+
+/// Get the elements of a `System.Collections.ArrayList`.
+fn array_list<'o, 'i>(value: &'o Value<'i>, name: &str) -> Result<&'o [Value<'i>], DataSetError> {
+  if let Value::Object(object) = value
+    && let (Some(Value::Array(items)), Some(Value::Int32(size))) =
+      (object.members.get("_items"), object.members.get("_size"))
+    && let Some(items) = usize::try_from(*size).ok().and_then(|size| items.get(..size))
+  {
+    return Ok(items)
+  }
+
+  Err(DataSetError::InvalidMember(name.to_owned()))
+}
+
+/// Get the bits of a `System.Collections.BitArray`.
+fn bit_array(value: &Value<'_>, name: &str) -> Result<Vec<bool>, DataSetError> {
+  if let Value::Object(object) = value
+    && let (Some(Value::Array(array)), Some(Value::Int32(length))) =
+      (object.members.get("m_array"), object.members.get("m_length"))
+    && let Ok(length) = usize::try_from(*length)
+  {
+    return (0..length)
+      .map(|i| match array.get(i / 32) {
+        Some(Value::Int32(bits)) => Ok((bits >> (i % 32)) & 1 == 1),
+        _ => Err(DataSetError::InvalidMember(name.to_owned())),
+      })
+      .collect()
+  }
+
+  Err(DataSetError::InvalidMember(name.to_owned()))
+}
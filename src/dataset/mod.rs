@@ -0,0 +1,207 @@
+//! Tabular view of serialized `System.Data.DataSet` and `System.Data.DataTable` objects.
+//!
+//! Both the binary (`SerializationFormat.Binary`) and the XML (`SerializationFormat.Xml`)
+//! remoting formats are supported.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use nrbf::{RemotingMessage, Value, dataset::DataSet};
+//!
+//! # let bytes: &[u8] = &[];
+//! let Ok(RemotingMessage::Value(Value::Object(object))) = RemotingMessage::parse(bytes) else {
+//!   panic!("expected an object");
+//! };
+//!
+//! let data_set = DataSet::try_from(&object)?;
+//! for table in &data_set.tables {
+//!   println!("{}: {} rows", table.name, table.rows.len());
+//! }
+//! # Ok::<(), nrbf::dataset::DataSetError>(())
+//! ```
+
+use std::{error, fmt};
+
+use crate::value::{DateTime, Guid, Object, TimeSpan};
+
+mod binary;
+mod xml;
+mod xml_reader;
+
+/// A set of tables, i.e. a serialized `System.Data.DataSet`.
+///
+/// Typed data sets, i.e. subclasses of `System.Data.DataSet`, are supported as well.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSet {
+  /// The name of the data set.
+  pub name: String,
+  /// The tables of the data set.
+  pub tables: Vec<DataTable>,
+}
+
+impl DataSet {
+  /// Get a table by name.
+  pub fn table(&self, name: &str) -> Option<&DataTable> {
+    self.tables.iter().find(|table| table.name == name)
+  }
+}
+
+impl TryFrom<&Object<'_>> for DataSet {
+  type Error = DataSetError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    if object.members.contains_key(xml::SCHEMA) {
+      xml::data_set(object)
+    } else if object.members.contains_key(binary::TABLE_COUNT) {
+      binary::data_set(object)
+    } else {
+      Err(DataSetError::UnexpectedClass(object.class.to_owned()))
+    }
+  }
+}
+
+/// A table, i.e. a serialized `System.Data.DataTable`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTable {
+  /// The name of the table.
+  pub name: String,
+  /// The columns of the table.
+  pub columns: Vec<DataColumn>,
+  /// The rows of the table, including deleted rows.
+  pub rows: Vec<DataRow>,
+}
+
+impl DataTable {
+  /// Get the index of a column by name.
+  pub fn column_index(&self, name: &str) -> Option<usize> {
+    self.columns.iter().position(|column| column.name == name)
+  }
+}
+
+impl TryFrom<&Object<'_>> for DataTable {
+  type Error = DataSetError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    if object.members.contains_key(xml::SCHEMA) {
+      xml::data_table(object)
+    } else if object.members.contains_key(binary::COLUMN_COUNT) {
+      binary::data_table(object)
+    } else {
+      Err(DataSetError::UnexpectedClass(object.class.to_owned()))
+    }
+  }
+}
+
+/// A column of a [`DataTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataColumn {
+  /// The name of the column.
+  pub name: String,
+  /// The full name of the .NET type of the column, e.g. `System.Int32`.
+  pub data_type: String,
+  /// Whether the column allows null values.
+  pub allow_null: bool,
+}
+
+/// The state of a [`DataRow`], i.e. a `System.Data.DataRowState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataRowState {
+  /// The row has not changed since the last call to `AcceptChanges`.
+  Unchanged,
+  /// The row has been added.
+  Added,
+  /// The row has been modified.
+  Modified,
+  /// The row has been deleted.
+  Deleted,
+}
+
+/// A row of a [`DataTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataRow {
+  /// The state of the row.
+  pub state: DataRowState,
+  /// The current values, in column order. `None` for deleted rows.
+  pub current: Option<Vec<Field>>,
+  /// The original values, in column order. `None` for added rows.
+  pub original: Option<Vec<Field>>,
+}
+
+impl DataRow {
+  /// Get the current values, or the original values of a deleted row.
+  pub fn values(&self) -> &[Field] {
+    self.current.as_deref().or(self.original.as_deref()).unwrap_or_default()
+  }
+}
+
+/// A field value of a [`DataRow`].
+///
+/// Values of the XML format are typed according to the column type. Date-times, time spans and
+/// values of other types are kept as their XML text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Field {
+  /// A null value, i.e. `System.DBNull`.
+  Null,
+  /// A boolean value.
+  Boolean(bool),
+  /// A byte.
+  Byte(u8),
+  /// A byte array.
+  Bytes(Vec<u8>),
+  /// A character.
+  Char(char),
+  /// A date-time.
+  DateTime(DateTime),
+  /// A decimal number.
+  Decimal(rust_decimal::Decimal),
+  /// A double precision floating point number.
+  Double(f64),
+  /// A GUID.
+  Guid(Guid),
+  /// A 16-bit signed integer.
+  Int16(i16),
+  /// A 32-bit signed integer.
+  Int32(i32),
+  /// A 64-bit signed integer.
+  Int64(i64),
+  /// A signed byte.
+  SByte(i8),
+  /// A single precision floating point number.
+  Single(f32),
+  /// A string.
+  String(String),
+  /// A time span.
+  TimeSpan(TimeSpan),
+  /// A 16-bit unsigned integer.
+  UInt16(u16),
+  /// A 32-bit unsigned integer.
+  UInt32(u32),
+  /// A 64-bit unsigned integer.
+  UInt64(u64),
+}
+
+/// Error while converting an object into a [`DataSet`] or [`DataTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataSetError {
+  /// The object is neither a binary nor an XML serialized data set or table.
+  UnexpectedClass(String),
+  /// A required member is missing.
+  MissingMember(String),
+  /// A member has an unexpected type or value.
+  InvalidMember(String),
+  /// The XML schema or diffgram is invalid.
+  InvalidXml(String),
+}
+
+impl fmt::Display for DataSetError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnexpectedClass(class) => write!(f, "expected a data set or data table, found {}", class),
+      Self::MissingMember(name) => write!(f, "missing member {}", name),
+      Self::InvalidMember(name) => write!(f, "invalid member {}", name),
+      Self::InvalidXml(message) => write!(f, "invalid XML: {}", message),
+    }
+  }
+}
+
+impl error::Error for DataSetError {}
@@ -0,0 +1,320 @@
+//! The XML remoting format, i.e. `SerializationFormat.Xml`.
+//!
+//! The schema is serialized as an XML schema, and the rows as a diffgram containing the current
+//! values and, in its `diffgr:before` element, the original values of modified and deleted rows.
+
+use std::{collections::HashMap, str::FromStr};
+
+use super::{DataColumn, DataRow, DataRowState, DataSet, DataSetError, DataTable, Field, xml_reader::Element};
+use crate::{
+  Value,
+  value::{Object, full_type_name},
+};
+
+pub(super) const SCHEMA: &str = "XmlSchema";
+const DIFFGRAM: &str = "XmlDiffGram";
+
+/// The schema of a table, with the XML names of the table and its columns.
+struct TableSchema<'s> {
+  xml_name: &'s str,
+  columns: Vec<ColumnSchema<'s>>,
+}
+
+struct ColumnSchema<'s> {
+  xml_name: &'s str,
+  is_attribute: bool,
+  column: DataColumn,
+}
+
+pub(super) fn data_set(object: &Object<'_>) -> Result<DataSet, DataSetError> {
+  let schema = Element::parse_document(str_member(object, SCHEMA)?)?;
+  let data_set = data_set_element(&schema)?;
+  let tables = table_schemas(data_set)?;
+
+  let diffgram = optional_str_member(object, DIFFGRAM)?.map(Element::parse_document).transpose()?;
+  let tables = tables.iter().map(|table| table_data(table, diffgram.as_ref())).collect::<Result<_, _>>()?;
+
+  Ok(DataSet { name: decode_name(data_set.attribute("name").unwrap_or_default()), tables })
+}
+
+pub(super) fn data_table(object: &Object<'_>) -> Result<DataTable, DataSetError> {
+  let schema = Element::parse_document(str_member(object, SCHEMA)?)?;
+  let data_set = data_set_element(&schema)?;
+  let tables = table_schemas(data_set)?;
+
+  let main_table = data_set.attribute("MainDataTable").map(|name| name.rsplit_once(':').map_or(name, |(_, name)| name));
+  let table = match main_table {
+    Some(main_table) => tables.iter().find(|table| table.xml_name == main_table),
+    None => tables.first(),
+  };
+  let table = table.ok_or_else(|| DataSetError::InvalidXml("missing table schema".into()))?;
+
+  let diffgram = optional_str_member(object, DIFFGRAM)?.map(Element::parse_document).transpose()?;
+  table_data(table, diffgram.as_ref())
+}
+
+fn str_member<'i>(object: &Object<'i>, name: &str) -> Result<&'i str, DataSetError> {
+  optional_str_member(object, name)?.ok_or_else(|| DataSetError::MissingMember(name.to_owned()))
+}
+
+fn optional_str_member<'i>(object: &Object<'i>, name: &str) -> Result<Option<&'i str>, DataSetError> {
+  match object.members.get(name) {
+    Some(Value::String(s)) => Ok(Some(s)),
+    Some(Value::Null) | None => Ok(None),
+    Some(_) => Err(DataSetError::InvalidMember(name.to_owned())),
+  }
+}
+
+/// Find the element marked with `msdata:IsDataSet` in the schema.
+fn data_set_element<'s, 'i>(schema: &'s Element<'i>) -> Result<&'s Element<'i>, DataSetError> {
+  schema
+    .children_named("element")
+    .find(|element| element.attribute("IsDataSet") == Some("true"))
+    .ok_or_else(|| DataSetError::InvalidXml("missing data set element in schema".into()))
+}
+
+fn table_schemas<'s>(data_set: &'s Element<'_>) -> Result<Vec<TableSchema<'s>>, DataSetError> {
+  let Some(choice) = data_set
+    .child("complexType")
+    .and_then(|complex_type| complex_type.child("choice").or_else(|| complex_type.child("sequence")))
+  else {
+    return Ok(Vec::new())
+  };
+
+  choice
+    .children_named("element")
+    .filter_map(|table| Some((table.attribute("name")?, table.child("complexType")?)))
+    .map(|(xml_name, complex_type)| {
+      let elements = complex_type
+        .child("sequence")
+        .into_iter()
+        .flat_map(|sequence| sequence.children_named("element"))
+        .map(|element| (element, false));
+      let attributes = complex_type.children_named("attribute").map(|attribute| (attribute, true));
+
+      let columns = elements
+        .chain(attributes)
+        .map(|(element, is_attribute)| {
+          let xml_name =
+            element.attribute("name").ok_or_else(|| DataSetError::InvalidXml("column without a name".into()))?;
+
+          let allow_null = if is_attribute {
+            element.attribute("use") != Some("required")
+          } else {
+            element.attribute("minOccurs") == Some("0")
+          };
+
+          Ok(ColumnSchema {
+            xml_name,
+            is_attribute,
+            column: DataColumn { name: decode_name(xml_name), data_type: data_type(element), allow_null },
+          })
+        })
+        .collect::<Result<_, _>>()?;
+
+      Ok(TableSchema { xml_name, columns })
+    })
+    .collect()
+}
+
+/// Get the .NET type of a column, either from `msdata:DataType` or from the XML schema type.
+fn data_type(element: &Element<'_>) -> String {
+  if let Some(data_type) = element.attribute("DataType") {
+    return full_type_name(data_type).to_owned()
+  }
+
+  let xml_type = element.attribute("type").or_else(|| {
+    element.child("simpleType").and_then(|simple_type| simple_type.child("restriction")?.attribute("base"))
+  });
+
+  let data_type = match xml_type.map(|t| t.rsplit_once(':').map_or(t, |(_, t)| t)) {
+    Some("boolean") => "System.Boolean",
+    Some("unsignedByte") => "System.Byte",
+    Some("base64Binary") => "System.Byte[]",
+    Some("dateTime") => "System.DateTime",
+    Some("decimal") => "System.Decimal",
+    Some("double") => "System.Double",
+    Some("short") => "System.Int16",
+    Some("int") => "System.Int32",
+    Some("long") => "System.Int64",
+    Some("byte") => "System.SByte",
+    Some("float") => "System.Single",
+    Some("duration") => "System.TimeSpan",
+    Some("unsignedShort") => "System.UInt16",
+    Some("unsignedInt") => "System.UInt32",
+    Some("unsignedLong") => "System.UInt64",
+    Some("anyType") => "System.Object",
+    _ => "System.String",
+  };
+
+  data_type.to_owned()
+}
+
+/// Read the rows of a table from the diffgram.
+fn table_data(table: &TableSchema<'_>, diffgram: Option<&Element<'_>>) -> Result<DataTable, DataSetError> {
+  let name = decode_name(table.xml_name);
+  let columns = table.columns.iter().map(|column| column.column.clone()).collect();
+
+  let Some(diffgram) = diffgram else { return Ok(DataTable { name, columns, rows: Vec::new() }) };
+
+  fn rows_of<'e, 'i>(element: &'e Element<'i>, table: &TableSchema<'_>) -> Vec<&'e Element<'i>> {
+    element.children.iter().filter(|row| row.local_name() == table.xml_name).collect()
+  }
+
+  let current = diffgram
+    .children
+    .iter()
+    .find(|element| !matches!(element.local_name(), "before" | "errors"))
+    .map(|element| rows_of(element, table))
+    .unwrap_or_default();
+  let mut before = diffgram
+    .child("before")
+    .map(|element| rows_of(element, table))
+    .unwrap_or_default()
+    .into_iter()
+    .map(|row| (row.attribute("id").unwrap_or_default().to_owned(), row))
+    .collect::<HashMap<_, _>>();
+
+  let mut rows = Vec::new();
+
+  for row in current {
+    let original = row.attribute("id").and_then(|id| before.remove(id));
+
+    let (state, original) = match row.attribute("hasChanges") {
+      Some("inserted") => (DataRowState::Added, None),
+      Some("modified") => (DataRowState::Modified, Some(values(table, original.unwrap_or(row))?)),
+      _ => (DataRowState::Unchanged, Some(values(table, row)?)),
+    };
+
+    rows.push((row_order(row), DataRow { state, current: Some(values(table, row)?), original }));
+  }
+
+  for row in before.values() {
+    rows.push((
+      row_order(row),
+      DataRow { state: DataRowState::Deleted, current: None, original: Some(values(table, row)?) },
+    ));
+  }
+
+  rows.sort_by_key(|(row_order, _)| *row_order);
+  Ok(DataTable { name, columns, rows: rows.into_iter().map(|(_, row)| row).collect() })
+}
+
+fn row_order(row: &Element<'_>) -> usize {
+  row.attribute("rowOrder").and_then(|row_order| row_order.parse().ok()).unwrap_or(usize::MAX)
+}
+
+fn values(table: &TableSchema<'_>, row: &Element<'_>) -> Result<Vec<Field>, DataSetError> {
+  table
+    .columns
+    .iter()
+    .map(|column| {
+      let text = if column.is_attribute {
+        row.attribute(column.xml_name)
+      } else {
+        row.child(column.xml_name).map(|element| element.text.as_str())
+      };
+
+      match text {
+        Some(text) => field(text, &column.column.data_type),
+        None => Ok(Field::Null),
+      }
+    })
+    .collect()
+}
+
+/// Parse the text of a field according to its column type.
+fn field(text: &str, data_type: &str) -> Result<Field, DataSetError> {
+  fn parse<T: FromStr>(text: &str, data_type: &str) -> Result<T, DataSetError> {
+    text.trim().parse().map_err(|_| DataSetError::InvalidXml(format!("invalid {} value {:?}", data_type, text)))
+  }
+
+  Ok(match data_type {
+    "System.Boolean" => Field::Boolean(match text.trim() {
+      "1" => true,
+      "0" => false,
+      text => parse(text, data_type)?,
+    }),
+    "System.Byte" => Field::Byte(parse(text, data_type)?),
+    "System.Byte[]" => Field::Bytes(
+      base64(text).ok_or_else(|| DataSetError::InvalidXml(format!("invalid {} value {:?}", data_type, text)))?,
+    ),
+    "System.Decimal" => Field::Decimal(parse(text, data_type)?),
+    "System.Double" => Field::Double(parse(text, data_type)?),
+    "System.Int16" => Field::Int16(parse(text, data_type)?),
+    "System.Int32" => Field::Int32(parse(text, data_type)?),
+    "System.Int64" => Field::Int64(parse(text, data_type)?),
+    "System.SByte" => Field::SByte(parse(text, data_type)?),
+    "System.Single" => Field::Single(parse(text, data_type)?),
+    "System.UInt16" => Field::UInt16(parse(text, data_type)?),
+    "System.UInt32" => Field::UInt32(parse(text, data_type)?),
+    "System.UInt64" => Field::UInt64(parse(text, data_type)?),
+    _ => Field::String(text.to_owned()),
+  })
+}
+
+/// Decode Base64 text, ignoring whitespace.
+fn base64(text: &str) -> Option<Vec<u8>> {
+  let digits = text.bytes().filter(|b| !b.is_ascii_whitespace()).collect::<Vec<_>>();
+  if digits.len() % 4 != 0 {
+    return None
+  }
+
+  let mut bytes = Vec::with_capacity(digits.len() / 4 * 3);
+
+  for chunk in digits.chunks(4) {
+    let padding = chunk.iter().rev().take_while(|&&b| b == b'=').count();
+    if padding > 2 {
+      return None
+    }
+
+    let mut n = 0u32;
+    for &digit in &chunk[..4 - padding] {
+      let value = match digit {
+        b'A'..=b'Z' => digit - b'A',
+        b'a'..=b'z' => digit - b'a' + 26,
+        b'0'..=b'9' => digit - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+      };
+      n = (n << 6) | u32::from(value);
+    }
+    n <<= 6 * padding;
+
+    bytes.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+  }
+
+  Some(bytes)
+}
+
+/// Decode a name encoded with `XmlConvert.EncodeLocalName`, e.g. `Order_x0020_Date`.
+fn decode_name(name: &str) -> String {
+  let mut decoded = String::with_capacity(name.len());
+  let mut rest = name;
+
+  while let Some(start) = rest.find("_x") {
+    decoded.push_str(&rest[..start]);
+    rest = &rest[start..];
+
+    let c = rest
+      .get(2..6)
+      .filter(|_| rest.as_bytes().get(6) == Some(&b'_'))
+      .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+      .and_then(char::from_u32);
+
+    match c {
+      Some(c) => {
+        decoded.push(c);
+        rest = &rest[7..];
+      },
+      None => {
+        decoded.push('_');
+        rest = &rest[1..];
+      },
+    }
+  }
+
+  decoded.push_str(rest);
+  decoded
+}
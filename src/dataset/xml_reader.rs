@@ -0,0 +1,167 @@
+//! A minimal XML reader for the schemas and diffgrams of data sets.
+//!
+//! Namespaces are not resolved; elements and attributes are matched by their local names.
+
+use nom::{
+  IResult, Parser,
+  branch::alt,
+  bytes::complete::{tag, take_until, take_while, take_while1},
+  character::complete::{char, multispace0, multispace1},
+  combinator::{all_consuming, map, value},
+  multi::many0,
+  sequence::{delimited, preceded, terminated},
+};
+
+use super::DataSetError;
+
+/// An XML element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Element<'i> {
+  pub name: &'i str,
+  pub attributes: Vec<(&'i str, String)>,
+  pub children: Vec<Element<'i>>,
+  pub text: String,
+}
+
+#[derive(Clone)]
+enum Content<'i> {
+  Element(Element<'i>),
+  Text(String),
+  Skip,
+}
+
+impl<'i> Element<'i> {
+  /// Parse the root element of an XML document.
+  pub fn parse_document(input: &'i str) -> Result<Self, DataSetError> {
+    all_consuming(delimited(misc, element, misc))
+      .parse(input)
+      .map(|(_, element)| element)
+      .map_err(|err| DataSetError::InvalidXml(err.to_string()))
+  }
+
+  pub fn local_name(&self) -> &'i str {
+    local_name(self.name)
+  }
+
+  /// Get an attribute by its local name.
+  pub fn attribute(&self, name: &str) -> Option<&str> {
+    self.attributes.iter().find(|(n, _)| local_name(n) == name).map(|(_, value)| value.as_str())
+  }
+
+  /// Get the first child element with the given local name.
+  pub fn child(&self, name: &str) -> Option<&Element<'i>> {
+    self.children.iter().find(|child| child.local_name() == name)
+  }
+
+  /// Get all child elements with the given local name.
+  pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element<'i>> {
+    self.children.iter().filter(move |child| child.local_name() == name)
+  }
+}
+
+fn local_name(name: &str) -> &str {
+  name.rsplit_once(':').map_or(name, |(_, local_name)| local_name)
+}
+
+fn name(input: &str) -> IResult<&str, &str> {
+  take_while1(|c: char| c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.')).parse(input)
+}
+
+fn comment(input: &str) -> IResult<&str, &str> {
+  delimited(tag("<!--"), take_until("-->"), tag("-->")).parse(input)
+}
+
+fn processing_instruction(input: &str) -> IResult<&str, &str> {
+  delimited(tag("<?"), take_until("?>"), tag("?>")).parse(input)
+}
+
+/// Whitespace, comments and processing instructions outside of the root element.
+fn misc(input: &str) -> IResult<&str, ()> {
+  value((), many0(alt((multispace1, comment, processing_instruction)))).parse(input)
+}
+
+fn attribute(input: &str) -> IResult<&str, (&str, String)> {
+  let (input, name) = preceded(multispace1, name).parse(input)?;
+  let (input, _) = delimited(multispace0, char('='), multispace0).parse(input)?;
+  let (input, value) =
+    alt((delimited(char('"'), take_until("\""), char('"')), delimited(char('\''), take_until("'"), char('\''))))
+      .parse(input)?;
+
+  Ok((input, (name, unescape(value))))
+}
+
+fn content(input: &str) -> IResult<&str, Content<'_>> {
+  alt((
+    map(delimited(tag("<![CDATA["), take_until("]]>"), tag("]]>")), |text: &str| Content::Text(text.to_owned())),
+    value(Content::Skip, comment),
+    value(Content::Skip, processing_instruction),
+    map(element, Content::Element),
+    map(take_while1(|c| c != '<'), |text| Content::Text(unescape(text))),
+  ))
+  .parse(input)
+}
+
+fn element(input: &str) -> IResult<&str, Element<'_>> {
+  let (input, name) = preceded(char('<'), name).parse(input)?;
+  let (input, attributes) = terminated(many0(attribute), multispace0).parse(input)?;
+
+  let mut element = Element { name, attributes, children: Vec::new(), text: String::new() };
+
+  if let Ok((input, _)) = tag::<_, _, nom::error::Error<&str>>("/>").parse(input) {
+    return Ok((input, element))
+  }
+
+  let (input, contents) = preceded(char('>'), many0(content)).parse(input)?;
+  let (input, _) = delimited(tag("</"), tag(name), (take_while(char::is_whitespace), char('>'))).parse(input)?;
+
+  for content in contents {
+    match content {
+      Content::Element(child) => element.children.push(child),
+      Content::Text(text) => element.text.push_str(&text),
+      Content::Skip => (),
+    }
+  }
+
+  Ok((input, element))
+}
+
+/// Replace predefined entities and character references.
+///
+/// Unknown entities are kept as is.
+fn unescape(text: &str) -> String {
+  let mut result = String::with_capacity(text.len());
+  let mut rest = text;
+
+  while let Some(start) = rest.find('&') {
+    result.push_str(&rest[..start]);
+    rest = &rest[start..];
+
+    let Some(end) = rest.find(';') else { break };
+
+    let c = match &rest[1..end] {
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "amp" => Some('&'),
+      "quot" => Some('"'),
+      "apos" => Some('\''),
+      reference => match reference.strip_prefix("#x").or_else(|| reference.strip_prefix("#X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+        None => reference.strip_prefix('#').and_then(|dec| dec.parse().ok()).and_then(char::from_u32),
+      },
+    };
+
+    match c {
+      Some(c) => {
+        result.push(c);
+        rest = &rest[end + 1..];
+      },
+      None => {
+        result.push('&');
+        rest = &rest[1..];
+      },
+    }
+  }
+
+  result.push_str(rest);
+  result
+}
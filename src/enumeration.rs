@@ -53,30 +53,47 @@ where
 }
 
 /// 2.1.2.3 `PrimitiveTypeEnumeration`
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum PrimitiveType {
+  /// A `BOOLEAN`.
   Boolean  = 1,
+  /// A `BYTE`.
   Byte     = 2,
+  /// A `CHAR`.
   Char     = 3,
+  /// A `Decimal`.
   Decimal  = 5,
+  /// A `DOUBLE`.
   Double   = 6,
+  /// An `INT16`.
   Int16    = 7,
+  /// An `INT32`.
   Int32    = 8,
+  /// An `INT64`.
   Int64    = 9,
+  /// An `INT8`.
   SByte    = 10,
+  /// A `SINGLE`.
   Single   = 11,
+  /// A `TimeSpan`.
   TimeSpan = 12,
+  /// A `DateTime`.
   DateTime = 13,
+  /// A `UINT16`.
   UInt16   = 14,
+  /// A `UINT32`.
   UInt32   = 15,
+  /// A `UINT64`.
   UInt64   = 16,
+  /// A `NULL`.
   Null     = 17,
+  /// A `LengthPrefixedString`.
   String   = 18,
 }
 
 impl PrimitiveType {
-  pub fn parse(input: &[u8]) -> IResult<&[u8], Self, Error<'_>> {
+  pub(crate) fn parse(input: &[u8]) -> IResult<&[u8], Self, Error<'_>> {
     alt((
       Self::Boolean,
       Self::Byte,
@@ -138,19 +155,25 @@ where
 }
 
 /// 2.4.1.1 `BinaryArrayTypeEnumeration`
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum BinaryArrayType {
+  /// A single-dimensional array.
   Single            = 0,
+  /// An array of arrays.
   Jagged            = 1,
+  /// A multi-dimensional rectangular array.
   Rectangular       = 2,
+  /// A single-dimensional array with a lower bound.
   SingleOffset      = 3,
+  /// A jagged array with a lower bound.
   JaggedOffset      = 4,
+  /// A multi-dimensional rectangular array with lower bounds.
   RectangularOffset = 5,
 }
 
 impl BinaryArrayType {
-  pub fn parse(input: &[u8]) -> IResult<&[u8], Self, Error<'_>> {
+  pub(crate) fn parse(input: &[u8]) -> IResult<&[u8], Self, Error<'_>> {
     alt((
       Self::Single,
       Self::Jagged,
@@ -43,6 +43,7 @@ pub(crate) enum ErrorInner {
   TrailingData,
   UnresolvableMemberReference,
   InvalidCallArrayId,
+  InvalidCallArray,
   MissingRootObject,
   InvalidNullCount,
   InvalidObjectId,
@@ -76,6 +77,7 @@ impl fmt::Display for ErrorInner {
       Self::TrailingData => write!(f, "unexpected trailing data"),
       Self::UnresolvableMemberReference => write!(f, "unresolvable member reference"),
       Self::InvalidCallArrayId => write!(f, "invalid call array ID"),
+      Self::InvalidCallArray => write!(f, "invalid call array"),
       Self::MissingRootObject => write!(f, "missing root object"),
       Self::InvalidNullCount => write!(f, "invalid NULL count"),
       Self::InvalidObjectId => write!(f, "invalid object ID"),
@@ -0,0 +1,71 @@
+use std::{collections::HashMap, error, fmt};
+
+use crate::{Value, value::Object};
+
+/// `COR_E_EXCEPTION`, the `HResult` of a plain `System.Exception`.
+const COR_E_EXCEPTION: i32 = 0x80131500u32 as i32;
+
+/// An exception thrown by a remote .NET method.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotNetFault {
+  /// The class name of the exception, e.g. `System.ArgumentException`.
+  pub class_name: String,
+  /// The exception message.
+  pub message: String,
+}
+
+impl DotNetFault {
+  /// Create a new fault with the given exception class name and message.
+  pub fn new(class_name: impl Into<String>, message: impl Into<String>) -> Self {
+    Self { class_name: class_name.into(), message: message.into() }
+  }
+
+  /// Extract a fault from a serialized `System.Exception` object.
+  ///
+  /// Returns `None` if the value is not an object containing the `ClassName` or `Message` members.
+  pub fn from_exception(exception: &Value<'_>) -> Option<Self> {
+    let Value::Object(object) = exception else { return None };
+
+    let class_name = match object.members.get("ClassName") {
+      Some(Value::String(class_name)) => class_name,
+      _ => object.class,
+    };
+
+    let message = match object.members.get("Message") {
+      Some(Value::String(message)) => message,
+      Some(Value::Null) => "",
+      _ if object.members.contains_key("ClassName") => "",
+      _ => return None,
+    };
+
+    Some(Self::new(class_name, message))
+  }
+
+  /// Convert the fault into a serialized `System.Exception` object.
+  pub fn to_exception(&self) -> Value<'_> {
+    let members = HashMap::from_iter([
+      ("ClassName", Value::String(&self.class_name)),
+      ("Message", Value::String(&self.message)),
+      ("Data", Value::Null),
+      ("InnerException", Value::Null),
+      ("HelpURL", Value::Null),
+      ("StackTraceString", Value::Null),
+      ("RemoteStackTraceString", Value::Null),
+      ("RemoteStackIndex", Value::Int32(0)),
+      ("ExceptionMethod", Value::Null),
+      ("HResult", Value::Int32(COR_E_EXCEPTION)),
+      ("Source", Value::Null),
+      ("WatsonBuckets", Value::Null),
+    ]);
+
+    Value::Object(Object { class: &self.class_name, library: None, members })
+  }
+}
+
+impl fmt::Display for DotNetFault {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {}", self.class_name, self.message)
+  }
+}
+
+impl error::Error for DotNetFault {}
@@ -0,0 +1,303 @@
+//! Helpers for messages transported over the .NET Remoting HTTP channel.
+//!
+//! The HTTP channel sends NRBF messages as `application/octet-stream` bodies of `POST` requests and
+//! their corresponding responses.
+//!
+//! # Example
+//!
+//! ```
+//! use nrbf::{MethodReturn, RemotingMessage, Value, http::Response};
+//!
+//! # use const_str::concat_bytes;
+//! # #[rustfmt::skip]
+//! let response = concat_bytes!(
+//!   "HTTP/1.1 200 OK\r\n",
+//!   "Content-Type: application/octet-stream\r\n",
+//!   "Server: MS .NET Remoting, MS .NET CLR 4.0.30319.42000\r\n",
+//!   "Content-Length: 29\r\n",
+//!   "\r\n",
+//!   0,
+//!     b"\x00\x00\x00\x00",
+//!     b"\x00\x00\x00\x00",
+//!     b"\x01\x00\x00\x00",
+//!     b"\x00\x00\x00\x00",
+//!   22,
+//!     b"\x11\x08\x00\x00",
+//!     18, 4, "Pong",
+//!   11,
+//! );
+//!
+//! let response = Response::parse(response)?;
+//! assert_eq!(response.status_code, 200);
+//! assert!(response.warnings.is_empty());
+//! assert_eq!(
+//!   response.message(),
+//!   Ok(RemotingMessage::MethodReturn(MethodReturn::new(Some(Value::String("Pong"))))),
+//! );
+//! # Ok::<(), nrbf::http::Error<'static>>(())
+//! ```
+
+use std::{borrow::Cow, fmt, str};
+
+use crate::RemotingMessage;
+
+/// The content type of NRBF message bodies.
+pub const CONTENT_TYPE: &str = "application/octet-stream";
+
+/// An HTTP header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header<'i> {
+  /// The header name.
+  pub name: &'i str,
+  /// The header value.
+  pub value: &'i str,
+}
+
+/// An irregularity in an HTTP message which does not prevent extracting its body.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+  /// The body was sent using chunked transfer encoding.
+  Chunked,
+  /// The `Content-Length` header does not match the number of body bytes.
+  ContentLengthMismatch {
+    /// The length declared in the `Content-Length` header.
+    declared: usize,
+    /// The number of bytes following the header.
+    actual: usize,
+  },
+}
+
+/// Error while parsing an HTTP message of the .NET Remoting HTTP channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error<'i> {
+  /// The HTTP header is incomplete.
+  IncompleteHeader,
+  /// The request or status line is malformed.
+  InvalidStartLine(&'i [u8]),
+  /// A header line is malformed.
+  InvalidHeader(&'i [u8]),
+  /// The HTTP version is not supported.
+  UnsupportedVersion(&'i str),
+  /// The request verb is not `POST`.
+  UnexpectedRequestVerb(&'i str),
+  /// The `Content-Type` header is missing.
+  MissingContentType,
+  /// The `Content-Type` header is not `application/octet-stream`.
+  UnexpectedContentType(&'i str),
+  /// The `Content-Length` header is not a valid length.
+  InvalidContentLength(&'i str),
+  /// The chunked body is malformed.
+  InvalidChunk,
+}
+
+impl fmt::Display for Error<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::IncompleteHeader => write!(f, "incomplete HTTP header"),
+      Self::InvalidStartLine(line) => write!(f, "invalid start line: {}", String::from_utf8_lossy(line)),
+      Self::InvalidHeader(line) => write!(f, "invalid header: {}", String::from_utf8_lossy(line)),
+      Self::UnsupportedVersion(version) => write!(f, "unsupported HTTP version: {}", version),
+      Self::UnexpectedRequestVerb(verb) => write!(f, "unexpected request verb: {}", verb),
+      Self::MissingContentType => write!(f, "missing Content-Type header"),
+      Self::UnexpectedContentType(content_type) => write!(f, "unexpected content type: {}", content_type),
+      Self::InvalidContentLength(content_length) => write!(f, "invalid Content-Length: {}", content_length),
+      Self::InvalidChunk => write!(f, "invalid chunk"),
+    }
+  }
+}
+
+/// An HTTP request of the .NET Remoting HTTP channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request<'i> {
+  /// The request verb, exposed as the `__RequestVerb` transport header by .NET.
+  pub method: &'i str,
+  /// The request URI, exposed as the `__RequestUri` transport header by .NET.
+  pub uri: &'i str,
+  /// The HTTP version.
+  pub version: &'i str,
+  /// The HTTP headers.
+  pub headers: Vec<Header<'i>>,
+  /// The decoded body.
+  pub body: Cow<'i, [u8]>,
+  /// Irregularities found while extracting the body.
+  pub warnings: Vec<Warning>,
+}
+
+impl<'i> Request<'i> {
+  /// Parse a raw HTTP/1.1 request and extract its body.
+  pub fn parse(input: &'i [u8]) -> Result<Self, Error<'i>> {
+    let (start_line, headers, rest) = split_message(input)?;
+
+    let mut parts = start_line.splitn(3, ' ');
+    let (Some(method), Some(uri), Some(version)) = (parts.next(), parts.next(), parts.next()) else {
+      return Err(Error::InvalidStartLine(start_line.as_bytes()))
+    };
+    check_version(version)?;
+
+    if method != "POST" {
+      return Err(Error::UnexpectedRequestVerb(method))
+    }
+
+    check_content_type(&headers)?;
+    let (body, warnings) = extract_body(&headers, rest)?;
+
+    Ok(Self { method, uri, version, headers, body, warnings })
+  }
+
+  /// Get the value of the header with the given case-insensitive name.
+  pub fn header(&self, name: &str) -> Option<&'i str> {
+    find_header(&self.headers, name)
+  }
+
+  /// Get the `SOAPAction` header without its surrounding quotes, if present.
+  pub fn soap_action(&self) -> Option<&'i str> {
+    self.header("SOAPAction").map(|action| action.trim_matches('"'))
+  }
+
+  /// Decode the body into a [`RemotingMessage`].
+  pub fn message(&self) -> Result<RemotingMessage<'_>, crate::Error<'_>> {
+    RemotingMessage::parse(&self.body)
+  }
+}
+
+/// An HTTP response of the .NET Remoting HTTP channel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response<'i> {
+  /// The HTTP version.
+  pub version: &'i str,
+  /// The status code.
+  pub status_code: u16,
+  /// The reason phrase.
+  pub reason: &'i str,
+  /// The HTTP headers.
+  pub headers: Vec<Header<'i>>,
+  /// The decoded body.
+  pub body: Cow<'i, [u8]>,
+  /// Irregularities found while extracting the body.
+  pub warnings: Vec<Warning>,
+}
+
+impl<'i> Response<'i> {
+  /// Parse a raw HTTP/1.1 response and extract its body.
+  ///
+  /// Responses with a status code other than `200` are accepted as well, since the HTTP channel returns
+  /// remote exceptions with status `500`.
+  pub fn parse(input: &'i [u8]) -> Result<Self, Error<'i>> {
+    let (start_line, headers, rest) = split_message(input)?;
+
+    let mut parts = start_line.splitn(3, ' ');
+    let (Some(version), Some(status_code), reason) = (parts.next(), parts.next(), parts.next().unwrap_or_default())
+    else {
+      return Err(Error::InvalidStartLine(start_line.as_bytes()))
+    };
+    check_version(version)?;
+
+    let Ok(status_code) = status_code.parse() else { return Err(Error::InvalidStartLine(start_line.as_bytes())) };
+
+    check_content_type(&headers)?;
+    let (body, warnings) = extract_body(&headers, rest)?;
+
+    Ok(Self { version, status_code, reason, headers, body, warnings })
+  }
+
+  /// Get the value of the header with the given case-insensitive name.
+  pub fn header(&self, name: &str) -> Option<&'i str> {
+    find_header(&self.headers, name)
+  }
+
+  /// Decode the body into a [`RemotingMessage`].
+  pub fn message(&self) -> Result<RemotingMessage<'_>, crate::Error<'_>> {
+    RemotingMessage::parse(&self.body)
+  }
+}
+
+fn find_header<'i>(headers: &[Header<'i>], name: &str) -> Option<&'i str> {
+  headers.iter().find(|header| header.name.eq_ignore_ascii_case(name)).map(|header| header.value)
+}
+
+/// Split a message into its start line, headers and the remaining bytes.
+fn split_message(input: &[u8]) -> Result<(&str, Vec<Header<'_>>, &[u8]), Error<'_>> {
+  let Some(end) = input.windows(4).position(|w| w == b"\r\n\r\n") else { return Err(Error::IncompleteHeader) };
+  let (head, rest) = (&input[..end], &input[(end + 4)..]);
+
+  let mut lines = head.split(|&b| b == b'\n').map(|line| line.strip_suffix(b"\r").unwrap_or(line));
+
+  let start_line = lines.next().unwrap_or_default();
+  let start_line = str::from_utf8(start_line).map_err(|_| Error::InvalidStartLine(start_line))?;
+
+  let headers = lines
+    .map(|line| {
+      str::from_utf8(line)
+        .ok()
+        .and_then(|l| l.split_once(':'))
+        .map(|(name, value)| Header { name: name.trim(), value: value.trim() })
+        .filter(|header| !header.name.is_empty())
+        .ok_or(Error::InvalidHeader(line))
+    })
+    .collect::<Result<Vec<_>, _>>()?;
+
+  Ok((start_line, headers, rest))
+}
+
+fn check_version(version: &str) -> Result<(), Error<'_>> {
+  match version {
+    "HTTP/1.1" | "HTTP/1.0" => Ok(()),
+    _ => Err(Error::UnsupportedVersion(version)),
+  }
+}
+
+fn check_content_type<'i>(headers: &[Header<'i>]) -> Result<(), Error<'i>> {
+  let Some(content_type) = find_header(headers, "Content-Type") else { return Err(Error::MissingContentType) };
+
+  let media_type = content_type.split(';').next().unwrap_or_default().trim();
+  if media_type.eq_ignore_ascii_case(CONTENT_TYPE) { Ok(()) } else { Err(Error::UnexpectedContentType(content_type)) }
+}
+
+fn extract_body<'i>(headers: &[Header<'i>], rest: &'i [u8]) -> Result<(Cow<'i, [u8]>, Vec<Warning>), Error<'i>> {
+  let mut warnings = vec![];
+
+  let chunked = find_header(headers, "Transfer-Encoding")
+    .is_some_and(|encoding| encoding.split(',').any(|e| e.trim().eq_ignore_ascii_case("chunked")));
+
+  if chunked {
+    warnings.push(Warning::Chunked);
+    return Ok((Cow::Owned(decode_chunked(rest)?), warnings))
+  }
+
+  let body = if let Some(content_length) = find_header(headers, "Content-Length") {
+    let declared = content_length.parse::<usize>().map_err(|_| Error::InvalidContentLength(content_length))?;
+
+    if declared != rest.len() {
+      warnings.push(Warning::ContentLengthMismatch { declared, actual: rest.len() });
+    }
+
+    &rest[..declared.min(rest.len())]
+  } else {
+    rest
+  };
+
+  Ok((Cow::Borrowed(body), warnings))
+}
+
+fn decode_chunked(mut input: &[u8]) -> Result<Vec<u8>, Error<'_>> {
+  let mut body = vec![];
+
+  loop {
+    let Some(end) = input.windows(2).position(|w| w == b"\r\n") else { return Err(Error::InvalidChunk) };
+
+    let size_line = str::from_utf8(&input[..end]).map_err(|_| Error::InvalidChunk)?;
+    let size = size_line.split(';').next().unwrap_or_default().trim();
+    let size = usize::from_str_radix(size, 16).map_err(|_| Error::InvalidChunk)?;
+    input = &input[(end + 2)..];
+
+    if size == 0 {
+      return Ok(body)
+    }
+
+    let Some((chunk, rest)) = input.split_at_checked(size) else { return Err(Error::InvalidChunk) };
+    let Some(rest) = rest.strip_prefix(b"\r\n") else { return Err(Error::InvalidChunk) };
+
+    body.extend_from_slice(chunk);
+    input = rest;
+  }
+}
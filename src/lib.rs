@@ -12,12 +12,33 @@ pub(crate) mod record;
 
 mod binary_parser;
 pub(crate) use binary_parser::BinaryParser;
+mod binary_writer;
+pub(crate) use binary_writer::BinaryWriter;
+pub mod codegen;
 mod combinator;
+pub mod compat;
+pub mod dataset;
 mod error;
 #[doc(inline)]
 pub use error::Error;
+mod fault;
+pub use fault::DotNetFault;
+pub mod http;
+mod member_names;
+pub use member_names::MemberNames;
+#[cfg(feature = "serde")]
+mod options;
+#[cfg(feature = "serde")]
+pub use options::Options;
 mod remoting_message;
 pub use remoting_message::{MethodCall, MethodReturn, RemotingMessage};
+pub mod schema;
+pub use schema::schema;
+pub mod tcp;
+mod type_registry;
+pub use type_registry::{TypeName, TypeRegistry};
+pub mod validate;
+pub use validate::validate;
 
 pub mod value;
 #[doc(inline)]
@@ -53,3 +74,72 @@ where
     RemotingMessage::parse(bytes).map_err(|err| de::Error::custom(format!("parsing error: {}", err)))?;
   T::deserialize(remoting_message)
 }
+
+/// Deserialize an instance of type `T` from bytes of a .NET Remoting message with the given
+/// [`Options`].
+///
+/// # Example
+///
+/// ```
+/// use nrbf::{Options, RemotingMessage, Value, value::Object};
+///
+/// let mut options = Options::new();
+/// options.type_registry.register("Contoso.Money, Contoso.Model", |object| Ok(object.members["units"].clone()));
+///
+/// let money = Object {
+///   class: "Contoso.Money",
+///   library: Some("Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null"),
+///   members: [("units", Value::Int64(42))].into(),
+/// };
+/// let bytes = RemotingMessage::Value(Value::Object(money)).to_bytes();
+///
+/// assert_eq!(nrbf::from_slice_with(&bytes, &options), Ok(42));
+/// ```
+#[cfg(feature = "serde")]
+pub fn from_slice_with<'i, T>(bytes: &'i [u8], options: &Options) -> Result<T, de::value::Error>
+where
+  T: Deserialize<'i>,
+{
+  let remoting_message =
+    RemotingMessage::parse(bytes).map_err(|err| de::Error::custom(format!("parsing error: {}", err)))?;
+  T::deserialize(remoting_message::MessageDeserializer::new(&remoting_message, options))
+}
+
+/// Deserialize an instance of type `T` from a parsed [`Value`] with the given [`Options`].
+///
+/// This can be used in a converter of a [`TypeRegistry`] to read the members of an object into a
+/// Rust type instead of matching them one by one.
+///
+/// # Example
+///
+/// ```
+/// use nrbf::{Options, Value, value::Object};
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Money {
+///   units: i64,
+///   scale: i32,
+/// }
+///
+/// let mut options = Options::new();
+/// options.type_registry.register("Contoso.Money", |object| {
+///   let money: Money = nrbf::from_value_with(&Value::Object(object.clone()), &Options::new())?;
+///   Ok(Value::Double(money.units as f64 / 10f64.powi(money.scale)))
+/// });
+///
+/// let money = Object {
+///   class: "Contoso.Money",
+///   library: Some("Contoso.Model"),
+///   members: [("units", Value::Int64(1234)), ("scale", Value::Int32(2))].into(),
+/// };
+///
+/// assert_eq!(nrbf::from_value_with(&Value::Object(money), &options), Ok(12.34));
+/// ```
+#[cfg(feature = "serde")]
+pub fn from_value_with<'i, T>(value: &Value<'i>, options: &Options) -> Result<T, de::value::Error>
+where
+  T: Deserialize<'i>,
+{
+  T::deserialize(value::ValueDeserializer::new(value, options))
+}
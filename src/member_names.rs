@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+
+/// How member names of objects are presented to serde.
+///
+/// # Example
+///
+/// ```
+/// use nrbf::MemberNames;
+///
+/// assert_eq!(MemberNames::Verbatim.apply("Order+<OrderId>k__BackingField"), "Order+<OrderId>k__BackingField");
+/// assert_eq!(MemberNames::Normalized.apply("Order+<OrderId>k__BackingField"), "OrderId");
+/// assert_eq!(MemberNames::SnakeCase.apply("Order+<OrderId>k__BackingField"), "order_id");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MemberNames {
+  /// Use the serialized member names.
+  #[default]
+  Verbatim,
+  /// Use the names of auto-properties instead of their backing fields, i.e. `Name` instead of
+  /// `<Name>k__BackingField`, and strip the class name prefix of inherited members, i.e. `field`
+  /// instead of `BaseClass+field`.
+  ///
+  /// Inherited members keep their prefix if it is needed to distinguish them from other members.
+  Normalized,
+  /// Like [`Normalized`](Self::Normalized), but additionally convert names to snake case, e.g.
+  /// `OrderId` to `order_id`.
+  SnakeCase,
+}
+
+impl MemberNames {
+  /// Apply the naming scheme to a member name.
+  pub fn apply(self, name: &str) -> Cow<'_, str> {
+    match self {
+      Self::Verbatim => Cow::Borrowed(name),
+      Self::Normalized => Cow::Borrowed(property_name(strip_class_prefix(name))),
+      Self::SnakeCase => snake_case(property_name(strip_class_prefix(name))),
+    }
+  }
+}
+
+/// Strip the class name prefix of an inherited member, e.g. `BaseClass+field`.
+fn strip_class_prefix(name: &str) -> &str {
+  name.rsplit_once('+').map_or(name, |(_, name)| name)
+}
+
+/// Get the property name of an auto-property backing field, e.g. `<Name>k__BackingField`.
+fn property_name(name: &str) -> &str {
+  name.strip_prefix('<').and_then(|name| name.strip_suffix(">k__BackingField")).unwrap_or(name)
+}
+
+/// Convert a PascalCase or camelCase name to snake case, keeping acronyms together, e.g.
+/// `HTTPServer` becomes `http_server`.
+fn snake_case(name: &str) -> Cow<'_, str> {
+  if !name.chars().any(char::is_uppercase) {
+    return Cow::Borrowed(name)
+  }
+
+  let chars = name.chars().collect::<Vec<_>>();
+  let mut snake_case = String::with_capacity(name.len() + 4);
+
+  for (i, c) in chars.iter().enumerate() {
+    if c.is_uppercase() && i > 0 {
+      let previous = chars[i - 1];
+      let next_is_lowercase = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+
+      if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lowercase) {
+        snake_case.push('_');
+      }
+    }
+
+    snake_case.extend(c.to_lowercase());
+  }
+
+  Cow::Owned(snake_case)
+}
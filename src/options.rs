@@ -0,0 +1,35 @@
+use crate::{MemberNames, TypeRegistry};
+
+/// Options for deserializing with [`from_slice_with`](crate::from_slice_with).
+#[derive(Debug, Default)]
+pub struct Options {
+  /// Converters applied to objects before deserializing them.
+  pub type_registry: TypeRegistry,
+  /// How member names of objects are presented to serde.
+  pub member_names: MemberNames,
+  /// The key of a virtual member holding the class name of objects of non-system classes, e.g.
+  /// `$type` for use with `#[serde(tag = "$type")]`.
+  pub type_tag: Option<&'static str>,
+}
+
+/// The options used by [`from_slice`](crate::from_slice).
+pub(crate) static DEFAULT_OPTIONS: Options = Options::new();
+
+impl Options {
+  /// Create the default options.
+  pub const fn new() -> Self {
+    Self { type_registry: TypeRegistry::new(), member_names: MemberNames::Verbatim, type_tag: None }
+  }
+
+  /// Set how member names of objects are presented to serde.
+  pub fn with_member_names(mut self, member_names: MemberNames) -> Self {
+    self.member_names = member_names;
+    self
+  }
+
+  /// Set the key of a virtual member holding the class name of objects.
+  pub fn with_type_tag(mut self, type_tag: &'static str) -> Self {
+    self.type_tag = Some(type_tag);
+    self
+  }
+}
@@ -1,12 +1,19 @@
 #[cfg(feature = "serde")]
 use serde::{
+  Deserialize,
   de::{self, Deserializer, Visitor},
   forward_to_deserialize_any,
 };
 
 #[cfg(feature = "serde")]
-use crate::value::ValueDeserializer;
-use crate::{BinaryParser, Error, Value};
+use crate::DotNetFault;
+use crate::{BinaryParser, BinaryWriter, Error, Value};
+#[cfg(feature = "serde")]
+use crate::{
+  Options,
+  options::DEFAULT_OPTIONS,
+  value::{ArrayDeserializer, ValueDeserializer},
+};
 
 /// A remote method call.
 #[derive(Debug, Clone, PartialEq)]
@@ -30,6 +37,120 @@ pub struct MethodReturn<'i> {
   pub call_context: Option<&'i str>,
   /// The arguments, if present.
   pub args: Option<Vec<Value<'i>>>,
+  /// The exception thrown by the remote method, if present.
+  pub exception: Option<Value<'i>>,
+}
+
+impl<'i> MethodReturn<'i> {
+  /// Create a method return with the given return value.
+  pub fn new(return_value: Option<Value<'i>>) -> Self {
+    Self { return_value, call_context: None, args: None, exception: None }
+  }
+
+  /// Create a method return for an exception thrown by the remote method.
+  pub fn from_exception(exception: Value<'i>) -> Self {
+    Self { return_value: None, call_context: None, args: None, exception: Some(exception) }
+  }
+
+  /// Set the logical call ID.
+  pub fn with_call_context(mut self, call_context: Option<&'i str>) -> Self {
+    self.call_context = call_context;
+    self
+  }
+
+  /// Set the output arguments.
+  pub fn with_args(mut self, args: Vec<Value<'i>>) -> Self {
+    self.args = Some(args);
+    self
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'i> MethodCall<'i> {
+  /// Deserialize the arguments into a type deserializable from a sequence, e.g. a tuple or a struct
+  /// with its fields in argument order.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use nrbf::{MethodCall, Value};
+  ///
+  /// let method_call = MethodCall {
+  ///   method_name: "Add",
+  ///   type_name: "Server.Calculator, App",
+  ///   call_context: None,
+  ///   args: Some(vec![Value::Int32(2), Value::String("three")]),
+  /// };
+  ///
+  /// assert_eq!(method_call.deserialize_args(), Ok((2, "three")));
+  /// ```
+  pub fn deserialize_args<T>(&self) -> Result<T, de::value::Error>
+  where
+    T: Deserialize<'i>,
+  {
+    self.deserialize_args_with(&DEFAULT_OPTIONS)
+  }
+
+  /// Deserialize the arguments like [`deserialize_args`](Self::deserialize_args) with the given
+  /// [`Options`].
+  pub fn deserialize_args_with<T>(&self, options: &Options) -> Result<T, de::value::Error>
+  where
+    T: Deserialize<'i>,
+  {
+    let args = self.args.as_deref().unwrap_or_default();
+    T::deserialize(ArrayDeserializer::new(args.iter(), options))
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'i> MethodReturn<'i> {
+  /// Deserialize the return value, treating a missing return value as null.
+  ///
+  /// Fails if the method return contains an exception.
+  pub fn deserialize_return<T>(&self) -> Result<T, de::value::Error>
+  where
+    T: Deserialize<'i>,
+  {
+    self.deserialize_return_with(&DEFAULT_OPTIONS)
+  }
+
+  /// Deserialize the return value like [`deserialize_return`](Self::deserialize_return) with the
+  /// given [`Options`].
+  pub fn deserialize_return_with<T>(&self, options: &Options) -> Result<T, de::value::Error>
+  where
+    T: Deserialize<'i>,
+  {
+    use serde::de::Error;
+
+    if let Some(exception) = &self.exception {
+      return Err(match DotNetFault::from_exception(exception) {
+        Some(fault) => de::value::Error::custom(format!("remote exception: {}", fault)),
+        None => de::value::Error::custom("remote exception"),
+      })
+    }
+
+    match &self.return_value {
+      Some(return_value) => T::deserialize(ValueDeserializer::new(return_value, options)),
+      None => T::deserialize(ValueDeserializer::new(&Value::Null, options)),
+    }
+  }
+
+  /// Deserialize the output arguments like [`MethodCall::deserialize_args`].
+  pub fn deserialize_args<T>(&self) -> Result<T, de::value::Error>
+  where
+    T: Deserialize<'i>,
+  {
+    self.deserialize_args_with(&DEFAULT_OPTIONS)
+  }
+
+  /// Deserialize the output arguments like [`MethodCall::deserialize_args_with`].
+  pub fn deserialize_args_with<T>(&self, options: &Options) -> Result<T, de::value::Error>
+  where
+    T: Deserialize<'i>,
+  {
+    let args = self.args.as_deref().unwrap_or_default();
+    T::deserialize(ArrayDeserializer::new(args.iter(), options))
+  }
 }
 
 /// A .NET Remoting message.
@@ -77,20 +198,127 @@ impl<'i> RemotingMessage<'i> {
     parser.deserialize(input)
   }
 
-  #[cfg(feature = "serde")]
-  fn to_deserializer<V: Visitor<'i>>(&self, visitor: &V) -> Result<ValueDeserializer<'i, '_>, de::value::Error> {
+  /// Serialize a [`RemotingMessage`] into bytes.
+  ///
+  /// Objects are written with member type information like `BinaryFormatter` does by default. The
+  /// member types are inferred from the member values, so null members are typed as `Object`.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use nrbf::{RemotingMessage, Value};
+  ///
+  /// let message = RemotingMessage::Value(Value::String("This is a string."));
+  /// let bytes = message.to_bytes();
+  ///
+  /// assert_eq!(RemotingMessage::parse(&bytes), Ok(message));
+  /// ```
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let writer = BinaryWriter::default();
+    writer.serialize(self)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserializer<'de> for RemotingMessage<'de> {
+  type Error = de::value::Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_any(visitor)
+  }
+
+  fn deserialize_struct<V>(
+    self,
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_struct(name, fields, visitor)
+  }
+
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_option(visitor)
+  }
+
+  fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_tuple(len, visitor)
+  }
+
+  fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_bytes(visitor)
+  }
+
+  fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_byte_buf(visitor)
+  }
+
+  fn deserialize_enum<V>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    MessageDeserializer::new(&self, &DEFAULT_OPTIONS).deserialize_enum(name, variants, visitor)
+  }
+
+  forward_to_deserialize_any! {
+      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+      unit unit_struct newtype_struct seq tuple_struct map identifier ignored_any
+  }
+}
+
+/// Deserializer of a [`RemotingMessage`] with the given [`Options`].
+///
+/// Only values can be deserialized, method calls and returns are deserialized with
+/// [`MethodCall::deserialize_args_with`] and [`MethodReturn::deserialize_return_with`].
+#[cfg(feature = "serde")]
+pub(crate) struct MessageDeserializer<'de, 'o> {
+  message: &'o RemotingMessage<'de>,
+  options: &'o Options,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'o> MessageDeserializer<'de, 'o> {
+  pub fn new(message: &'o RemotingMessage<'de>, options: &'o Options) -> Self {
+    Self { message, options }
+  }
+
+  fn to_deserializer<V: Visitor<'de>>(&self, visitor: &V) -> Result<ValueDeserializer<'de, 'o>, de::value::Error> {
     use serde::de::{Error, Unexpected};
 
-    match self {
-      Self::MethodCall(..) => Err(de::value::Error::invalid_type(Unexpected::Other("method call"), visitor)),
-      Self::MethodReturn(..) => Err(de::value::Error::invalid_type(Unexpected::Other("method return"), visitor)),
-      Self::Value(root_object) => Ok(ValueDeserializer::new(root_object)),
+    match self.message {
+      RemotingMessage::MethodCall(..) => Err(de::value::Error::invalid_type(Unexpected::Other("method call"), visitor)),
+      RemotingMessage::MethodReturn(..) => {
+        Err(de::value::Error::invalid_type(Unexpected::Other("method return"), visitor))
+      },
+      RemotingMessage::Value(root_object) => Ok(ValueDeserializer::new(root_object, self.options)),
     }
   }
 }
 
 #[cfg(feature = "serde")]
-impl<'de> Deserializer<'de> for RemotingMessage<'de> {
+impl<'de> Deserializer<'de> for MessageDeserializer<'de, '_> {
   type Error = de::value::Error;
 
   fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -112,9 +340,62 @@ impl<'de> Deserializer<'de> for RemotingMessage<'de> {
     self.to_deserializer(&visitor)?.deserialize_struct(name, fields, visitor)
   }
 
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_option(visitor)
+  }
+
+  fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_tuple(len, visitor)
+  }
+
+  fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_bytes(visitor)
+  }
+
+  fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_byte_buf(visitor)
+  }
+
+  fn deserialize_enum<V>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_enum(name, variants, visitor)
+  }
+
+  fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_str(visitor)
+  }
+
+  fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.to_deserializer(&visitor)?.deserialize_string(visitor)
+  }
+
   forward_to_deserialize_any! {
-      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-      bytes byte_buf option unit unit_struct newtype_struct seq tuple
-      tuple_struct map enum identifier ignored_any
+      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+      unit unit_struct newtype_struct seq tuple_struct map identifier ignored_any
   }
 }
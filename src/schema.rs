@@ -0,0 +1,240 @@
+//! Extraction of the class and array metadata of a payload.
+//!
+//! A [`Schema`] lists the libraries, class metadata records and array records of a payload in the
+//! order in which they appear, e.g. for checking contracts between service versions or for reviewing
+//! which types a payload references.
+
+pub use crate::enumeration::{BinaryArrayType, PrimitiveType};
+use crate::{
+  BinaryParser, Error,
+  binary_parser::Class,
+  common::{AdditionalTypeInfo, MemberTypeInfo},
+  enumeration::BinaryType,
+  record::BinaryArray,
+};
+
+/// Extract the [`Schema`] of a payload.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use nrbf::{
+///   RemotingMessage, Value,
+///   schema::{BinaryArrayType, MemberType},
+///   value::Object,
+/// };
+///
+/// let point = Object {
+///   class: "Contoso.Point",
+///   library: Some("Contoso"),
+///   members: HashMap::from_iter([("x", Value::Int32(1))]),
+/// };
+/// let bytes = RemotingMessage::Value(Value::Array(vec![Value::Object(point)])).to_bytes();
+///
+/// let schema = nrbf::schema(&bytes).unwrap();
+/// assert_eq!(schema.libraries[0].name, "Contoso");
+///
+/// let class = &schema.classes[0];
+/// assert_eq!(class.name, "Contoso.Point");
+/// assert_eq!(class.library_id, Some(schema.libraries[0].id));
+/// assert_eq!(class.members[0].name, "x");
+///
+/// assert_eq!(schema.arrays[0].array_type, BinaryArrayType::Single);
+/// assert_eq!(schema.arrays[0].element_type, MemberType::Object);
+/// ```
+pub fn schema(bytes: &[u8]) -> Result<Schema<'_>, Error<'_>> {
+  BinaryParser::default().deserialize_schema(bytes)
+}
+
+/// The metadata records of a payload.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema<'i> {
+  /// The `BinaryLibrary` records.
+  pub libraries: Vec<Library<'i>>,
+  /// The class metadata records.
+  pub classes: Vec<ClassMetadata<'i>>,
+  /// The array records.
+  pub arrays: Vec<ArrayMetadata<'i>>,
+}
+
+impl<'i> Schema<'i> {
+  /// Get a library by its ID.
+  pub fn library(&self, id: u32) -> Option<&Library<'i>> {
+    self.libraries.iter().find(|library| library.id == id)
+  }
+
+  /// Get the metadata of an object by its ID, including objects reusing metadata via `ClassWithId`.
+  pub fn class(&self, object_id: u32) -> Option<&ClassMetadata<'i>> {
+    self.classes.iter().find(|class| class.object_id == object_id || class.reused_by.contains(&object_id))
+  }
+
+  pub(crate) fn add_library(&mut self, id: u32, name: &'i str) {
+    self.libraries.push(Library { id, name });
+  }
+
+  pub(crate) fn add_class(&mut self, object_id: u32, class: &Class<'i>) {
+    let (class_info, library_id, member_type_info) = match class {
+      Class::ClassWithMembers(class) => (&class.class_info, Some(class.library_id.get()), None),
+      Class::ClassWithMembersAndTypes(class) => {
+        (&class.class_info, Some(class.library_id.get()), Some(&class.member_type_info))
+      },
+      Class::SystemClassWithMembers(class) => (&class.class_info, None, None),
+      Class::SystemClassWithMembersAndTypes(class) => (&class.class_info, None, Some(&class.member_type_info)),
+    };
+
+    let member_types = member_type_info.map(|MemberTypeInfo { binary_type_enums, additional_infos }| {
+      binary_type_enums.iter().zip(additional_infos).map(|(t, info)| MemberType::new(*t, info.as_ref()))
+    });
+    let mut member_types = member_types.into_iter().flatten();
+
+    let members = class_info
+      .member_names
+      .iter()
+      .map(|name| MemberMetadata { name: name.as_str(), member_type: member_types.next().flatten() })
+      .collect();
+
+    self.classes.push(ClassMetadata {
+      object_id,
+      name: class_info.name.as_str(),
+      library_id,
+      members,
+      reused_by: Vec::new(),
+    });
+  }
+
+  pub(crate) fn add_class_with_id(&mut self, object_id: u32, metadata_id: u32) {
+    if let Some(class) =
+      self.classes.iter_mut().find(|class| class.object_id == metadata_id || class.reused_by.contains(&metadata_id))
+    {
+      class.reused_by.push(object_id);
+    }
+  }
+
+  pub(crate) fn add_array(&mut self, object_id: u32, length: usize, element_type: MemberType<'i>) {
+    self.arrays.push(ArrayMetadata {
+      object_id,
+      array_type: BinaryArrayType::Single,
+      lengths: vec![length],
+      lower_bounds: None,
+      element_type,
+    });
+  }
+
+  pub(crate) fn add_binary_array(&mut self, binary_array: &BinaryArray<'i>) {
+    if let Some(element_type) = MemberType::new(binary_array.type_enum, binary_array.additional_type_info.as_ref()) {
+      self.arrays.push(ArrayMetadata {
+        object_id: binary_array.object_id.get(),
+        array_type: binary_array.binary_array_type_enum,
+        lengths: binary_array.lengths.clone(),
+        lower_bounds: binary_array.lower_bounds.clone(),
+        element_type,
+      });
+    }
+  }
+}
+
+/// A `BinaryLibrary` record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Library<'i> {
+  /// The library ID.
+  pub id: u32,
+  /// The assembly name of the library.
+  pub name: &'i str,
+}
+
+/// A class metadata record, i.e. a `ClassWithMembers`, `ClassWithMembersAndTypes`,
+/// `SystemClassWithMembers` or `SystemClassWithMembersAndTypes` record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassMetadata<'i> {
+  /// The ID of the object defining the metadata.
+  pub object_id: u32,
+  /// The class name.
+  pub name: &'i str,
+  /// The library ID, or `None` for system classes.
+  pub library_id: Option<u32>,
+  /// The members.
+  pub members: Vec<MemberMetadata<'i>>,
+  /// The IDs of objects reusing the metadata via `ClassWithId` records.
+  pub reused_by: Vec<u32>,
+}
+
+impl<'i> ClassMetadata<'i> {
+  /// Get a member by its name.
+  pub fn member(&self, name: &str) -> Option<&MemberMetadata<'i>> {
+    self.members.iter().find(|member| member.name == name)
+  }
+}
+
+/// The metadata of a class member.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberMetadata<'i> {
+  /// The member name.
+  pub name: &'i str,
+  /// The member type from the `MemberTypeInfo`, or `None` if the record has no type information.
+  pub member_type: Option<MemberType<'i>>,
+}
+
+/// The type of a class member or an array element, i.e. a `BinaryTypeEnumeration` together with its
+/// additional type information.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MemberType<'i> {
+  /// A primitive value.
+  Primitive(PrimitiveType),
+  /// A string.
+  String,
+  /// An object of any type.
+  Object,
+  /// An instance of a class in the system library.
+  SystemClass(&'i str),
+  /// An instance of a class in another library.
+  Class {
+    /// The class name.
+    name: &'i str,
+    /// The library ID.
+    library_id: u32,
+  },
+  /// An array of objects.
+  ObjectArray,
+  /// An array of strings.
+  StringArray,
+  /// An array of primitive values.
+  PrimitiveArray(PrimitiveType),
+}
+
+impl<'i> MemberType<'i> {
+  fn new(binary_type: BinaryType, additional_info: Option<&AdditionalTypeInfo<'i>>) -> Option<Self> {
+    Some(match (binary_type, additional_info) {
+      (BinaryType::Primitive, Some(AdditionalTypeInfo::Primitive(primitive_type))) => Self::Primitive(*primitive_type),
+      (BinaryType::String, None) => Self::String,
+      (BinaryType::Object, None) => Self::Object,
+      (BinaryType::SystemClass, Some(AdditionalTypeInfo::SystemClass(name))) => Self::SystemClass(name.as_str()),
+      (BinaryType::Class, Some(AdditionalTypeInfo::Class(class_type_info))) => {
+        Self::Class { name: class_type_info.type_name.as_str(), library_id: class_type_info.library_id().get() }
+      },
+      (BinaryType::ObjectArray, None) => Self::ObjectArray,
+      (BinaryType::StringArray, None) => Self::StringArray,
+      (BinaryType::PrimitiveArray, Some(AdditionalTypeInfo::Primitive(primitive_type))) => {
+        Self::PrimitiveArray(*primitive_type)
+      },
+      _ => return None,
+    })
+  }
+}
+
+/// An array record, i.e. an `ArraySingleObject`, `ArraySinglePrimitive`, `ArraySingleString` or
+/// `BinaryArray` record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayMetadata<'i> {
+  /// The ID of the array.
+  pub object_id: u32,
+  /// The array type, always [`BinaryArrayType::Single`] for `ArraySingle*` records.
+  pub array_type: BinaryArrayType,
+  /// The length of each dimension.
+  pub lengths: Vec<usize>,
+  /// The lower bound of each dimension, if any.
+  pub lower_bounds: Option<Vec<usize>>,
+  /// The element type.
+  pub element_type: MemberType<'i>,
+}
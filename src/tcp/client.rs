@@ -0,0 +1,188 @@
+use std::{error, fmt, io, net::TcpStream};
+
+use super::{Message, OperationType, STATUS_CODE_ERROR};
+use crate::{DotNetFault, MethodCall, MethodReturn, RemotingMessage, Value};
+
+/// Error while calling a remote method over the TCP channel.
+#[derive(Debug)]
+pub enum ClientError {
+  /// The object URI is not a valid `tcp://host:port/path` URI.
+  InvalidUri(String),
+  /// Sending the request or receiving the reply failed.
+  Io(io::Error),
+  /// The reply body could not be decoded.
+  Decode(String),
+  /// The reply body is not a method return.
+  UnexpectedMessage,
+  /// The remote method threw an exception.
+  Fault(DotNetFault),
+}
+
+impl fmt::Display for ClientError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidUri(uri) => write!(f, "invalid object URI: {}", uri),
+      Self::Io(err) => write!(f, "I/O error: {}", err),
+      Self::Decode(err) => write!(f, "decoding error: {}", err),
+      Self::UnexpectedMessage => write!(f, "expected a method return"),
+      Self::Fault(fault) => write!(f, "remote exception: {}", fault),
+    }
+  }
+}
+
+impl error::Error for ClientError {
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      Self::Io(err) => Some(err),
+      Self::Fault(fault) => Some(fault),
+      _ => None,
+    }
+  }
+}
+
+impl From<io::Error> for ClientError {
+  fn from(err: io::Error) -> Self {
+    Self::Io(err)
+  }
+}
+
+impl From<DotNetFault> for ClientError {
+  fn from(fault: DotNetFault) -> Self {
+    Self::Fault(fault)
+  }
+}
+
+/// The reply to a remote method call, owning the received message body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+  body: Vec<u8>,
+}
+
+impl Reply {
+  /// Get the raw message body.
+  pub fn body(&self) -> &[u8] {
+    &self.body
+  }
+
+  /// Decode the [`MethodReturn`], converting a returned exception into [`ClientError::Fault`].
+  pub fn method_return(&self) -> Result<MethodReturn<'_>, ClientError> {
+    let method_return = match RemotingMessage::parse(&self.body) {
+      Ok(RemotingMessage::MethodReturn(method_return)) => method_return,
+      Ok(_) => return Err(ClientError::UnexpectedMessage),
+      Err(err) => return Err(ClientError::Decode(err.to_string())),
+    };
+
+    if let Some(exception) = &method_return.exception {
+      let fault = DotNetFault::from_exception(exception)
+        .unwrap_or_else(|| DotNetFault::new("System.Exception", "unknown remote exception"));
+      return Err(ClientError::Fault(fault))
+    }
+
+    Ok(method_return)
+  }
+
+  /// Decode the return value, which is [`Value::Null`] for `void` methods.
+  pub fn return_value(&self) -> Result<Value<'_>, ClientError> {
+    Ok(self.method_return()?.return_value.unwrap_or(Value::Null))
+  }
+}
+
+/// A client calling remote methods of a server object over the TCP channel.
+///
+/// The connection is established on the first call and re-established after an I/O error.
+///
+/// # Example
+///
+/// ```no_run
+/// use nrbf::{Value, tcp::RemotingClient};
+///
+/// let mut client = RemotingClient::new("tcp://localhost:8080/Calculator.rem")?;
+///
+/// let reply = client.call("Server.ICalculator, App", "Add", &[Value::Int32(2), Value::Int32(3)])?;
+/// assert_eq!(reply.return_value()?, Value::Int32(5));
+/// # Ok::<(), nrbf::tcp::ClientError>(())
+/// ```
+#[derive(Debug)]
+pub struct RemotingClient {
+  authority: String,
+  object_uri: String,
+  stream: Option<TcpStream>,
+}
+
+impl RemotingClient {
+  /// Create a client for the server object with the given `tcp://host:port/path` URI.
+  pub fn new(uri: &str) -> Result<Self, ClientError> {
+    let invalid_uri = || ClientError::InvalidUri(uri.to_owned());
+
+    let rest = uri.strip_prefix("tcp://").ok_or_else(invalid_uri)?;
+    let (authority, path) = rest.split_once('/').ok_or_else(invalid_uri)?;
+
+    match authority.rsplit_once(':') {
+      Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => (),
+      _ => return Err(invalid_uri()),
+    }
+    if path.is_empty() {
+      return Err(invalid_uri())
+    }
+
+    Ok(Self { authority: authority.to_owned(), object_uri: format!("/{}", path), stream: None })
+  }
+
+  /// Get the URI of the server object, relative to the server.
+  pub fn object_uri(&self) -> &str {
+    &self.object_uri
+  }
+
+  /// Call a method of the given server type and wait for the reply.
+  pub fn call(&mut self, type_name: &str, method_name: &str, args: &[Value<'_>]) -> Result<Reply, ClientError> {
+    let message = self.request(type_name, method_name, args, OperationType::Request);
+
+    let reply = self.exchange(|stream| {
+      message.write_to(stream)?;
+      Message::read_from(stream)
+    })?;
+
+    if reply.operation_type != OperationType::Reply {
+      return Err(ClientError::UnexpectedMessage)
+    }
+
+    if reply.status_code() == Some(STATUS_CODE_ERROR) {
+      let message = reply.status_phrase().unwrap_or("remoting error");
+      return Err(ClientError::Fault(DotNetFault::new("System.Runtime.Remoting.RemotingException", message)))
+    }
+
+    Ok(Reply { body: reply.body })
+  }
+
+  /// Call a one-way method of the given server type without waiting for a reply.
+  pub fn call_one_way(&mut self, type_name: &str, method_name: &str, args: &[Value<'_>]) -> Result<(), ClientError> {
+    let message = self.request(type_name, method_name, args, OperationType::OneWayRequest);
+
+    self.exchange(|stream| message.write_to(stream))
+  }
+
+  fn request(&self, type_name: &str, method_name: &str, args: &[Value<'_>], operation_type: OperationType) -> Message {
+    let method_call = RemotingMessage::MethodCall(MethodCall {
+      method_name,
+      type_name,
+      call_context: None,
+      args: (!args.is_empty()).then(|| args.to_vec()),
+    });
+
+    let mut message = Message::request(self.object_uri.clone(), method_call.to_bytes());
+    message.operation_type = operation_type;
+    message
+  }
+
+  fn exchange<T>(&mut self, f: impl FnOnce(&mut TcpStream) -> io::Result<T>) -> Result<T, ClientError> {
+    let stream = match &mut self.stream {
+      Some(stream) => stream,
+      stream => stream.insert(TcpStream::connect(&self.authority)?),
+    };
+
+    f(stream).map_err(|err| {
+      self.stream = None;
+      ClientError::Io(err)
+    })
+  }
+}
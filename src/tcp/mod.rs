@@ -0,0 +1,346 @@
+//! The .NET Remoting TCP channel.
+//!
+//! Messages sent over the TCP channel are framed as described in
+//! [MS-NRTP 2.2.3](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-nrtp/1a9aa5ab-1a9e-4d5d-a7ca-cc4ebbf9b72e)
+//! and contain an NRBF message as their body.
+
+use std::io::{self, Read, Write};
+
+mod client;
+pub use client::{ClientError, RemotingClient, Reply};
+mod server;
+pub use server::RemotingServer;
+
+/// The protocol identifier at the start of each message.
+pub const PROTOCOL_ID: [u8; 4] = *b".NET";
+
+/// The content type of NRBF message bodies.
+pub const CONTENT_TYPE: &str = "application/octet-stream";
+
+/// The status code of a successful reply.
+pub const STATUS_CODE_SUCCESS: u16 = 0;
+/// The status code of a reply indicating an error.
+pub const STATUS_CODE_ERROR: u16 = 1;
+
+/// 2.2.3.1.1 `OperationType`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum OperationType {
+  /// A request expecting a reply.
+  Request       = 0,
+  /// A request not expecting a reply.
+  OneWayRequest = 1,
+  /// A reply to a request.
+  Reply         = 2,
+}
+
+/// 2.2.3.1.2 `ContentDistribution`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+enum ContentDistribution {
+  NotChunked = 0,
+  Chunked    = 1,
+}
+
+/// 2.2.3.1.3 `HeaderToken`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+enum HeaderToken {
+  EndHeaders      = 0,
+  Custom          = 1,
+  StatusCode      = 2,
+  StatusPhrase    = 3,
+  RequestUri      = 4,
+  CloseConnection = 5,
+  ContentType     = 6,
+}
+
+/// 2.2.3.1.4 `HeaderDataFormat`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum HeaderDataFormat {
+  Void          = 0,
+  CountedString = 1,
+  UInt16        = 3,
+}
+
+/// 2.2.3.1.5 `StringEncoding`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum StringEncoding {
+  Unicode = 0,
+  Utf8    = 1,
+}
+
+/// A header of a TCP channel message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Header {
+  /// A custom header.
+  Custom {
+    /// The header name.
+    name: String,
+    /// The header value.
+    value: String,
+  },
+  /// The status of a reply, either [`STATUS_CODE_SUCCESS`] or [`STATUS_CODE_ERROR`].
+  StatusCode(u16),
+  /// A description of the status of a reply.
+  StatusPhrase(String),
+  /// The URI of the requested server object.
+  RequestUri(String),
+  /// A request to close the connection after the reply.
+  CloseConnection,
+  /// The content type of the body.
+  ContentType(String),
+}
+
+/// A message of the .NET Remoting TCP channel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+  /// The operation type.
+  pub operation_type: OperationType,
+  /// The message headers.
+  pub headers: Vec<Header>,
+  /// The message body, usually containing an NRBF message.
+  pub body: Vec<u8>,
+}
+
+impl Message {
+  /// Create a request for the server object with the given URI.
+  pub fn request(request_uri: impl Into<String>, body: Vec<u8>) -> Self {
+    Self {
+      operation_type: OperationType::Request,
+      headers: vec![Header::RequestUri(request_uri.into()), Header::ContentType(CONTENT_TYPE.into())],
+      body,
+    }
+  }
+
+  /// Create a successful reply.
+  pub fn reply(body: Vec<u8>) -> Self {
+    Self { operation_type: OperationType::Reply, headers: vec![], body }
+  }
+
+  /// Get the URI of the requested server object, if present.
+  pub fn request_uri(&self) -> Option<&str> {
+    self.headers.iter().find_map(|header| match header {
+      Header::RequestUri(request_uri) => Some(request_uri.as_str()),
+      _ => None,
+    })
+  }
+
+  /// Get the status code of a reply, if present.
+  pub fn status_code(&self) -> Option<u16> {
+    self.headers.iter().find_map(|header| match header {
+      Header::StatusCode(status_code) => Some(*status_code),
+      _ => None,
+    })
+  }
+
+  /// Get the status phrase of a reply, if present.
+  pub fn status_phrase(&self) -> Option<&str> {
+    self.headers.iter().find_map(|header| match header {
+      Header::StatusPhrase(status_phrase) => Some(status_phrase.as_str()),
+      _ => None,
+    })
+  }
+
+  /// Whether the connection should be closed after this message.
+  pub fn close_connection(&self) -> bool {
+    self.headers.contains(&Header::CloseConnection)
+  }
+
+  /// Read a message from the given reader.
+  pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+    let mut protocol_id = [0; 4];
+    reader.read_exact(&mut protocol_id)?;
+    if protocol_id != PROTOCOL_ID {
+      return Err(invalid_data("invalid protocol ID"))
+    }
+
+    let [major_version, minor_version] = read_array(reader)?;
+    if (major_version, minor_version) != (1, 0) {
+      return Err(invalid_data("unsupported protocol version"))
+    }
+
+    let operation_type = match read_u16(reader)? {
+      0 => OperationType::Request,
+      1 => OperationType::OneWayRequest,
+      2 => OperationType::Reply,
+      _ => return Err(invalid_data("invalid operation type")),
+    };
+
+    let content_length = match read_u16(reader)? {
+      d if d == ContentDistribution::NotChunked as u16 => Some(read_length(reader)?),
+      d if d == ContentDistribution::Chunked as u16 => None,
+      _ => return Err(invalid_data("invalid content distribution")),
+    };
+
+    let mut headers = vec![];
+    loop {
+      let header = match read_u16(reader)? {
+        0 => break,
+        1 => Header::Custom { name: read_counted_string(reader)?, value: read_counted_string(reader)? },
+        2 => {
+          expect_data_format(reader, HeaderDataFormat::UInt16)?;
+          Header::StatusCode(read_u16(reader)?)
+        },
+        3 => {
+          expect_data_format(reader, HeaderDataFormat::CountedString)?;
+          Header::StatusPhrase(read_counted_string(reader)?)
+        },
+        4 => {
+          expect_data_format(reader, HeaderDataFormat::CountedString)?;
+          Header::RequestUri(read_counted_string(reader)?)
+        },
+        5 => {
+          expect_data_format(reader, HeaderDataFormat::Void)?;
+          Header::CloseConnection
+        },
+        6 => {
+          expect_data_format(reader, HeaderDataFormat::CountedString)?;
+          Header::ContentType(read_counted_string(reader)?)
+        },
+        _ => return Err(invalid_data("invalid header token")),
+      };
+
+      headers.push(header);
+    }
+
+    let body = if let Some(content_length) = content_length {
+      read_vec(reader, content_length)?
+    } else {
+      let mut body = vec![];
+
+      loop {
+        let chunk_size = read_length(reader)?;
+        if chunk_size == 0 {
+          break
+        }
+
+        body.extend(read_vec(reader, chunk_size)?);
+
+        if read_array(reader)? != *b"\r\n" {
+          return Err(invalid_data("invalid chunk delimiter"))
+        }
+      }
+
+      body
+    };
+
+    Ok(Self { operation_type, headers, body })
+  }
+
+  /// Write the message to the given writer.
+  pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+    let mut output = Vec::with_capacity(self.body.len() + 64);
+
+    output.extend(PROTOCOL_ID);
+    output.extend([1, 0]);
+    output.extend((self.operation_type as u16).to_le_bytes());
+    output.extend((ContentDistribution::NotChunked as u16).to_le_bytes());
+    output.extend(i32::try_from(self.body.len()).map_err(|_| invalid_data("body too large"))?.to_le_bytes());
+
+    for header in &self.headers {
+      match header {
+        Header::Custom { name, value } => {
+          output.extend((HeaderToken::Custom as u16).to_le_bytes());
+          write_counted_string(&mut output, name)?;
+          write_counted_string(&mut output, value)?;
+        },
+        Header::StatusCode(status_code) => {
+          output.extend((HeaderToken::StatusCode as u16).to_le_bytes());
+          output.push(HeaderDataFormat::UInt16 as u8);
+          output.extend(status_code.to_le_bytes());
+        },
+        Header::StatusPhrase(status_phrase) => {
+          output.extend((HeaderToken::StatusPhrase as u16).to_le_bytes());
+          output.push(HeaderDataFormat::CountedString as u8);
+          write_counted_string(&mut output, status_phrase)?;
+        },
+        Header::RequestUri(request_uri) => {
+          output.extend((HeaderToken::RequestUri as u16).to_le_bytes());
+          output.push(HeaderDataFormat::CountedString as u8);
+          write_counted_string(&mut output, request_uri)?;
+        },
+        Header::CloseConnection => {
+          output.extend((HeaderToken::CloseConnection as u16).to_le_bytes());
+          output.push(HeaderDataFormat::Void as u8);
+        },
+        Header::ContentType(content_type) => {
+          output.extend((HeaderToken::ContentType as u16).to_le_bytes());
+          output.push(HeaderDataFormat::CountedString as u8);
+          write_counted_string(&mut output, content_type)?;
+        },
+      }
+    }
+    output.extend((HeaderToken::EndHeaders as u16).to_le_bytes());
+
+    output.extend(&self.body);
+
+    writer.write_all(&output)?;
+    writer.flush()
+  }
+}
+
+fn invalid_data(message: &'static str) -> io::Error {
+  io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+  let mut bytes = [0; N];
+  reader.read_exact(&mut bytes)?;
+  Ok(bytes)
+}
+
+fn read_vec<R: Read>(reader: &mut R, len: usize) -> io::Result<Vec<u8>> {
+  let mut bytes = vec![];
+  reader.take(len as u64).read_to_end(&mut bytes)?;
+
+  if bytes.len() != len {
+    return Err(io::ErrorKind::UnexpectedEof.into())
+  }
+
+  Ok(bytes)
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> io::Result<u16> {
+  read_array(reader).map(u16::from_le_bytes)
+}
+
+fn read_length<R: Read>(reader: &mut R) -> io::Result<usize> {
+  let length = i32::from_le_bytes(read_array(reader)?);
+  usize::try_from(length).map_err(|_| invalid_data("invalid length"))
+}
+
+fn expect_data_format<R: Read>(reader: &mut R, data_format: HeaderDataFormat) -> io::Result<()> {
+  let [format] = read_array(reader)?;
+  if format == data_format as u8 { Ok(()) } else { Err(invalid_data("unexpected header data format")) }
+}
+
+/// 2.2.3.2.1 `CountedString`
+fn read_counted_string<R: Read>(reader: &mut R) -> io::Result<String> {
+  let [encoding] = read_array(reader)?;
+  let length = read_length(reader)?;
+  let bytes = read_vec(reader, length)?;
+
+  match encoding {
+    e if e == StringEncoding::Unicode as u8 => {
+      if bytes.len() % 2 != 0 {
+        return Err(invalid_data("invalid UTF-16 string"))
+      }
+
+      let units = bytes.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]]));
+      char::decode_utf16(units).collect::<Result<_, _>>().map_err(|_| invalid_data("invalid UTF-16 string"))
+    },
+    e if e == StringEncoding::Utf8 as u8 => String::from_utf8(bytes).map_err(|_| invalid_data("invalid UTF-8 string")),
+    _ => Err(invalid_data("invalid string encoding")),
+  }
+}
+
+/// 2.2.3.2.1 `CountedString`
+fn write_counted_string(output: &mut Vec<u8>, s: &str) -> io::Result<()> {
+  output.push(StringEncoding::Utf8 as u8);
+  output.extend(i32::try_from(s.len()).map_err(|_| invalid_data("string too large"))?.to_le_bytes());
+  output.extend(s.as_bytes());
+  Ok(())
+}
@@ -0,0 +1,179 @@
+use std::{
+  collections::HashMap,
+  fmt,
+  io::{self, Read, Write},
+  net::TcpListener,
+  sync::Arc,
+  thread,
+};
+
+use super::{Message, OperationType};
+use crate::{DotNetFault, MethodReturn, RemotingMessage, Value, value::full_type_name};
+
+type Handler = Box<dyn for<'a> Fn(&[Value<'a>]) -> Result<Value<'a>, DotNetFault> + Send + Sync>;
+type ErrorHandler = Box<dyn Fn(io::Error) + Send + Sync>;
+
+/// A server dispatching remote method calls received over the TCP channel to registered handlers.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::net::TcpListener;
+///
+/// use nrbf::{DotNetFault, Value, tcp::RemotingServer};
+///
+/// let mut server = RemotingServer::new();
+/// server.register("Server.Calculator, App", "Add", |args| match args {
+///   [Value::Int32(a), Value::Int32(b)] => Ok(Value::Int32(a + b)),
+///   _ => Err(DotNetFault::new("System.ArgumentException", "expected two integers")),
+/// });
+///
+/// server.serve(TcpListener::bind("127.0.0.1:8080")?)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct RemotingServer {
+  handlers: HashMap<(String, String), Handler>,
+  error_handler: Option<ErrorHandler>,
+}
+
+impl fmt::Debug for RemotingServer {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("RemotingServer").field("handlers", &self.handlers.keys().collect::<Vec<_>>()).finish()
+  }
+}
+
+impl RemotingServer {
+  /// Create a server without any registered handlers.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Register a handler for the given method of the given server type.
+  ///
+  /// The assembly part of an assembly-qualified type name is ignored when routing calls.
+  pub fn register<F>(&mut self, type_name: &str, method_name: &str, handler: F) -> &mut Self
+  where
+    F: for<'a> Fn(&[Value<'a>]) -> Result<Value<'a>, DotNetFault> + Send + Sync + 'static,
+  {
+    self.handlers.insert((full_type_name(type_name).to_owned(), method_name.to_owned()), Box::new(handler));
+    self
+  }
+
+  /// Register a handler for errors on connections accepted by [`serve`](Self::serve).
+  ///
+  /// Without a handler, errors are ignored.
+  pub fn on_error<F>(&mut self, handler: F) -> &mut Self
+  where
+    F: Fn(io::Error) + Send + Sync + 'static,
+  {
+    self.error_handler = Some(Box::new(handler));
+    self
+  }
+
+  /// Decode a serialized method call, dispatch it and return the serialized method return.
+  ///
+  /// Errors are returned to the caller as serialized exceptions.
+  pub fn dispatch(&self, request: &[u8]) -> Vec<u8> {
+    let method_call = match RemotingMessage::parse(request) {
+      Ok(RemotingMessage::MethodCall(method_call)) => method_call,
+      Ok(_) => {
+        let fault = DotNetFault::new("System.Runtime.Remoting.RemotingException", "expected a method call");
+        return fault_return(&fault, None)
+      },
+      Err(err) => {
+        let fault = DotNetFault::new("System.Runtime.Serialization.SerializationException", err.to_string());
+        return fault_return(&fault, None)
+      },
+    };
+
+    let key = (full_type_name(method_call.type_name).to_owned(), method_call.method_name.to_owned());
+    let Some(handler) = self.handlers.get(&key) else {
+      let fault = DotNetFault::new(
+        "System.Runtime.Remoting.RemotingException",
+        format!("method {} not found on type {}", method_call.method_name, method_call.type_name),
+      );
+      return fault_return(&fault, method_call.call_context)
+    };
+
+    match handler(method_call.args.as_deref().unwrap_or_default()) {
+      Ok(return_value) => {
+        let method_return = MethodReturn::new(Some(return_value)).with_call_context(method_call.call_context);
+        RemotingMessage::MethodReturn(method_return).to_bytes()
+      },
+      Err(fault) => fault_return(&fault, method_call.call_context),
+    }
+  }
+
+  /// Handle requests on a single connection until it is closed by the client.
+  ///
+  /// One-way requests are dispatched without sending a reply.
+  pub fn serve_connection<S: Read + Write>(&self, mut stream: S) -> io::Result<()> {
+    loop {
+      let Some(request) = read_message(&mut stream)? else { return Ok(()) };
+
+      let body = self.dispatch(&request.body);
+
+      match request.operation_type {
+        OperationType::Request => Message::reply(body).write_to(&mut stream)?,
+        OperationType::OneWayRequest => (),
+        OperationType::Reply => return Err(io::Error::new(io::ErrorKind::InvalidData, "unexpected reply")),
+      }
+
+      if request.close_connection() {
+        return Ok(())
+      }
+    }
+  }
+
+  /// Accept connections on the given listener, handling each of them on a separate thread.
+  ///
+  /// Returns only if accepting a connection fails. Errors on accepted connections are passed to the
+  /// handler registered with [`on_error`](Self::on_error).
+  pub fn serve(self, listener: TcpListener) -> io::Result<()> {
+    let server = Arc::new(self);
+
+    for stream in listener.incoming() {
+      let stream = stream?;
+      let server = Arc::clone(&server);
+
+      thread::spawn(move || {
+        if let Err(err) = server.serve_connection(stream)
+          && let Some(error_handler) = &server.error_handler
+        {
+          error_handler(err)
+        }
+      });
+    }
+
+    Ok(())
+  }
+}
+
+/// Read the next message, or `None` if the connection was closed before its first byte.
+///
+/// A connection closed within a message is an error.
+fn read_message<R: Read>(reader: &mut R) -> io::Result<Option<Message>> {
+  let mut first_byte = [0];
+  loop {
+    match reader.read(&mut first_byte) {
+      Ok(0) => return Ok(None),
+      Ok(_) => break,
+      Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+      Err(err) => return Err(err),
+    }
+  }
+
+  Message::read_from(&mut first_byte.as_slice().chain(reader)).map(Some).map_err(|err| {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+      io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed within a message")
+    } else {
+      err
+    }
+  })
+}
+
+fn fault_return(fault: &DotNetFault, call_context: Option<&str>) -> Vec<u8> {
+  let method_return = MethodReturn::from_exception(fault.to_exception()).with_call_context(call_context);
+  RemotingMessage::MethodReturn(method_return).to_bytes()
+}
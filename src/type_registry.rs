@@ -0,0 +1,183 @@
+use std::fmt;
+
+use crate::{
+  Value,
+  value::{ConversionError, Object, full_type_name},
+};
+
+type Converter = Box<dyn for<'i> Fn(&Object<'i>) -> Result<Value<'i>, ConversionError> + Send + Sync>;
+
+/// A .NET type name, optionally restricted to an assembly and an assembly version.
+///
+/// # Example
+///
+/// ```
+/// use nrbf::TypeName;
+///
+/// let type_name = TypeName::from("Contoso.Order, Contoso.Model, Version=1.2.0.0, Culture=neutral");
+///
+/// assert_eq!(type_name, TypeName::new("Contoso.Order").with_assembly("Contoso.Model").with_version("1.2.0.0"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TypeName {
+  /// The full name of the type, e.g. `Contoso.Order` or `Contoso.Box`1` for a generic type.
+  pub name: String,
+  /// The simple name of the assembly, e.g. `Contoso.Model`. `None` matches any assembly.
+  pub assembly: Option<String>,
+  /// The assembly version, e.g. `1.2.0.0`. `None` matches any version.
+  pub version: Option<String>,
+}
+
+impl TypeName {
+  /// Create a type name matching the type in any assembly.
+  pub fn new(name: impl Into<String>) -> Self {
+    Self { name: name.into(), assembly: None, version: None }
+  }
+
+  /// Restrict the type name to the given assembly.
+  pub fn with_assembly(mut self, assembly: impl Into<String>) -> Self {
+    self.assembly = Some(assembly.into());
+    self
+  }
+
+  /// Restrict the type name to the given assembly version.
+  pub fn with_version(mut self, version: impl Into<String>) -> Self {
+    self.version = Some(version.into());
+    self
+  }
+
+  /// Check whether an object is an instance of this type.
+  ///
+  /// Generic types match with any type arguments. Objects without a library belong to `mscorlib`.
+  pub fn matches(&self, object: &Object<'_>) -> bool {
+    let generic_definition = object.class.split_once('[').map_or(object.class, |(name, _)| name);
+    if self.name != object.class && self.name != generic_definition {
+      return false
+    }
+
+    let library = object.library.unwrap_or("mscorlib");
+
+    if let Some(assembly) = &self.assembly
+      && library.split(',').next().map(str::trim) != Some(assembly.as_str())
+    {
+      return false
+    }
+
+    if let Some(version) = &self.version
+      && assembly_version(library) != Some(version.as_str())
+    {
+      return false
+    }
+
+    true
+  }
+
+  /// The number of restrictions besides the name, used to prefer more specific converters.
+  fn specificity(&self) -> usize {
+    usize::from(self.assembly.is_some()) + usize::from(self.version.is_some())
+  }
+}
+
+/// Parse an assembly-qualified type name, e.g. `Contoso.Order, Contoso.Model, Version=1.2.0.0`.
+///
+/// The assembly and version are optional.
+impl From<&str> for TypeName {
+  fn from(type_name: &str) -> Self {
+    let name = full_type_name(type_name);
+    let assembly = type_name.trim_start()[name.len()..].trim_start().strip_prefix(',').map(str::trim);
+
+    Self {
+      name: name.to_owned(),
+      assembly: assembly.and_then(|assembly| assembly.split(',').next()).map(|name| name.trim().to_owned()),
+      version: assembly.and_then(assembly_version).map(str::to_owned),
+    }
+  }
+}
+
+impl fmt::Display for TypeName {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.name)?;
+
+    if let Some(assembly) = &self.assembly {
+      write!(f, ", {}", assembly)?;
+    }
+
+    if let Some(version) = &self.version {
+      write!(f, ", Version={}", version)?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Get the `Version` of an assembly name, e.g. `Contoso.Model, Version=1.2.0.0, Culture=neutral`.
+pub(crate) fn assembly_version(assembly: &str) -> Option<&str> {
+  assembly.split(',').find_map(|part| part.trim().strip_prefix("Version="))
+}
+
+/// A registry of converters for custom .NET classes.
+///
+/// A converter maps an object to a [`Value`], e.g. to reshape the members of a class
+/// implementing `ISerializable`. When deserializing with [`from_slice_with`](crate::from_slice_with),
+/// objects are converted before deserializing them.
+///
+/// # Example
+///
+/// ```
+/// use nrbf::{TypeRegistry, Value, value::ConversionError};
+///
+/// let mut registry = TypeRegistry::new();
+/// registry.register("Contoso.Money, Contoso.Model", |object| match object.members.get("units") {
+///   Some(Value::Int64(units)) => Ok(Value::Int64(*units)),
+///   _ => Err(ConversionError::InvalidMember("units")),
+/// });
+/// ```
+#[derive(Default)]
+pub struct TypeRegistry {
+  converters: Vec<(TypeName, Converter)>,
+}
+
+impl fmt::Debug for TypeRegistry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("TypeRegistry")
+      .field("converters", &self.converters.iter().map(|(type_name, _)| type_name).collect::<Vec<_>>())
+      .finish()
+  }
+}
+
+impl TypeRegistry {
+  /// Create a registry without any converters.
+  pub const fn new() -> Self {
+    Self { converters: Vec::new() }
+  }
+
+  /// Register a converter for the given type.
+  ///
+  /// If several converters match an object, the one restricted to an assembly or version is
+  /// preferred. Among equally specific converters, the last registered one is used.
+  pub fn register<F>(&mut self, type_name: impl Into<TypeName>, converter: F) -> &mut Self
+  where
+    F: for<'i> Fn(&Object<'i>) -> Result<Value<'i>, ConversionError> + Send + Sync + 'static,
+  {
+    self.converters.push((type_name.into(), Box::new(converter)));
+    self
+  }
+
+  /// Check whether the registry has no converters.
+  pub fn is_empty(&self) -> bool {
+    self.converters.is_empty()
+  }
+
+  /// Convert an object with the matching converter.
+  ///
+  /// Returns `None` if no converter matches the object.
+  pub fn convert<'i>(&self, object: &Object<'i>) -> Option<Result<Value<'i>, ConversionError>> {
+    let (_, converter) = self
+      .converters
+      .iter()
+      .filter(|(type_name, _)| type_name.matches(object))
+      .max_by_key(|(type_name, _)| type_name.specificity())?;
+
+    Some(converter(object))
+  }
+}
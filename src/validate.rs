@@ -0,0 +1,547 @@
+//! Validation of payloads against an expected schema.
+//!
+//! A [`Schema`] declares which classes may appear in a payload, which members they have and of
+//! which types, and how long arrays may be. It can be built in Rust or, with the `serde` feature,
+//! deserialized from any self-describing format such as JSON:
+//!
+//! ```json
+//! {
+//!   "classes": {
+//!     "Contoso.Order": {
+//!       "assembly": "Contoso.Model",
+//!       "members": {
+//!         "id": { "type": "Int32" },
+//!         "lines": { "type": { "Array": "Double" }, "max_len": 100 },
+//!         "note": { "type": "String", "optional": true, "nullable": true }
+//!       }
+//!     }
+//!   },
+//!   "max_array_len": 10000
+//! }
+//! ```
+
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+use crate::{
+  RemotingMessage, Value,
+  value::{Object, type_name},
+};
+
+static NULL: Value<'static> = Value::Null;
+
+/// Validate a message against a schema, returning all violations.
+///
+/// Paths start with `$` for a value, or with `$.args`, `$.return` and `$.exception` for the
+/// arguments, return value and exception of method calls and returns.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use nrbf::{
+///   RemotingMessage, Value,
+///   validate::{ClassSchema, MemberSchema, Schema, ValueType},
+///   value::Object,
+/// };
+///
+/// let schema = Schema::new().with_class(
+///   "Contoso.Order",
+///   ClassSchema::new()
+///     .with_member("id", MemberSchema::new(ValueType::Int32))
+///     .with_member("lines", MemberSchema::new(ValueType::Array(Box::new(ValueType::Double))).with_max_len(2)),
+/// );
+///
+/// let order = Object {
+///   class: "Contoso.Order",
+///   library: Some("Contoso.Model"),
+///   members: HashMap::from_iter([("lines", Value::Array(vec![Value::Double(1.0); 3]))]),
+/// };
+/// let message = RemotingMessage::Value(Value::Object(order));
+///
+/// let violations = nrbf::validate(&message, &schema);
+/// assert_eq!(
+///   violations.iter().map(ToString::to_string).collect::<Vec<_>>(),
+///   vec!["$: missing member id", "$.lines: array length 3 exceeds maximum of 2"],
+/// );
+/// ```
+pub fn validate(message: &RemotingMessage<'_>, schema: &Schema) -> Vec<Violation> {
+  let mut validator = Validator { schema, violations: Vec::new() };
+
+  match message {
+    RemotingMessage::Value(value) => validator.validate_value(value, &mut String::from("$")),
+    RemotingMessage::MethodCall(method_call) => {
+      validator.validate_args(method_call.args.as_deref().unwrap_or_default());
+    },
+    RemotingMessage::MethodReturn(method_return) => {
+      if let Some(return_value) = &method_return.return_value {
+        validator.validate_value(return_value, &mut String::from("$.return"));
+      }
+      validator.validate_args(method_return.args.as_deref().unwrap_or_default());
+      if let Some(exception) = &method_return.exception {
+        validator.validate_value(exception, &mut String::from("$.exception"));
+      }
+    },
+  }
+
+  validator.violations
+}
+
+/// The expected schema of a payload.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(default, deny_unknown_fields))]
+pub struct Schema {
+  /// The classes which may appear, by their full name.
+  ///
+  /// Generic classes match by their full name including type arguments or by their generic type
+  /// definition, e.g. ``System.Collections.Generic.List`1``.
+  pub classes: BTreeMap<String, ClassSchema>,
+  /// Whether classes which are not listed may appear.
+  pub allow_unknown_classes: bool,
+  /// The maximum length of all arrays.
+  pub max_array_len: Option<usize>,
+}
+
+impl Schema {
+  /// Create a schema which does not allow any classes.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Allow a class.
+  pub fn with_class(mut self, class: impl Into<String>, class_schema: ClassSchema) -> Self {
+    self.classes.insert(class.into(), class_schema);
+    self
+  }
+
+  /// Set whether classes which are not listed may appear.
+  pub fn with_unknown_classes(mut self, allow: bool) -> Self {
+    self.allow_unknown_classes = allow;
+    self
+  }
+
+  /// Set the maximum length of all arrays.
+  pub fn with_max_array_len(mut self, max_len: usize) -> Self {
+    self.max_array_len = Some(max_len);
+    self
+  }
+
+  fn class(&self, class: &str) -> Option<&ClassSchema> {
+    self.classes.get(class).or_else(|| self.classes.get(generic_definition(class)))
+  }
+}
+
+/// The expected members of a class.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(default, deny_unknown_fields))]
+pub struct ClassSchema {
+  /// The simple name of the assembly, any assembly is accepted if missing. Objects without a library
+  /// belong to `mscorlib`.
+  pub assembly: Option<String>,
+  /// The members by their name.
+  pub members: BTreeMap<String, MemberSchema>,
+  /// Whether members which are not listed may appear.
+  pub allow_unknown_members: bool,
+}
+
+impl ClassSchema {
+  /// Create a class schema without any members.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Set the simple name of the assembly.
+  pub fn with_assembly(mut self, assembly: impl Into<String>) -> Self {
+    self.assembly = Some(assembly.into());
+    self
+  }
+
+  /// Add a member.
+  pub fn with_member(mut self, name: impl Into<String>, member: MemberSchema) -> Self {
+    self.members.insert(name.into(), member);
+    self
+  }
+
+  /// Set whether members which are not listed may appear.
+  pub fn with_unknown_members(mut self, allow: bool) -> Self {
+    self.allow_unknown_members = allow;
+    self
+  }
+}
+
+/// The expected type of a member.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(deny_unknown_fields))]
+pub struct MemberSchema {
+  /// The type of the member.
+  #[cfg_attr(feature = "serde", serde(rename = "type"))]
+  pub value_type: ValueType,
+  /// Whether the member may be missing.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub optional: bool,
+  /// Whether the member may be null.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub nullable: bool,
+  /// The minimum length of an array.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub min_len: Option<usize>,
+  /// The maximum length of an array.
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub max_len: Option<usize>,
+}
+
+impl MemberSchema {
+  /// Create a required, non-nullable member of the given type.
+  pub fn new(value_type: ValueType) -> Self {
+    Self { value_type, optional: false, nullable: false, min_len: None, max_len: None }
+  }
+
+  /// Allow the member to be missing.
+  pub fn optional(mut self) -> Self {
+    self.optional = true;
+    self
+  }
+
+  /// Allow the member to be null.
+  pub fn nullable(mut self) -> Self {
+    self.nullable = true;
+    self
+  }
+
+  /// Set the minimum length of an array.
+  pub fn with_min_len(mut self, min_len: usize) -> Self {
+    self.min_len = Some(min_len);
+    self
+  }
+
+  /// Set the maximum length of an array.
+  pub fn with_max_len(mut self, max_len: usize) -> Self {
+    self.max_len = Some(max_len);
+    self
+  }
+}
+
+/// The expected type of a value.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
+pub enum ValueType {
+  /// Any value, including null.
+  Any,
+  /// A `Boolean`.
+  Boolean,
+  /// A `Byte`.
+  Byte,
+  /// A `Char`.
+  Char,
+  /// A `Decimal`.
+  Decimal,
+  /// A `Double`.
+  Double,
+  /// An `Int16`.
+  Int16,
+  /// An `Int32`.
+  Int32,
+  /// An `Int64`.
+  Int64,
+  /// An `SByte`.
+  SByte,
+  /// A `Single`.
+  Single,
+  /// A `TimeSpan`.
+  TimeSpan,
+  /// A `DateTime`.
+  DateTime,
+  /// A `UInt16`.
+  UInt16,
+  /// A `UInt32`.
+  UInt32,
+  /// A `UInt64`.
+  UInt64,
+  /// A `String`.
+  String,
+  /// An object of any class.
+  Object,
+  /// An object of the given class, by its full name or its generic type definition like
+  /// [`Schema::classes`].
+  Class(String),
+  /// An array with elements of the given type. Elements may be null.
+  Array(Box<ValueType>),
+}
+
+impl ValueType {
+  fn matches(&self, value: &Value<'_>) -> bool {
+    // Boxed primitives and enums match their underlying type.
+    let value = match self {
+      Self::Any | Self::Object | Self::Class(_) => value,
+      _ => value.unboxed(),
+    };
+
+    match (self, value) {
+      (Self::Any, _) => true,
+      (Self::Boolean, Value::Boolean(_))
+      | (Self::Byte, Value::Byte(_))
+      | (Self::Char, Value::Char(_))
+      | (Self::Decimal, Value::Decimal(_))
+      | (Self::Double, Value::Double(_))
+      | (Self::Int16, Value::Int16(_))
+      | (Self::Int32, Value::Int32(_))
+      | (Self::Int64, Value::Int64(_))
+      | (Self::SByte, Value::SByte(_))
+      | (Self::Single, Value::Single(_))
+      | (Self::TimeSpan, Value::TimeSpan(_))
+      | (Self::DateTime, Value::DateTime(_))
+      | (Self::UInt16, Value::UInt16(_))
+      | (Self::UInt32, Value::UInt32(_))
+      | (Self::UInt64, Value::UInt64(_))
+      | (Self::String, Value::String(_))
+      | (Self::Object, Value::Object(_)) => true,
+      (Self::Class(class), Value::Object(object)) => object.class == class || generic_definition(object.class) == class,
+      (Self::Array(element_type), Value::Array(elements)) => {
+        elements.iter().all(|element| matches!(element, Value::Null) || element_type.matches(element))
+      },
+      (Self::Array(element_type), Value::Bytes(_)) => matches!(**element_type, Self::Any | Self::Byte),
+      _ => false,
+    }
+  }
+}
+
+impl fmt::Display for ValueType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Class(class) => f.write_str(class),
+      Self::Array(element_type) => write!(f, "{}[]", element_type),
+      value_type => write!(f, "{:?}", value_type),
+    }
+  }
+}
+
+/// A violation of a [`Schema`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+  /// The path of the offending value, e.g. `$.orders[2].customer`.
+  pub path: String,
+  /// The kind of violation.
+  pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}: {}", self.path, self.kind)
+  }
+}
+
+/// The kind of a [`Violation`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+  /// An object of a class which is not allowed.
+  UnexpectedClass(String),
+  /// An object of an allowed class from another assembly.
+  UnexpectedAssembly {
+    /// The expected simple name of the assembly.
+    expected: String,
+    /// The library of the object.
+    found: String,
+  },
+  /// A required member is missing.
+  MissingMember(String),
+  /// A member which is not allowed.
+  UnexpectedMember(String),
+  /// A value of an unexpected type.
+  UnexpectedType {
+    /// The expected type.
+    expected: ValueType,
+    /// The name of the type or class found.
+    found: String,
+  },
+  /// A null value of a non-nullable member.
+  UnexpectedNull,
+  /// An array which is shorter than allowed.
+  ArrayTooShort {
+    /// The minimum length.
+    min_len: usize,
+    /// The actual length.
+    len: usize,
+  },
+  /// An array which is longer than allowed.
+  ArrayTooLong {
+    /// The maximum length.
+    max_len: usize,
+    /// The actual length.
+    len: usize,
+  },
+}
+
+impl fmt::Display for ViolationKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnexpectedClass(class) => write!(f, "unexpected class {}", class),
+      Self::UnexpectedAssembly { expected, found } => write!(f, "expected assembly {}, found {}", expected, found),
+      Self::MissingMember(name) => write!(f, "missing member {}", name),
+      Self::UnexpectedMember(name) => write!(f, "unexpected member {}", name),
+      Self::UnexpectedType { expected, found } => write!(f, "expected {}, found {}", expected, found),
+      Self::UnexpectedNull => write!(f, "unexpected null"),
+      Self::ArrayTooShort { min_len, len } => write!(f, "array length {} is below minimum of {}", len, min_len),
+      Self::ArrayTooLong { max_len, len } => write!(f, "array length {} exceeds maximum of {}", len, max_len),
+    }
+  }
+}
+
+struct Validator<'s> {
+  schema: &'s Schema,
+  violations: Vec<Violation>,
+}
+
+impl<'s> Validator<'s> {
+  fn violation(&mut self, path: &str, kind: ViolationKind) {
+    self.violations.push(Violation { path: path.to_owned(), kind });
+  }
+
+  fn validate_args(&mut self, args: &[Value<'_>]) {
+    for (i, arg) in args.iter().enumerate() {
+      self.validate_value(arg, &mut format!("$.args[{}]", i));
+    }
+  }
+
+  fn validate_len(&mut self, path: &str, len: usize, min_len: Option<usize>, max_len: Option<usize>) {
+    if let Some(min_len) = min_len
+      && len < min_len
+    {
+      self.violation(path, ViolationKind::ArrayTooShort { min_len, len });
+    }
+
+    if let Some(max_len) = max_len
+      && len > max_len
+    {
+      self.violation(path, ViolationKind::ArrayTooLong { max_len, len });
+    }
+  }
+
+  fn validate_value(&mut self, value: &Value<'_>, path: &mut String) {
+    match value {
+      // Boxed primitives, enums and `Nullable<T>` are validated as their value.
+      Value::Object(object) => match nullable_value(object) {
+        Some(value) => self.validate_value(value, path),
+        // Enums are still checked against the schema before their value is validated.
+        None if object.members.contains_key("value__") && !std::ptr::eq(value.unboxed(), value) => {
+          self.validate_class(object, path);
+          self.validate_value(value.unboxed(), path);
+        },
+        None if !std::ptr::eq(value.unboxed(), value) => self.validate_value(value.unboxed(), path),
+        None => self.validate_object(object, path),
+      },
+      Value::Array(elements) => {
+        self.validate_len(path, elements.len(), None, self.schema.max_array_len);
+
+        for (i, element) in elements.iter().enumerate() {
+          let len = path.len();
+          path.push_str(&format!("[{}]", i));
+          self.validate_value(element, path);
+          path.truncate(len);
+        }
+      },
+      Value::Bytes(bytes) => self.validate_len(path, bytes.len(), None, self.schema.max_array_len),
+      _ => (),
+    }
+  }
+
+  fn validate_object(&mut self, object: &Object<'_>, path: &mut String) {
+    let mut names = object.members.keys().copied().collect::<Vec<_>>();
+    names.sort_unstable();
+
+    if let Some(class_schema) = self.validate_class(object, path) {
+      for (name, member_schema) in &class_schema.members {
+        match object.members.get(name.as_str()) {
+          Some(value) => {
+            let len = path.len();
+            path.push('.');
+            path.push_str(name);
+            self.validate_member(value, member_schema, path);
+            path.truncate(len);
+          },
+          None if member_schema.optional => (),
+          None => self.violation(path, ViolationKind::MissingMember(name.clone())),
+        }
+      }
+
+      if !class_schema.allow_unknown_members {
+        for name in names.iter().filter(|name| !class_schema.members.contains_key(**name)) {
+          self.violation(path, ViolationKind::UnexpectedMember((*name).to_owned()));
+        }
+      }
+    }
+
+    for name in names {
+      let len = path.len();
+      path.push('.');
+      path.push_str(name);
+      self.validate_value(&object.members[name], path);
+      path.truncate(len);
+    }
+  }
+
+  /// Check that the class of an object is allowed and belongs to the expected assembly.
+  fn validate_class(&mut self, object: &Object<'_>, path: &str) -> Option<&'s ClassSchema> {
+    let schema = self.schema;
+    let Some(class_schema) = schema.class(object.class) else {
+      if !schema.allow_unknown_classes {
+        self.violation(path, ViolationKind::UnexpectedClass(object.class.to_owned()));
+      }
+
+      return None
+    };
+
+    let library = object.library.unwrap_or("mscorlib");
+    if let Some(assembly) = &class_schema.assembly
+      && library.split(',').next().map(str::trim) != Some(assembly.as_str())
+    {
+      self.violation(path, ViolationKind::UnexpectedAssembly { expected: assembly.clone(), found: library.to_owned() });
+    }
+
+    Some(class_schema)
+  }
+
+  fn validate_member(&mut self, value: &Value<'_>, member_schema: &MemberSchema, path: &str) {
+    // A `Nullable<T>` is validated as its value unless an object is expected.
+    let value = match (&member_schema.value_type, value) {
+      (ValueType::Object | ValueType::Class(_), value) => value,
+      (_, Value::Object(object)) => nullable_value(object).unwrap_or(value),
+      (_, value) => value,
+    };
+
+    match value {
+      Value::Null if member_schema.nullable || member_schema.value_type == ValueType::Any => (),
+      Value::Null => self.violation(path, ViolationKind::UnexpectedNull),
+      value if !member_schema.value_type.matches(value) => {
+        let found = match value {
+          Value::Object(object) => object.class.to_owned(),
+          value => type_name(value).to_owned(),
+        };
+
+        self.violation(path, ViolationKind::UnexpectedType { expected: member_schema.value_type.clone(), found });
+      },
+      Value::Array(elements) => self.validate_len(path, elements.len(), member_schema.min_len, member_schema.max_len),
+      Value::Bytes(bytes) => self.validate_len(path, bytes.len(), member_schema.min_len, member_schema.max_len),
+      _ => (),
+    }
+  }
+}
+
+/// Get the class name without generic arguments, e.g. ``System.Collections.Generic.List`1``.
+fn generic_definition(class: &str) -> &str {
+  class.split_once('[').map_or(class, |(name, _)| name)
+}
+
+/// Get the value of a `Nullable<T>`, or [`Value::Null`] if it has no value.
+fn nullable_value<'v, 'i>(object: &'v Object<'i>) -> Option<&'v Value<'i>> {
+  if object.library.is_some() || generic_definition(object.class) != "System.Nullable`1" {
+    return None
+  }
+
+  match object.members.get("hasValue") {
+    Some(Value::Boolean(true)) => object.members.get("value"),
+    _ => Some(&NULL),
+  }
+}
@@ -0,0 +1,188 @@
+use std::{ops::Index, sync::LazyLock};
+
+use super::{Object, Value};
+
+/// The value returned when indexing a missing member or element.
+static NULL: Value<'static> = Value::Null;
+
+/// The values returned when indexing a byte array, one for each byte.
+static BYTES: LazyLock<[Value<'static>; 256]> = LazyLock::new(|| std::array::from_fn(|byte| Value::Byte(byte as u8)));
+
+impl<'i> Value<'i> {
+  /// Get the value of a boxed primitive or enum, i.e. the `m_value` member of a system class like
+  /// `System.Int32` or the `value__` member of an enum, or the value itself otherwise.
+  pub fn unboxed(&self) -> &Self {
+    match self {
+      Self::Object(Object { class, library, members }) if members.len() == 1 => {
+        let member = match library {
+          None if class.starts_with("System.") => members.get("m_value"),
+          _ => members.get("value__"),
+        };
+
+        member.unwrap_or(self)
+      },
+      value => value,
+    }
+  }
+
+  /// Check whether the value is null.
+  pub fn is_null(&self) -> bool {
+    matches!(self, Self::Null)
+  }
+
+  /// Get a boolean value.
+  pub fn as_bool(&self) -> Option<bool> {
+    match self.unboxed() {
+      Self::Boolean(v) => Some(*v),
+      _ => None,
+    }
+  }
+
+  /// Get a string value.
+  pub fn as_str(&self) -> Option<&'i str> {
+    match self.unboxed() {
+      Self::String(v) => Some(v),
+      _ => None,
+    }
+  }
+
+  /// Get a signed integer value which fits into an `i32`, i.e. an `SByte`, `Byte`, `Int16`,
+  /// `UInt16` or `Int32`.
+  pub fn as_i32(&self) -> Option<i32> {
+    match self.unboxed() {
+      Self::SByte(v) => Some((*v).into()),
+      Self::Byte(v) => Some((*v).into()),
+      Self::Int16(v) => Some((*v).into()),
+      Self::UInt16(v) => Some((*v).into()),
+      Self::Int32(v) => Some(*v),
+      _ => None,
+    }
+  }
+
+  /// Get a signed integer value which fits into an `i64`, i.e. any integer but a `UInt64`.
+  pub fn as_i64(&self) -> Option<i64> {
+    match self.unboxed() {
+      Self::UInt32(v) => Some((*v).into()),
+      Self::Int64(v) => Some(*v),
+      value => value.as_i32().map(i64::from),
+    }
+  }
+
+  /// Get an unsigned integer value, i.e. a `Byte`, `UInt16`, `UInt32` or `UInt64`.
+  pub fn as_u64(&self) -> Option<u64> {
+    match self.unboxed() {
+      Self::Byte(v) => Some((*v).into()),
+      Self::UInt16(v) => Some((*v).into()),
+      Self::UInt32(v) => Some((*v).into()),
+      Self::UInt64(v) => Some(*v),
+      _ => None,
+    }
+  }
+
+  /// Get a floating point value, i.e. a `Single` or `Double`.
+  pub fn as_f64(&self) -> Option<f64> {
+    match self.unboxed() {
+      Self::Single(v) => Some((*v).into()),
+      Self::Double(v) => Some(*v),
+      _ => None,
+    }
+  }
+
+  /// Get an object.
+  pub fn as_object(&self) -> Option<&Object<'i>> {
+    match self {
+      Self::Object(object) => Some(object),
+      _ => None,
+    }
+  }
+
+  /// Get the elements of an array, or of a `List<T>` or an `ArrayList` object.
+  ///
+  /// Returns `None` for a byte array or a `List<byte>`, which are not stored as values, use
+  /// [`as_bytes`](Self::as_bytes) instead.
+  pub fn as_array(&self) -> Option<&[Self]> {
+    match self {
+      Self::Array(elements) => Some(elements),
+      Self::Object(object) => object.list_items(),
+      _ => None,
+    }
+  }
+
+  /// Get a byte array, or the bytes of a `List<byte>` object.
+  pub fn as_bytes(&self) -> Option<&'i [u8]> {
+    match self {
+      Self::Bytes(bytes) => Some(bytes),
+      Self::Object(object) => object.list_bytes(),
+      _ => None,
+    }
+  }
+
+  /// Get the elements of an array, a `List<T>` or an `ArrayList`, including the bytes of a byte
+  /// array or a `List<byte>` as [`Value::Byte`] values.
+  pub(crate) fn elements(&self) -> Option<Vec<&Self>> {
+    match self.as_bytes() {
+      Some(bytes) => Some(bytes.iter().map(|byte| &BYTES[usize::from(*byte)]).collect()),
+      None => self.as_array().map(|elements| elements.iter().collect()),
+    }
+  }
+}
+
+/// Get a member of an object, or [`Value::Null`] if the value is not an object or the member is
+/// missing.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use nrbf::{Value, value::Object};
+///
+/// let int32 = Value::Object(Object {
+///   class: "System.Int32",
+///   library: None,
+///   members: HashMap::from_iter([("m_value", Value::Int32(42))]),
+/// });
+/// let order = Value::Object(Object {
+///   class: "Contoso.Order",
+///   library: Some("Contoso"),
+///   members: HashMap::from_iter([("Id", int32), ("Lines", Value::Array(vec![Value::Int16(3)]))]),
+/// });
+///
+/// assert_eq!(order["Id"].as_i64(), Some(42));
+/// assert_eq!(order["Lines"][0].as_i64(), Some(3));
+/// assert!(order["Missing"]["Name"][1].is_null());
+/// ```
+impl<'i> Index<&str> for Value<'i> {
+  type Output = Value<'i>;
+
+  fn index(&self, name: &str) -> &Self::Output {
+    match self {
+      Self::Object(object) => &object[name],
+      _ => &NULL,
+    }
+  }
+}
+
+/// Get an element of an array, a `List<T>` or an `ArrayList`, or [`Value::Null`] if the value is not
+/// an array or the index is out of bounds. Elements of a byte array or a `List<byte>` are returned
+/// as [`Value::Byte`].
+impl<'i> Index<usize> for Value<'i> {
+  type Output = Value<'i>;
+
+  fn index(&self, index: usize) -> &Self::Output {
+    if let Some(bytes) = self.as_bytes() {
+      return bytes.get(index).map_or(&NULL, |byte| &BYTES[usize::from(*byte)])
+    }
+
+    self.as_array().and_then(|elements| elements.get(index)).unwrap_or(&NULL)
+  }
+}
+
+/// Get a member, or [`Value::Null`] if the member is missing.
+impl<'i> Index<&str> for Object<'i> {
+  type Output = Value<'i>;
+
+  fn index(&self, name: &str) -> &Self::Output {
+    self.members.get(name).unwrap_or(&NULL)
+  }
+}
@@ -0,0 +1,154 @@
+use std::{error, fmt};
+
+use super::{Object, Value};
+
+/// Error while converting an [`Object`] into a typed view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+  /// The object is not an instance of the expected class.
+  UnexpectedClass {
+    /// The expected class name.
+    expected: &'static str,
+    /// The actual class name.
+    found: String,
+  },
+  /// A required member is missing.
+  MissingMember(&'static str),
+  /// A member has an unexpected type or value.
+  InvalidMember(&'static str),
+  /// Any other error, e.g. while deserializing an object with [`from_value_with`](crate::from_value_with).
+  Custom(String),
+}
+
+impl fmt::Display for ConversionError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnexpectedClass { expected, found } => write!(f, "expected class {}, found {}", expected, found),
+      Self::MissingMember(name) => write!(f, "missing member {}", name),
+      Self::InvalidMember(name) => write!(f, "invalid member {}", name),
+      Self::Custom(message) => f.write_str(message),
+    }
+  }
+}
+
+impl error::Error for ConversionError {}
+
+#[cfg(feature = "serde")]
+impl From<serde::de::value::Error> for ConversionError {
+  fn from(err: serde::de::value::Error) -> Self {
+    Self::Custom(err.to_string())
+  }
+}
+
+impl<'i> Object<'i> {
+  pub(crate) fn expect_class(&self, expected: &'static str) -> Result<(), ConversionError> {
+    if self.class == expected {
+      Ok(())
+    } else {
+      Err(ConversionError::UnexpectedClass { expected, found: self.class.to_owned() })
+    }
+  }
+
+  pub(crate) fn member(&self, name: &'static str) -> Result<&Value<'i>, ConversionError> {
+    self.members.get(name).ok_or(ConversionError::MissingMember(name))
+  }
+
+  /// Get a member which may be missing or null.
+  pub(crate) fn optional_member(&self, name: &'static str) -> Option<&Value<'i>> {
+    self.members.get(name).filter(|value| **value != Value::Null)
+  }
+
+  pub(crate) fn str_member(&self, name: &'static str) -> Result<&'i str, ConversionError> {
+    match self.member(name)? {
+      Value::String(s) => Ok(s),
+      _ => Err(ConversionError::InvalidMember(name)),
+    }
+  }
+
+  pub(crate) fn int32_member(&self, name: &'static str) -> Result<i32, ConversionError> {
+    match self.member(name)? {
+      Value::Int32(v) => Ok(*v),
+      _ => Err(ConversionError::InvalidMember(name)),
+    }
+  }
+
+  /// Get the underlying value of an enum member, which is either boxed or a plain `Int32`.
+  pub(crate) fn enum_member(&self, name: &'static str) -> Result<i32, ConversionError> {
+    match self.member(name)? {
+      Value::Int32(v) => Ok(*v),
+      Value::Object(object) => match object.members.get("value__") {
+        Some(Value::Int32(v)) => Ok(*v),
+        _ => Err(ConversionError::InvalidMember(name)),
+      },
+      _ => Err(ConversionError::InvalidMember(name)),
+    }
+  }
+
+  pub(crate) fn optional_str_member(&self, name: &'static str) -> Result<Option<&'i str>, ConversionError> {
+    match self.optional_member(name) {
+      Some(Value::String(s)) => Ok(Some(s)),
+      Some(_) => Err(ConversionError::InvalidMember(name)),
+      None => Ok(None),
+    }
+  }
+
+  pub(crate) fn optional_array_member(&self, name: &'static str) -> Result<&[Value<'i>], ConversionError> {
+    match self.optional_member(name) {
+      Some(Value::Array(values)) => Ok(values),
+      Some(_) => Err(ConversionError::InvalidMember(name)),
+      None => Ok(&[]),
+    }
+  }
+
+  /// Get an array of strings, skipping null elements.
+  pub(crate) fn optional_str_array_member(&self, name: &'static str) -> Result<Vec<&'i str>, ConversionError> {
+    self
+      .optional_array_member(name)?
+      .iter()
+      .filter_map(|value| match value {
+        Value::String(s) => Some(Ok(*s)),
+        Value::Null => None,
+        _ => Some(Err(ConversionError::InvalidMember(name))),
+      })
+      .collect()
+  }
+
+  pub(crate) fn optional_object_member(&self, name: &'static str) -> Result<Option<&Object<'i>>, ConversionError> {
+    match self.optional_member(name) {
+      Some(Value::Object(object)) => Ok(Some(object)),
+      Some(_) => Err(ConversionError::InvalidMember(name)),
+      None => Ok(None),
+    }
+  }
+
+  /// Get the elements of a `List<T>` or an `ArrayList`, i.e. the first `_size` items of `_items`.
+  pub(crate) fn list_items(&self) -> Option<&[Value<'i>]> {
+    match self.list_members()? {
+      (Value::Array(items), size) => items.get(..size),
+      _ => None,
+    }
+  }
+
+  /// Get the bytes of a `List<byte>`, i.e. the first `_size` bytes of `_items`.
+  pub(crate) fn list_bytes(&self) -> Option<&'i [u8]> {
+    match self.list_members()? {
+      (Value::Bytes(bytes), size) => bytes.get(..size),
+      _ => None,
+    }
+  }
+
+  /// Get the `_items` and `_size` members of a `List<T>` or an `ArrayList`.
+  fn list_members(&self) -> Option<(&Value<'i>, usize)> {
+    let class_name = self.class.split_once('`').map_or(self.class, |(name, _)| name);
+    if self.library.is_some()
+      || !matches!(class_name, "System.Collections.Generic.List" | "System.Collections.ArrayList")
+    {
+      return None
+    }
+
+    match (self.members.get("_items"), self.members.get("_size")) {
+      (Some(items), Some(Value::Int32(size))) => Some((items, usize::try_from(*size).ok()?)),
+      _ => None,
+    }
+  }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+use super::{ConversionError, Object, Value};
+
+/// A culture, i.e. a serialized `System.Globalization.CultureInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CultureInfo<'i> {
+  /// The culture name, e.g. `en-US`, or an empty string for the invariant culture.
+  pub name: &'i str,
+  /// The culture identifier, if present.
+  pub lcid: Option<i32>,
+  /// Whether user-selected culture settings are used.
+  pub use_user_override: bool,
+}
+
+impl fmt::Display for CultureInfo<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.name)
+  }
+}
+
+impl<'i> TryFrom<&Object<'i>> for CultureInfo<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Globalization.CultureInfo")?;
+
+    let lcid = match object.optional_member("cultureID") {
+      Some(Value::Int32(lcid)) => Some(*lcid),
+      Some(_) => return Err(ConversionError::InvalidMember("cultureID")),
+      None => None,
+    };
+
+    let use_user_override = match object.optional_member("m_useUserOverride") {
+      Some(Value::Boolean(v)) => *v,
+      Some(_) => return Err(ConversionError::InvalidMember("m_useUserOverride")),
+      None => false,
+    };
+
+    Ok(Self { name: object.str_member("m_name")?, lcid, use_user_override })
+  }
+}
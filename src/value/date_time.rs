@@ -1,6 +1,12 @@
+use std::{
+  error, fmt,
+  time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 use crate::data_type;
 
 /// Time-zone information for [`DateTime`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DateTimeKind {
   /// The time specified is in the Coordinated Universal Time (UTC) time zone.
   Utc,
@@ -9,6 +15,11 @@ pub enum DateTimeKind {
 }
 
 /// An date-time value.
+///
+/// With `serde`, it deserializes as its ticks without its kind. When a string is requested, e.g. into
+/// a `String`, `chrono::DateTime<Utc>`, `chrono::NaiveDateTime` or with `time`'s `rfc3339` format,
+/// it deserializes as its round-trip string (e.g. `2009-06-15T13:45:30.0000000Z`). It also
+/// deserializes into a `std::time::SystemTime`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DateTime(pub(crate) data_type::DateTime);
 
@@ -17,15 +28,157 @@ impl DateTime {
   /// The value can represent time instants in a granularity of 100 nanoseconds
   /// until 23:59:59.9999999, December 31, 9999.
   pub fn ticks(&self) -> i64 {
-    i64::from(self.0) >> 2
+    i64::from(self.0) & TICKS_MASK
   }
 
   /// Provides the time-zone information.
   pub fn kind(&self) -> Option<DateTimeKind> {
-    match i64::from(self.0) & 0b11 {
+    match (i64::from(self.0) as u64) >> KIND_SHIFT {
       1 => Some(DateTimeKind::Utc),
-      2 => Some(DateTimeKind::Local),
+      // `3` is a local time in the ambiguous hour at the end of daylight saving time.
+      2 | 3 => Some(DateTimeKind::Local),
       _ => None,
     }
   }
 }
+
+/// Formats the value like .NET's round-trip (`"o"`) format specifier, e.g. `2009-06-15T13:45:30.0000000Z`.
+///
+/// Only UTC values have a time-zone suffix, since the offset of local values is not serialized.
+impl fmt::Display for DateTime {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_round_trip(f, self.ticks())?;
+
+    match self.kind() {
+      Some(DateTimeKind::Utc) => f.write_str("Z"),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// Error while converting a [`DateTime`] or [`TimeSpan`](super::TimeSpan) into another time type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeConversionError {
+  /// The value is out of the range of the target type.
+  OutOfRange,
+  /// The value is a local time of the serializing machine, whose offset from UTC is unknown.
+  LocalTime,
+}
+
+impl fmt::Display for TimeConversionError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::OutOfRange => write!(f, "value out of range"),
+      Self::LocalTime => write!(f, "local time with unknown offset"),
+    }
+  }
+}
+
+impl error::Error for TimeConversionError {}
+
+impl DateTime {
+  /// Ticks since the Unix epoch, treating unspecified times as UTC.
+  fn unix_ticks(&self) -> Result<i64, TimeConversionError> {
+    match self.kind() {
+      Some(DateTimeKind::Local) => Err(TimeConversionError::LocalTime),
+      _ => Ok(self.ticks() - DAYS_TO_UNIX_EPOCH * TICKS_PER_DAY),
+    }
+  }
+}
+
+/// Converts UTC or unspecified times, treating the latter as UTC.
+impl TryFrom<DateTime> for SystemTime {
+  type Error = TimeConversionError;
+
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    let ticks = date_time.unix_ticks()?;
+    let duration = Duration::from_nanos(ticks.unsigned_abs() * 100);
+
+    if ticks < 0 { UNIX_EPOCH.checked_sub(duration) } else { UNIX_EPOCH.checked_add(duration) }
+      .ok_or(TimeConversionError::OutOfRange)
+  }
+}
+
+/// Converts the date and time regardless of its kind.
+#[cfg(feature = "chrono")]
+impl From<DateTime> for chrono::NaiveDateTime {
+  fn from(date_time: DateTime) -> Self {
+    let ticks = date_time.ticks() - DAYS_TO_UNIX_EPOCH * TICKS_PER_DAY;
+    let (seconds, fraction) = (ticks.div_euclid(TICKS_PER_SECOND), ticks.rem_euclid(TICKS_PER_SECOND));
+
+    // 62-bit ticks are well within the range of `chrono`.
+    chrono::DateTime::from_timestamp(seconds, fraction as u32 * 100).expect("date-time out of range").naive_utc()
+  }
+}
+
+/// Converts UTC or unspecified times, treating the latter as UTC.
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTime> for chrono::DateTime<chrono::Utc> {
+  type Error = TimeConversionError;
+
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    date_time.unix_ticks()?;
+    Ok(chrono::NaiveDateTime::from(date_time).and_utc())
+  }
+}
+
+/// Converts the date and time regardless of its kind.
+#[cfg(feature = "time")]
+impl TryFrom<DateTime> for time::PrimitiveDateTime {
+  type Error = TimeConversionError;
+
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    let ticks = date_time.ticks() - DAYS_TO_UNIX_EPOCH * TICKS_PER_DAY;
+    let utc = time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(ticks) * 100)
+      .map_err(|_| TimeConversionError::OutOfRange)?;
+
+    Ok(Self::new(utc.date(), utc.time()))
+  }
+}
+
+/// Converts UTC or unspecified times, treating the latter as UTC.
+#[cfg(feature = "time")]
+impl TryFrom<DateTime> for time::OffsetDateTime {
+  type Error = TimeConversionError;
+
+  fn try_from(date_time: DateTime) -> Result<Self, Self::Error> {
+    Self::from_unix_timestamp_nanos(i128::from(date_time.unix_ticks()?) * 100)
+      .map_err(|_| TimeConversionError::OutOfRange)
+  }
+}
+
+/// The ticks occupy the lower 62 bits, the kind the upper 2 bits.
+const TICKS_MASK: i64 = 0x3FFF_FFFF_FFFF_FFFF;
+const KIND_SHIFT: u32 = 62;
+
+pub(crate) const TICKS_PER_SECOND: i64 = 10_000_000;
+pub(crate) const TICKS_PER_DAY: i64 = 86_400 * TICKS_PER_SECOND;
+/// Days between January 1, 0001 and January 1, 1970.
+pub(crate) const DAYS_TO_UNIX_EPOCH: i64 = 719_162;
+
+/// Write ticks in the ISO 8601 format of .NET's round-trip (`"o"`) format specifier, e.g.
+/// `2009-06-15T13:45:30.0000000`.
+pub(crate) fn write_round_trip(f: &mut fmt::Formatter<'_>, ticks: i64) -> fmt::Result {
+  let (days, time) = (ticks.div_euclid(TICKS_PER_DAY), ticks.rem_euclid(TICKS_PER_DAY));
+  let (year, month, day) = civil_from_days(days - DAYS_TO_UNIX_EPOCH);
+
+  let (seconds, fraction) = (time / TICKS_PER_SECOND, time % TICKS_PER_SECOND);
+  let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+  write!(f, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:07}", year, month, day, hour, minute, second, fraction)
+}
+
+/// Convert days since the Unix epoch into a proleptic Gregorian `(year, month, day)`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097);
+  let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+  let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+  let year = yoe + era * 400 + i64::from(month <= 2);
+
+  (year, month, day)
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use super::{ConversionError, DateTime, Object, Value, date_time};
+
+/// A point in time relative to UTC, i.e. a serialized `System.DateTimeOffset`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DateTimeOffset {
+  /// The date and time in UTC.
+  pub date_time: DateTime,
+  /// The offset from UTC in minutes.
+  pub offset_minutes: i16,
+}
+
+impl DateTimeOffset {
+  /// The ticks of the local date and time, i.e. the UTC date and time plus the offset.
+  pub fn local_ticks(&self) -> i64 {
+    self.date_time.ticks() + i64::from(self.offset_minutes) * 60 * date_time::TICKS_PER_SECOND
+  }
+}
+
+/// Formats the value like .NET's round-trip (`"o"`) format specifier, e.g. `2009-06-15T13:45:30.0000000-07:00`.
+impl fmt::Display for DateTimeOffset {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    date_time::write_round_trip(f, self.local_ticks())?;
+
+    let sign = if self.offset_minutes < 0 { '-' } else { '+' };
+    let offset = self.offset_minutes.unsigned_abs();
+    write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)
+  }
+}
+
+impl TryFrom<&Object<'_>> for DateTimeOffset {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.DateTimeOffset")?;
+
+    let Value::DateTime(date_time) = object.member("DateTime")? else {
+      return Err(ConversionError::InvalidMember("DateTime"))
+    };
+    let Value::Int16(offset_minutes) = object.member("OffsetMinutes")? else {
+      return Err(ConversionError::InvalidMember("OffsetMinutes"))
+    };
+
+    Ok(Self { date_time: *date_time, offset_minutes: *offset_minutes })
+  }
+}
@@ -1,5 +1,64 @@
+use std::fmt;
+
+use super::{ConversionError, Object, Value};
 use crate::data_type;
 
 /// A decimal number.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Decimal(pub(crate) data_type::Decimal);
+
+impl Decimal {
+  /// Get the value as a [`rust_decimal::Decimal`].
+  pub fn to_rust_decimal(&self) -> rust_decimal::Decimal {
+    (self.0).0
+  }
+}
+
+impl From<rust_decimal::Decimal> for Decimal {
+  fn from(decimal: rust_decimal::Decimal) -> Self {
+    Self(data_type::Decimal(decimal))
+  }
+}
+
+impl From<Decimal> for rust_decimal::Decimal {
+  fn from(decimal: Decimal) -> Self {
+    decimal.to_rust_decimal()
+  }
+}
+
+impl fmt::Display for Decimal {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    (self.0).0.fmt(f)
+  }
+}
+
+/// Converts a `System.Decimal` object, as serialized by .NET Framework (`flags`, `hi`, `mid`, `lo`)
+/// or .NET (`_flags`, `_hi32` and either `_lo64` or `_mid32` and `_lo32`).
+impl TryFrom<&Object<'_>> for Decimal {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Decimal")?;
+
+    let int32 = |name| match object.member(name)? {
+      Value::Int32(v) => Ok(*v as u32),
+      Value::UInt32(v) => Ok(*v),
+      _ => Err(ConversionError::InvalidMember(name)),
+    };
+
+    let (flags, hi, lo) = if object.members.contains_key("flags") {
+      ("flags", int32("hi")?, (u64::from(int32("mid")?) << 32) | u64::from(int32("lo")?))
+    } else {
+      let lo = match object.members.get("_lo64") {
+        Some(Value::Int64(v)) => *v as u64,
+        Some(Value::UInt64(v)) => *v,
+        Some(_) => return Err(ConversionError::InvalidMember("_lo64")),
+        None => (u64::from(int32("_mid32")?) << 32) | u64::from(int32("_lo32")?),
+      };
+
+      ("_flags", int32("_hi32")?, lo)
+    };
+
+    data_type::Decimal::from_parts(int32(flags)? as i32, hi, lo).map(Self).ok_or(ConversionError::InvalidMember(flags))
+  }
+}
@@ -0,0 +1,284 @@
+use std::fmt;
+
+use bitflags::bitflags;
+
+use super::{ConversionError, Object, Value};
+
+/// Names of the `System.Drawing.KnownColor` values, starting at `1`.
+#[rustfmt::skip]
+const KNOWN_COLOR_NAMES: [&str; 175] = [
+  "ActiveBorder", "ActiveCaption", "ActiveCaptionText", "AppWorkspace", "Control", "ControlDark", "ControlDarkDark",
+  "ControlLight", "ControlLightLight", "ControlText", "Desktop", "GrayText", "Highlight", "HighlightText", "HotTrack",
+  "InactiveBorder", "InactiveCaption", "InactiveCaptionText", "Info", "InfoText", "Menu", "MenuText", "ScrollBar",
+  "Window", "WindowFrame", "WindowText", "Transparent", "AliceBlue", "AntiqueWhite", "Aqua", "Aquamarine", "Azure",
+  "Beige", "Bisque", "Black", "BlanchedAlmond", "Blue", "BlueViolet", "Brown", "BurlyWood", "CadetBlue", "Chartreuse",
+  "Chocolate", "Coral", "CornflowerBlue", "Cornsilk", "Crimson", "Cyan", "DarkBlue", "DarkCyan", "DarkGoldenrod",
+  "DarkGray", "DarkGreen", "DarkKhaki", "DarkMagenta", "DarkOliveGreen", "DarkOrange", "DarkOrchid", "DarkRed",
+  "DarkSalmon", "DarkSeaGreen", "DarkSlateBlue", "DarkSlateGray", "DarkTurquoise", "DarkViolet", "DeepPink",
+  "DeepSkyBlue", "DimGray", "DodgerBlue", "Firebrick", "FloralWhite", "ForestGreen", "Fuchsia", "Gainsboro",
+  "GhostWhite", "Gold", "Goldenrod", "Gray", "Green", "GreenYellow", "Honeydew", "HotPink", "IndianRed", "Indigo",
+  "Ivory", "Khaki", "Lavender", "LavenderBlush", "LawnGreen", "LemonChiffon", "LightBlue", "LightCoral", "LightCyan",
+  "LightGoldenrodYellow", "LightGray", "LightGreen", "LightPink", "LightSalmon", "LightSeaGreen", "LightSkyBlue",
+  "LightSlateGray", "LightSteelBlue", "LightYellow", "Lime", "LimeGreen", "Linen", "Magenta", "Maroon",
+  "MediumAquamarine", "MediumBlue", "MediumOrchid", "MediumPurple", "MediumSeaGreen", "MediumSlateBlue",
+  "MediumSpringGreen", "MediumTurquoise", "MediumVioletRed", "MidnightBlue", "MintCream", "MistyRose", "Moccasin",
+  "NavajoWhite", "Navy", "OldLace", "Olive", "OliveDrab", "Orange", "OrangeRed", "Orchid", "PaleGoldenrod", "PaleGreen",
+  "PaleTurquoise", "PaleVioletRed", "PapayaWhip", "PeachPuff", "Peru", "Pink", "Plum", "PowderBlue", "Purple", "Red",
+  "RosyBrown", "RoyalBlue", "SaddleBrown", "Salmon", "SandyBrown", "SeaGreen", "SeaShell", "Sienna", "Silver",
+  "SkyBlue", "SlateBlue", "SlateGray", "Snow", "SpringGreen", "SteelBlue", "Tan", "Teal", "Thistle", "Tomato",
+  "Turquoise", "Violet", "Wheat", "White", "WhiteSmoke", "Yellow", "YellowGreen", "ButtonFace", "ButtonHighlight",
+  "ButtonShadow", "GradientActiveCaption", "GradientInactiveCaption", "MenuBar", "MenuHighlight", "RebeccaPurple",
+];
+
+const STATE_KNOWN_COLOR_VALID: i16 = 0x0001;
+const STATE_ARGB_VALUE_VALID: i16 = 0x0002;
+const STATE_NAME_VALID: i16 = 0x0008;
+
+/// An image, i.e. a serialized `System.Drawing.Bitmap`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bitmap<'i> {
+  /// The encoded image, usually a PNG or BMP file, borrowed from the input.
+  pub data: &'i [u8],
+}
+
+impl<'i> TryFrom<&Object<'i>> for Bitmap<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Drawing.Bitmap")?;
+
+    match object.member("Data")? {
+      Value::Bytes(data) => Ok(Self { data }),
+      _ => Err(ConversionError::InvalidMember("Data")),
+    }
+  }
+}
+
+/// A color, i.e. a serialized `System.Drawing.Color`.
+///
+/// A color is either empty, a known color, a named color or an ARGB color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color<'i> {
+  /// The name of a color which is neither known nor an ARGB color.
+  pub name: Option<&'i str>,
+  /// The ARGB value, e.g. `0xFF00FF00` for opaque green.
+  pub argb: Option<u32>,
+  /// The `System.Drawing.KnownColor` value.
+  pub known_color: Option<i16>,
+}
+
+impl Color<'_> {
+  /// Get the name of a known color, e.g. `Red` or `ControlText`.
+  pub fn known_color_name(&self) -> Option<&'static str> {
+    let index = usize::try_from(self.known_color?).ok()?.checked_sub(1)?;
+    KNOWN_COLOR_NAMES.get(index).copied()
+  }
+
+  /// Whether this is `Color.Empty`.
+  pub fn is_empty(&self) -> bool {
+    self.name.is_none() && self.argb.is_none() && self.known_color.is_none()
+  }
+}
+
+/// Formats the color like `Color.Name`, i.e. as its name or as its hexadecimal ARGB value, e.g. `ff00ff00`.
+impl fmt::Display for Color<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if let Some(name) = self.known_color_name().or(self.name) {
+      return f.write_str(name)
+    }
+
+    match self.argb {
+      Some(argb) => write!(f, "{:x}", argb),
+      None => f.write_str("0"),
+    }
+  }
+}
+
+impl<'i> TryFrom<&Object<'i>> for Color<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Drawing.Color")?;
+
+    let short = |name| match object.member(name)? {
+      Value::Int16(v) => Ok(*v),
+      _ => Err(ConversionError::InvalidMember(name)),
+    };
+
+    let state = short("state")?;
+    let value = match object.member("value")? {
+      Value::Int64(v) => *v,
+      _ => return Err(ConversionError::InvalidMember("value")),
+    };
+
+    Ok(Self {
+      name: if state & STATE_NAME_VALID != 0 { object.optional_str_member("name")? } else { None },
+      argb: (state & STATE_ARGB_VALUE_VALID != 0).then_some(value as u32),
+      known_color: (state & STATE_KNOWN_COLOR_VALID != 0).then_some(short("knownColor")?),
+    })
+  }
+}
+
+/// A point, i.e. a serialized `System.Drawing.Point`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+  /// The x-coordinate.
+  pub x: i32,
+  /// The y-coordinate.
+  pub y: i32,
+}
+
+impl TryFrom<&Object<'_>> for Point {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Drawing.Point")?;
+
+    Ok(Self { x: object.int32_member("x")?, y: object.int32_member("y")? })
+  }
+}
+
+/// A size, i.e. a serialized `System.Drawing.Size`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Size {
+  /// The width.
+  pub width: i32,
+  /// The height.
+  pub height: i32,
+}
+
+impl TryFrom<&Object<'_>> for Size {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Drawing.Size")?;
+
+    Ok(Self { width: object.int32_member("width")?, height: object.int32_member("height")? })
+  }
+}
+
+/// A rectangle, i.e. a serialized `System.Drawing.Rectangle`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rectangle {
+  /// The x-coordinate of the upper-left corner.
+  pub x: i32,
+  /// The y-coordinate of the upper-left corner.
+  pub y: i32,
+  /// The width.
+  pub width: i32,
+  /// The height.
+  pub height: i32,
+}
+
+impl Rectangle {
+  /// Get the upper-left corner.
+  pub fn location(&self) -> Point {
+    Point { x: self.x, y: self.y }
+  }
+
+  /// Get the size.
+  pub fn size(&self) -> Size {
+    Size { width: self.width, height: self.height }
+  }
+}
+
+impl TryFrom<&Object<'_>> for Rectangle {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Drawing.Rectangle")?;
+
+    Ok(Self {
+      x: object.int32_member("x")?,
+      y: object.int32_member("y")?,
+      width: object.int32_member("width")?,
+      height: object.int32_member("height")?,
+    })
+  }
+}
+
+bitflags! {
+  /// The style of a [`Font`], i.e. a `System.Drawing.FontStyle`.
+  ///
+  /// A regular font has no flags set.
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+  pub struct FontStyle: i32 {
+    /// Bold text.
+    const BOLD      = 0x01;
+    /// Italic text.
+    const ITALIC    = 0x02;
+    /// Underlined text.
+    const UNDERLINE = 0x04;
+    /// Text with a line through the middle.
+    const STRIKEOUT = 0x08;
+  }
+}
+
+/// The unit of the size of a [`Font`], i.e. a `System.Drawing.GraphicsUnit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphicsUnit {
+  /// The world coordinate system unit.
+  World,
+  /// The unit of the display device.
+  Display,
+  /// A device pixel.
+  Pixel,
+  /// A printer's point, i.e. 1/72 inch.
+  Point,
+  /// An inch.
+  Inch,
+  /// A document unit, i.e. 1/300 inch.
+  Document,
+  /// A millimeter.
+  Millimeter,
+}
+
+impl TryFrom<i32> for GraphicsUnit {
+  type Error = i32;
+
+  fn try_from(value: i32) -> Result<Self, Self::Error> {
+    Ok(match value {
+      0 => Self::World,
+      1 => Self::Display,
+      2 => Self::Pixel,
+      3 => Self::Point,
+      4 => Self::Inch,
+      5 => Self::Document,
+      6 => Self::Millimeter,
+      value => return Err(value),
+    })
+  }
+}
+
+/// A font, i.e. a serialized `System.Drawing.Font`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Font<'i> {
+  /// The name of the font family, e.g. `Segoe UI`.
+  pub name: &'i str,
+  /// The size in [`unit`](Self::unit)s.
+  pub size: f32,
+  /// The style.
+  pub style: FontStyle,
+  /// The unit of the size.
+  pub unit: GraphicsUnit,
+}
+
+impl<'i> TryFrom<&Object<'i>> for Font<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Drawing.Font")?;
+
+    let size = match object.member("Size")? {
+      Value::Single(size) => *size,
+      _ => return Err(ConversionError::InvalidMember("Size")),
+    };
+
+    Ok(Self {
+      name: object.str_member("Name")?,
+      size,
+      style: FontStyle::from_bits_retain(object.enum_member("Style")?),
+      unit: GraphicsUnit::try_from(object.enum_member("Unit")?).map_err(|_| ConversionError::InvalidMember("Unit"))?,
+    })
+  }
+}
@@ -0,0 +1,298 @@
+use std::{error, fmt};
+
+use super::{DateTime, Decimal, Guid, Object, TimeSpan, Value};
+
+/// Strict conversion from a [`Value`].
+///
+/// Unlike deserializing with serde, no implicit conversions between value types are performed.
+/// Implemented for .NET classes with [`#[derive(NrbfClass)]`](derive@crate::value::NrbfClass).
+pub trait FromValue<'i>: Sized {
+  /// Convert a value.
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError>;
+}
+
+/// A Rust type bound to a .NET class.
+///
+/// Usually implemented with [`#[derive(NrbfClass)]`](derive@crate::value::NrbfClass).
+pub trait NrbfClass<'i>: FromValue<'i> {
+  /// The full name of the .NET class, e.g. `Contoso.Order`.
+  const CLASS: &'static str;
+  /// The simple name of the assembly of the class, e.g. `Contoso.Model`, or `None` to accept any assembly.
+  const ASSEMBLY: Option<&'static str>;
+
+  /// Convert an object, checking its class and library.
+  fn from_object(object: &Object<'i>) -> Result<Self, FromValueError>;
+}
+
+/// Error while converting a [`Value`] with [`FromValue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FromValueError {
+  /// The value has an unexpected type.
+  UnexpectedType {
+    /// The expected type.
+    expected: &'static str,
+    /// The actual type, e.g. `object` or `Int32`.
+    found: &'static str,
+  },
+  /// The object is not an instance of the expected class.
+  UnexpectedClass {
+    /// The expected class name.
+    expected: &'static str,
+    /// The actual class name.
+    found: String,
+  },
+  /// The object belongs to an unexpected library.
+  UnexpectedLibrary {
+    /// The expected assembly name.
+    expected: &'static str,
+    /// The actual library name, `None` for `mscorlib`.
+    found: Option<String>,
+  },
+  /// A required member is missing.
+  MissingMember(&'static str),
+  /// A member could not be converted.
+  Member {
+    /// The member name.
+    name: &'static str,
+    /// The error converting the member.
+    error: Box<FromValueError>,
+  },
+  /// An array element could not be converted.
+  Element {
+    /// The element index.
+    index: usize,
+    /// The error converting the element.
+    error: Box<FromValueError>,
+  },
+  /// A custom error, e.g. from a converter.
+  Custom(String),
+}
+
+impl FromValueError {
+  /// Create a custom error.
+  pub fn custom(message: impl fmt::Display) -> Self {
+    Self::Custom(message.to_string())
+  }
+
+  /// Create an error for a value of an unexpected type.
+  pub fn unexpected_type(expected: &'static str, value: &Value<'_>) -> Self {
+    Self::UnexpectedType { expected, found: type_name(value) }
+  }
+}
+
+impl fmt::Display for FromValueError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnexpectedType { expected, found } => write!(f, "expected {}, found {}", expected, found),
+      Self::UnexpectedClass { expected, found } => write!(f, "expected class {}, found {}", expected, found),
+      Self::UnexpectedLibrary { expected, found } => {
+        write!(f, "expected library {}, found {}", expected, found.as_deref().unwrap_or("mscorlib"))
+      },
+      Self::MissingMember(name) => write!(f, "missing member {}", name),
+      Self::Member { name, error } => write!(f, "member {}: {}", name, error),
+      Self::Element { index, error } => write!(f, "element {}: {}", index, error),
+      Self::Custom(message) => f.write_str(message),
+    }
+  }
+}
+
+impl error::Error for FromValueError {}
+
+impl From<super::ConversionError> for FromValueError {
+  fn from(err: super::ConversionError) -> Self {
+    Self::Custom(err.to_string())
+  }
+}
+
+/// Get the name of the type of a value used in error messages.
+pub(crate) fn type_name(value: &Value<'_>) -> &'static str {
+  match value {
+    Value::Object(_) => "object",
+    Value::Array(_) | Value::Bytes(_) => "array",
+    Value::Boolean(_) => "Boolean",
+    Value::Byte(_) => "Byte",
+    Value::Char(_) => "Char",
+    Value::Decimal(_) => "Decimal",
+    Value::Double(_) => "Double",
+    Value::Int16(_) => "Int16",
+    Value::Int32(_) => "Int32",
+    Value::Int64(_) => "Int64",
+    Value::SByte(_) => "SByte",
+    Value::Single(_) => "Single",
+    Value::TimeSpan(_) => "TimeSpan",
+    Value::DateTime(_) => "DateTime",
+    Value::UInt16(_) => "UInt16",
+    Value::UInt32(_) => "UInt32",
+    Value::UInt64(_) => "UInt64",
+    Value::String(_) => "String",
+    Value::Null => "null",
+  }
+}
+
+impl<'i> Object<'i> {
+  /// Check that the object is an instance of the given class in the given assembly.
+  ///
+  /// The assembly is compared with the simple name of the library, objects without a library
+  /// belong to `mscorlib`.
+  pub fn expect_class_in(&self, class: &'static str, assembly: Option<&'static str>) -> Result<(), FromValueError> {
+    if self.class != class {
+      return Err(FromValueError::UnexpectedClass { expected: class, found: self.class.to_owned() })
+    }
+
+    if let Some(assembly) = assembly {
+      let library = self.library.unwrap_or("mscorlib");
+      if library.split(',').next().map(str::trim) != Some(assembly) {
+        return Err(FromValueError::UnexpectedLibrary { expected: assembly, found: self.library.map(str::to_owned) })
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Convert a member with [`FromValue`].
+  ///
+  /// Errors are wrapped in [`FromValueError::Member`].
+  pub fn convert_member<T: FromValue<'i>>(&self, name: &'static str) -> Result<T, FromValueError> {
+    self.convert_member_with(name, T::from_value)
+  }
+
+  /// Convert a member with the given function.
+  ///
+  /// Errors are wrapped in [`FromValueError::Member`].
+  pub fn convert_member_with<T, F>(&self, name: &'static str, f: F) -> Result<T, FromValueError>
+  where
+    F: FnOnce(&Value<'i>) -> Result<T, FromValueError>,
+  {
+    let value = self.members.get(name).ok_or(FromValueError::MissingMember(name))?;
+    f(value).map_err(|error| FromValueError::Member { name, error: Box::new(error) })
+  }
+
+  /// Convert a member with [`FromValue`], using `None` if the member is missing.
+  pub fn convert_optional_member<T: FromValue<'i>>(&self, name: &'static str) -> Result<Option<T>, FromValueError> {
+    match self.members.get(name) {
+      Some(_) => self.convert_member(name).map(Some),
+      None => Ok(None),
+    }
+  }
+}
+
+macro_rules! impl_from_value {
+  ($($ty:ty => $variant:ident),* $(,)?) => {
+    $(
+      impl FromValue<'_> for $ty {
+        fn from_value(value: &Value<'_>) -> Result<Self, FromValueError> {
+          match value {
+            Value::$variant(v) => Ok(*v),
+            _ => Err(FromValueError::unexpected_type(stringify!($variant), value)),
+          }
+        }
+      }
+    )*
+  };
+}
+
+impl_from_value! {
+  bool => Boolean,
+  u8 => Byte,
+  char => Char,
+  f64 => Double,
+  i16 => Int16,
+  i32 => Int32,
+  i64 => Int64,
+  i8 => SByte,
+  f32 => Single,
+  TimeSpan => TimeSpan,
+  DateTime => DateTime,
+  u16 => UInt16,
+  u32 => UInt32,
+  u64 => UInt64,
+}
+
+impl FromValue<'_> for Decimal {
+  fn from_value(value: &Value<'_>) -> Result<Self, FromValueError> {
+    match value {
+      Value::Decimal(v) => Ok(v.clone()),
+      Value::Object(object) => Ok(Decimal::try_from(object)?),
+      _ => Err(FromValueError::unexpected_type("Decimal", value)),
+    }
+  }
+}
+
+impl<'i> FromValue<'i> for &'i str {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    match value {
+      Value::String(s) => Ok(s),
+      _ => Err(FromValueError::unexpected_type("String", value)),
+    }
+  }
+}
+
+impl FromValue<'_> for String {
+  fn from_value(value: &Value<'_>) -> Result<Self, FromValueError> {
+    <&str>::from_value(value).map(str::to_owned)
+  }
+}
+
+impl<'i> FromValue<'i> for &'i [u8] {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    match value {
+      Value::Bytes(bytes) => Ok(bytes),
+      _ => Err(FromValueError::unexpected_type("byte array", value)),
+    }
+  }
+}
+
+impl FromValue<'_> for Guid {
+  fn from_value(value: &Value<'_>) -> Result<Self, FromValueError> {
+    match value {
+      Value::Object(object) => Ok(Guid::try_from(object)?),
+      _ => Err(FromValueError::unexpected_type("System.Guid", value)),
+    }
+  }
+}
+
+impl<'i> FromValue<'i> for Value<'i> {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    Ok(value.clone())
+  }
+}
+
+impl<'i> FromValue<'i> for Object<'i> {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    match value {
+      Value::Object(object) => Ok(object.clone()),
+      _ => Err(FromValueError::unexpected_type("object", value)),
+    }
+  }
+}
+
+/// Converts `null` into `None`.
+impl<'i, T: FromValue<'i>> FromValue<'i> for Option<T> {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    match value {
+      Value::Null => Ok(None),
+      value => T::from_value(value).map(Some),
+    }
+  }
+}
+
+/// Converts an array, including a byte array.
+impl<'i, T: FromValue<'i>> FromValue<'i> for Vec<T> {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    let element = |index, value: &Value<'i>| {
+      T::from_value(value).map_err(|error| FromValueError::Element { index, error: Box::new(error) })
+    };
+
+    match value {
+      Value::Array(values) => values.iter().enumerate().map(|(i, value)| element(i, value)).collect(),
+      Value::Bytes(bytes) => bytes.iter().enumerate().map(|(i, b)| element(i, &Value::Byte(*b))).collect(),
+      _ => Err(FromValueError::unexpected_type("array", value)),
+    }
+  }
+}
+
+impl<'i, T: FromValue<'i>> FromValue<'i> for Box<T> {
+  fn from_value(value: &Value<'i>) -> Result<Self, FromValueError> {
+    T::from_value(value).map(Box::new)
+  }
+}
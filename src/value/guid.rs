@@ -0,0 +1,81 @@
+use std::fmt;
+
+use super::{ConversionError, Object, Value};
+
+/// A globally unique identifier, i.e. a serialized `System.Guid`.
+///
+/// # Example
+///
+/// ```
+/// use nrbf::value::Guid;
+///
+/// let guid = Guid::from_bytes_le([
+///   0x78, 0x56, 0x34, 0x12, 0x34, 0x12, 0x78, 0x56, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0,
+/// ]);
+///
+/// assert_eq!(guid.to_string(), "12345678-1234-5678-1234-56789abcdef0");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Guid([u8; 16]);
+
+impl Guid {
+  /// Create a GUID from bytes in the order of .NET's `Guid.ToByteArray`, i.e. with the first three
+  /// groups in little-endian byte order.
+  pub fn from_bytes_le(bytes: [u8; 16]) -> Self {
+    Self(bytes)
+  }
+
+  /// Get the bytes in the order of .NET's `Guid.ToByteArray`, i.e. with the first three groups in
+  /// little-endian byte order.
+  pub fn to_bytes_le(&self) -> [u8; 16] {
+    self.0
+  }
+}
+
+impl fmt::Display for Guid {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let b = &self.0;
+
+    write!(
+      f,
+      "{:08x}-{:04x}-{:04x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+      u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+      u16::from_le_bytes([b[4], b[5]]),
+      u16::from_le_bytes([b[6], b[7]]),
+      b[8],
+      b[9],
+      b[10],
+      b[11],
+      b[12],
+      b[13],
+      b[14],
+      b[15],
+    )
+  }
+}
+
+impl TryFrom<&Object<'_>> for Guid {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Guid")?;
+
+    let mut bytes = [0; 16];
+
+    let Value::Int32(a) = object.member("_a")? else { return Err(ConversionError::InvalidMember("_a")) };
+    bytes[0..4].copy_from_slice(&a.to_le_bytes());
+
+    let Value::Int16(b) = object.member("_b")? else { return Err(ConversionError::InvalidMember("_b")) };
+    bytes[4..6].copy_from_slice(&b.to_le_bytes());
+
+    let Value::Int16(c) = object.member("_c")? else { return Err(ConversionError::InvalidMember("_c")) };
+    bytes[6..8].copy_from_slice(&c.to_le_bytes());
+
+    for (byte, name) in bytes[8..].iter_mut().zip(["_d", "_e", "_f", "_g", "_h", "_i", "_j", "_k"]) {
+      let Value::Byte(b) = object.member(name)? else { return Err(ConversionError::InvalidMember(name)) };
+      *byte = *b;
+    }
+
+    Ok(Self(bytes))
+  }
+}
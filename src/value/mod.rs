@@ -6,20 +6,51 @@ use std::{fmt, iter};
 #[cfg(feature = "serde")]
 use serde::{
   Deserializer,
-  de::{self, Expected, IntoDeserializer, Visitor, value::Error},
+  de::{
+    self, Expected, IntoDeserializer, Visitor,
+    value::{Error, SeqDeserializer},
+  },
   forward_to_deserialize_any,
 };
 
+#[cfg(feature = "serde")]
+use crate::Options;
+
+mod accessors;
+mod conversion;
+pub use conversion::ConversionError;
+mod culture_info;
+pub use culture_info::CultureInfo;
 mod date_time;
-pub use date_time::{DateTime, DateTimeKind};
+pub use date_time::{DateTime, DateTimeKind, TimeConversionError};
+mod date_time_offset;
+pub use date_time_offset::DateTimeOffset;
 mod decimal;
 pub use decimal::Decimal;
+mod drawing;
+pub use drawing::{Bitmap, Color, Font, FontStyle, GraphicsUnit, Point, Rectangle, Size};
+mod from_value;
+pub(crate) use from_value::type_name;
+pub use from_value::{FromValue, FromValueError, NrbfClass};
+#[cfg(feature = "derive")]
+pub use nrbf_derive::NrbfClass;
+mod guid;
+pub use guid::Guid;
 mod object;
 pub use object::Object;
 #[cfg(feature = "serde")]
 use object::ObjectDeserializer;
+mod path;
+pub use path::{Path, PathError};
+mod remoting;
+pub(crate) use remoting::full_type_name;
+pub use remoting::{ChannelInfo, ConstructionCall, ConstructionResponse, ObjRef, TypeInfo};
 mod time_span;
 pub use time_span::TimeSpan;
+mod uri;
+pub use uri::Uri;
+mod version;
+pub use version::Version;
 
 /// An NRBF value.
 #[derive(Debug, Clone, PartialEq)]
@@ -28,6 +59,12 @@ pub enum Value<'i> {
   Object(Object<'i>),
   /// An array.
   Array(Vec<Value<'i>>),
+  /// A byte array, borrowed from the input. The rows of multi-dimensional byte arrays are byte
+  /// arrays as well.
+  ///
+  /// Byte arrays were parsed as an [`Array`](Self::Array) of [`Byte`](Self::Byte) values before
+  /// this variant was added.
+  Bytes(&'i [u8]),
   /// An boolean value.
   Boolean(bool),
   /// A byte.
@@ -81,23 +118,24 @@ impl Expected for ExpectedInArray {
 
 #[cfg(feature = "serde")]
 #[derive(Debug)]
-pub(crate) struct ArrayDeserializer<I> {
+pub(crate) struct ArrayDeserializer<'o, I> {
   iter: iter::Fuse<I>,
   count: usize,
+  options: &'o Options,
 }
 
 #[cfg(feature = "serde")]
-impl<I> ArrayDeserializer<I>
+impl<'o, I> ArrayDeserializer<'o, I>
 where
   I: Iterator,
 {
-  pub fn new(iter: I) -> Self {
-    Self { iter: iter.fuse(), count: 0 }
+  pub fn new(iter: I, options: &'o Options) -> Self {
+    Self { iter: iter.fuse(), count: 0, options }
   }
 }
 
 #[cfg(feature = "serde")]
-impl<'de, 'o, I> ArrayDeserializer<I>
+impl<'de, 'o, I> ArrayDeserializer<'o, I>
 where
   'de: 'o,
   I: Iterator<Item = &'o Value<'de>>,
@@ -117,7 +155,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, 'o, I> de::Deserializer<'de> for ArrayDeserializer<I>
+impl<'de, 'o, I> de::Deserializer<'de> for ArrayDeserializer<'o, I>
 where
   'de: 'o,
   I: Iterator<Item = &'o Value<'de>>,
@@ -141,7 +179,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, 'o, I> de::SeqAccess<'de> for ArrayDeserializer<I>
+impl<'de, 'o, I> de::SeqAccess<'de> for ArrayDeserializer<'o, I>
 where
   'de: 'o,
   I: Iterator<Item = &'o Value<'de>>,
@@ -155,7 +193,7 @@ where
     match self.iter.next() {
       Some(object) => {
         self.count += 1;
-        seed.deserialize(ValueDeserializer::new(object)).map(Some)
+        seed.deserialize(ValueDeserializer::new(object, self.options)).map(Some)
       },
       None => Ok(None),
     }
@@ -166,12 +204,23 @@ where
 #[derive(Debug)]
 pub(crate) struct ValueDeserializer<'de, 'o> {
   object: &'o Value<'de>,
+  options: &'o Options,
+  convert: bool,
 }
 
 #[cfg(feature = "serde")]
 impl<'de, 'o> ValueDeserializer<'de, 'o> {
-  pub fn new(object: &'o Value<'de>) -> Self {
-    Self { object }
+  pub fn new(object: &'o Value<'de>, options: &'o Options) -> Self {
+    Self { object, options, convert: true }
+  }
+
+  /// Create a deserializer for the result of a converter, which is not converted again.
+  pub(crate) fn converted(object: &'o Value<'de>, options: &'o Options) -> Self {
+    Self { object, options, convert: false }
+  }
+
+  fn object_deserializer(&self, object: &'o Object<'de>) -> ObjectDeserializer<'de, 'o> {
+    ObjectDeserializer::new(object, self.options, self.convert)
   }
 }
 
@@ -193,8 +242,9 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de, '_> {
     V: Visitor<'de>,
   {
     match self.object {
-      Value::Object(object) => ObjectDeserializer::new(object).deserialize_any(visitor),
-      Value::Array(members) => ArrayDeserializer::new(members.iter()).deserialize_any(visitor),
+      Value::Object(object) => self.object_deserializer(object).deserialize_any(visitor),
+      Value::Array(members) => ArrayDeserializer::new(members.iter(), self.options).deserialize_any(visitor),
+      Value::Bytes(bytes) => SeqDeserializer::<_, Error>::new(bytes.iter().copied()).deserialize_any(visitor),
       Value::Boolean(v) => visitor.visit_bool(*v),
       Value::SByte(v) => visitor.visit_i8(*v),
       Value::Int16(v) => visitor.visit_i16(*v),
@@ -209,17 +259,85 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de, '_> {
       Value::Char(v) => visitor.visit_char(*v),
       Value::Decimal(v) => visitor.visit_string((v.0).0.to_string()),
       Value::TimeSpan(v) => visitor.visit_i64(v.0.into()),
-      Value::DateTime(v) => visitor.visit_i64(v.0.into()),
+      Value::DateTime(v) => visitor.visit_i64(v.ticks()),
       Value::String(s) => visitor.visit_borrowed_str(s),
       Value::Null => visitor.visit_unit(),
     }
   }
 
+  /// Deserialize a `DateTime` as its round-trip string.
+  fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.object {
+      Value::DateTime(v) => visitor.visit_string(v.to_string()),
+      _ => self.deserialize_any(visitor),
+    }
+  }
+
+  fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_str(visitor)
+  }
+
   fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
   where
     V: Visitor<'de>,
   {
-    if matches!(self.object, Value::Null) { visitor.visit_none() } else { visitor.visit_some(self) }
+    match self.object {
+      Value::Null => visitor.visit_none(),
+      Value::Object(object) => self.object_deserializer(object).deserialize_option(visitor),
+      _ => visitor.visit_some(self),
+    }
+  }
+
+  fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.object {
+      Value::Object(object) => self.object_deserializer(object).deserialize_tuple(len, visitor),
+      _ => self.deserialize_any(visitor),
+    }
+  }
+
+  fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.object {
+      Value::Object(object) => self.object_deserializer(object).deserialize_bytes(visitor),
+      Value::Bytes(bytes) => visitor.visit_borrowed_bytes(bytes),
+      _ => self.deserialize_any(visitor),
+    }
+  }
+
+  fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_bytes(visitor)
+  }
+
+  /// Deserialize a unit variant by its name. Integers, like the `value__` member of .NET enums, are
+  /// deserialized as is, so enums map by discriminant with `serde_repr`.
+  fn deserialize_enum<V>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    match self.object {
+      Value::Object(object) => self.object_deserializer(object).deserialize_enum(name, variants, visitor),
+      Value::String(variant) => visitor.visit_enum(EnumDeserializer { variant }),
+      _ => self.deserialize_any(visitor),
+    }
   }
 
   fn deserialize_struct<V>(
@@ -231,15 +349,85 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de, '_> {
   where
     V: Visitor<'de>,
   {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde::de::{Error, value::MapDeserializer};
+
+    // Match the representation of `std::time` types in `serde`.
+    let duration_map = |secs_name, nanos_name, duration: Duration| {
+      MapDeserializer::new(
+        [(secs_name, duration.as_secs()), (nanos_name, u64::from(duration.subsec_nanos()))].into_iter(),
+      )
+    };
+
     match self.object {
-      Value::Object(object) => ObjectDeserializer::new(object).deserialize_struct(name, fields, visitor),
+      Value::Object(object) => self.object_deserializer(object).deserialize_struct(name, fields, visitor),
+      Value::DateTime(v) if name == "SystemTime" => {
+        let system_time = SystemTime::try_from(*v).map_err(Error::custom)?;
+        let since_epoch =
+          system_time.duration_since(UNIX_EPOCH).map_err(|_| Error::custom("date-time before the Unix epoch"))?;
+        duration_map("secs_since_epoch", "nanos_since_epoch", since_epoch).deserialize_map(visitor)
+      },
+      Value::TimeSpan(v) if name == "Duration" => {
+        let duration = Duration::try_from(*v).map_err(Error::custom)?;
+        duration_map("secs", "nanos", duration).deserialize_map(visitor)
+      },
       _ => self.deserialize_any(visitor),
     }
   }
 
   forward_to_deserialize_any! {
-      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-      bytes byte_buf unit unit_struct newtype_struct seq tuple
-      tuple_struct map enum identifier ignored_any
+      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+      unit unit_struct newtype_struct seq tuple_struct map identifier ignored_any
+  }
+}
+
+/// Deserializer of unit enum variants identified by name.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+struct EnumDeserializer<'de> {
+  variant: &'de str,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::EnumAccess<'de> for EnumDeserializer<'de> {
+  type Error = Error;
+  type Variant = Self;
+
+  fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+  where
+    V: de::DeserializeSeed<'de>,
+  {
+    seed.deserialize(self.variant.into_deserializer()).map(|v| (v, self))
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::VariantAccess<'de> for EnumDeserializer<'de> {
+  type Error = Error;
+
+  fn unit_variant(self) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+  where
+    T: de::DeserializeSeed<'de>,
+  {
+    Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"newtype variant"))
+  }
+
+  fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"tuple variant"))
+  }
+
+  fn struct_variant<V>(self, _fields: &'static [&'static str], _visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    Err(de::Error::invalid_type(de::Unexpected::UnitVariant, &"struct variant"))
   }
 }
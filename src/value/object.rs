@@ -1,21 +1,32 @@
 use std::collections::HashMap;
 #[cfg(feature = "serde")]
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
 #[cfg(feature = "serde")]
 use serde::{
   de::Expected,
-  de::{self, Visitor, value::Error},
+  de::{
+    self, IntoDeserializer, Visitor,
+    value::{Error, MapDeserializer, SeqDeserializer},
+  },
   forward_to_deserialize_any,
 };
 
 use super::Value;
 #[cfg(feature = "serde")]
-use super::{ArrayDeserializer, ValueDeserializer};
+use super::{
+  ArrayDeserializer, Bitmap, Color, CultureInfo, DateTimeOffset, Decimal, Guid, Uri, ValueDeserializer, Version,
+};
+#[cfg(feature = "serde")]
+use crate::Options;
 #[cfg(feature = "serde")]
 use crate::data_type::{Boolean, Byte, Char, Double, Int8, Int16, Int32, Int64, Single, UInt16, UInt32, UInt64};
 
 /// An NRBF object.
+///
+/// With `serde`, an object deserializes into the enum variant named like its class, either by its
+/// full name, e.g. `Contoso.Circle`, or without the namespace, e.g. `Circle`. A .NET enum
+/// deserializes as its underlying integer, e.g. into an enum deriving `serde_repr::Deserialize_repr`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Object<'i> {
   /// The class name.
@@ -26,34 +37,129 @@ pub struct Object<'i> {
   pub members: HashMap<&'i str, Value<'i>>,
 }
 
+#[cfg(feature = "serde")]
+type MemberMapDeserializer<'de, 'o> =
+  MapDeserializer<'de, std::vec::IntoIter<(MemberNameDeserializer<'de>, ValueDeserializer<'de, 'o>)>, Error>;
+
 #[cfg(feature = "serde")]
 #[derive(Debug)]
 pub(crate) struct ObjectDeserializer<'de, 'o> {
   object: &'o Object<'de>,
+  options: &'o Options,
+  convert: bool,
 }
 
 #[cfg(feature = "serde")]
 impl<'de, 'o> ObjectDeserializer<'de, 'o> {
-  pub fn new(object: &'o Object<'de>) -> Self {
-    Self { object }
+  /// Create a deserializer, which applies a matching converter of the type registry if `convert` is `true`.
+  pub fn new(object: &'o Object<'de>, options: &'o Options, convert: bool) -> Self {
+    Self { object, options, convert }
+  }
+
+  /// Convert the object with a matching converter of the type registry.
+  fn converted(&self) -> Option<Result<Value<'de>, Error>> {
+    use serde::de::Error;
+
+    if !self.convert {
+      return None
+    }
+
+    Some(self.options.type_registry.convert(self.object)?.map_err(Error::custom))
+  }
+
+  fn value_deserializer(&self, value: &'o Value<'de>) -> ValueDeserializer<'de, 'o> {
+    ValueDeserializer::new(value, self.options)
+  }
+
+  /// Get the members as a map, with member names according to the options.
+  ///
+  /// Inherited members keep their class name prefix if their name would be ambiguous otherwise.
+  /// If given, the class name is added as the first entry with the key of the type tag.
+  fn member_map<'a>(&self, class: Option<&'a Value<'de>>) -> MemberMapDeserializer<'de, 'a>
+  where
+    'o: 'a,
+  {
+    let member_names = self.options.member_names;
+    let members = &self.object.members;
+
+    let type_tag = self.options.type_tag.zip(class).map(|(type_tag, class)| {
+      (MemberNameDeserializer(Cow::Borrowed(type_tag)), ValueDeserializer::new(class, self.options))
+    });
+
+    let names = members.keys().map(|name| (*name, member_names.apply(name))).collect::<Vec<_>>();
+    let mut counts = HashMap::<&str, usize>::new();
+    for (_, name) in &names {
+      *counts.entry(name).or_default() += 1;
+    }
+
+    let entries = names.iter().map(|(name, renamed)| {
+      let key = if counts[renamed.as_ref()] > 1 && name.contains('+') { Cow::Borrowed(*name) } else { renamed.clone() };
+      (MemberNameDeserializer(key), self.value_deserializer(&members[name]))
+    });
+    let entries = type_tag.into_iter().chain(entries).collect::<Vec<_>>();
+
+    MapDeserializer::new(entries.into_iter())
+  }
+
+  /// Get the `hasValue` and `value` members if the object is a `Nullable`.
+  fn nullable(&self) -> Option<(bool, &'o Value<'de>)> {
+    if !self.object.class.starts_with("System.Nullable`") {
+      return None
+    }
+
+    match (self.object.members.get("hasValue"), self.object.members.get("value")) {
+      (Some(Value::Boolean(has_value)), Some(value)) => Some((*has_value, value)),
+      _ => None,
+    }
+  }
+
+  /// Get the underlying value of an enum or a boxed primitive.
+  fn underlying_value(&self) -> Option<&'o Value<'de>> {
+    let Object { class, library, members } = self.object;
+
+    if members.len() != 1 {
+      return None
+    }
+
+    match members.get("value__") {
+      Some(value) => Some(value),
+      None if library.is_none() && class.starts_with("System.") => members.get("m_value"),
+      None => None,
+    }
   }
 }
 
+/// A member name, borrowed from the input unless it was renamed.
 #[cfg(feature = "serde")]
-use serde::de::{IntoDeserializer, value::BorrowedStrDeserializer};
+struct MemberNameDeserializer<'de>(Cow<'de, str>);
 
 #[cfg(feature = "serde")]
-struct StrDeserializer<'i>(&'i str);
+impl<'de> IntoDeserializer<'de, Error> for MemberNameDeserializer<'de> {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self::Deserializer {
+    self
+  }
+}
 
 #[cfg(feature = "serde")]
-impl<'de, E> IntoDeserializer<'de, E> for StrDeserializer<'de>
-where
-  E: de::Error,
-{
-  type Deserializer = BorrowedStrDeserializer<'de, E>;
+impl<'de> de::Deserializer<'de> for MemberNameDeserializer<'de> {
+  type Error = Error;
+
+  fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: de::Visitor<'de>,
+  {
+    match self.0 {
+      Cow::Borrowed(name) => visitor.visit_borrowed_str(name),
+      Cow::Owned(name) => visitor.visit_string(name),
+    }
+  }
 
-  fn into_deserializer(self) -> BorrowedStrDeserializer<'de, E> {
-    BorrowedStrDeserializer::new(self.0)
+  forward_to_deserialize_any! {
+      bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+      bytes byte_buf option unit unit_struct newtype_struct seq tuple
+      tuple_struct map struct enum identifier ignored_any
   }
 }
 
@@ -65,25 +171,55 @@ impl<'de, 'o> de::Deserializer<'de> for ObjectDeserializer<'de, 'o> {
   where
     V: de::Visitor<'de>,
   {
-    use serde::{
-      Deserialize,
-      de::{Error, value::MapDeserializer},
-    };
+    use serde::{Deserialize, de::Error};
 
-    let Object { class, library, members } = self.object;
+    if let Some(value) = self.converted() {
+      return ValueDeserializer::converted(&value?, self.options).deserialize_any(visitor)
+    }
 
-    let map_deserializer: MapDeserializer<
-      'de,
-      std::iter::Map<std::collections::hash_map::Iter<'_, &'de str, Value<'de>>, _>,
-      _,
-    > = MapDeserializer::new(members.iter().map(|(key, value)| (StrDeserializer(key), ValueDeserializer::new(value))));
+    if let Some((has_value, value)) = self.nullable() {
+      return if has_value { self.value_deserializer(value).deserialize_any(visitor) } else { visitor.visit_none() }
+    }
 
-    if library.is_some() {
-      return map_deserializer.deserialize_map(visitor)
+    if let Some(value) = self.underlying_value()
+      && self.object.members.contains_key("value__")
+    {
+      return self.value_deserializer(value).deserialize_any(visitor)
     }
 
+    let Object { class, library, members } = self.object;
+
+    let map_deserializer = self.member_map(None);
+
     let class_name = class.split_once('`').map(|(s, _)| s).unwrap_or(*class);
 
+    if let Some(entries) = map_entries(class_name, members, self.options)? {
+      return MapDeserializer::new(entries.into_iter()).deserialize_map(visitor)
+    }
+
+    match seq_items(class_name, members)? {
+      Some(SeqItems::Values(items)) => {
+        return ArrayDeserializer::new(items.into_iter(), self.options).deserialize_any(visitor)
+      },
+      Some(SeqItems::Bytes(bytes)) => {
+        return SeqDeserializer::<_, de::value::Error>::new(bytes.into_iter()).deserialize_any(visitor)
+      },
+      None => (),
+    }
+
+    if let Ok(bitmap) = Bitmap::try_from(self.object) {
+      return visitor.visit_borrowed_bytes(bitmap.data)
+    }
+
+    if let Some(s) = display_string(class_name, self.object) {
+      return visitor.visit_string(s?)
+    }
+
+    if library.is_some() {
+      let class = Value::String(class);
+      return self.member_map(Some(&class)).deserialize_map(visitor)
+    }
+
     match class_name {
       "System.Boolean" => {
         let v = Boolean::deserialize(map_deserializer)?;
@@ -133,12 +269,22 @@ impl<'de, 'o> de::Deserializer<'de> for ObjectDeserializer<'de, 'o> {
         let v = UInt16::deserialize(map_deserializer)?;
         return visitor.visit_u16(v.into())
       },
-      "System.Collections.Generic.List" => {
-        if members.len() == 3 {
-          if let (Some(Value::Array(items)), Some(Value::Int32(size)), Some(Value::Int32(_version))) =
+      "System.Collections.Generic.List" | "System.Collections.ArrayList" => {
+        if members.len() == 3
+          && let (Some(items), Some(Value::Int32(size)), Some(Value::Int32(_version))) =
             (members.get("_items"), members.get("_size"), members.get("_version"))
-          {
-            return ListDeserializer::new(items.iter(), (*size) as usize).deserialize_any(visitor)
+        {
+          match items {
+            Value::Array(items) => {
+              return ListDeserializer::new(items.iter(), (*size) as usize, self.options).deserialize_any(visitor)
+            },
+            Value::Bytes(bytes) => match bytes.get(..(*size) as usize) {
+              Some(bytes) => {
+                return SeqDeserializer::<_, de::value::Error>::new(bytes.iter().copied()).deserialize_any(visitor)
+              },
+              None => return Err(Error::invalid_length(bytes.len(), &ExpectedInList(*size as usize))),
+            },
+            _ => (),
           }
         }
       },
@@ -150,25 +296,410 @@ impl<'de, 'o> de::Deserializer<'de> for ObjectDeserializer<'de, 'o> {
 
   fn deserialize_struct<V>(
     self,
-    _name: &'static str,
-    _fields: &'static [&'static str],
+    name: &'static str,
+    fields: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    if let Some(value) = self.converted() {
+      return ValueDeserializer::converted(&value?, self.options).deserialize_struct(name, fields, visitor)
+    }
+
+    self.member_map(None).deserialize_map(visitor)
+  }
+
+  fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    if let Some(value) = self.converted() {
+      return ValueDeserializer::converted(&value?, self.options).deserialize_option(visitor)
+    }
+
+    match self.nullable() {
+      Some((true, value)) => visitor.visit_some(self.value_deserializer(value)),
+      Some((false, _)) => visitor.visit_none(),
+      None => visitor.visit_some(self),
+    }
+  }
+
+  /// Deserialize a `System.Guid` from 16 bytes in the order of .NET's `Guid.ToByteArray`.
+  fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    if let Some(value) = self.converted() {
+      return ValueDeserializer::converted(&value?, self.options).deserialize_tuple(len, visitor)
+    }
+
+    match Guid::try_from(self.object) {
+      Ok(guid) if len == 16 => SeqDeserializer::new(guid.to_bytes_le().into_iter()).deserialize_any(visitor),
+      _ => self.deserialize_any(visitor),
+    }
+  }
+
+  /// Deserialize a `System.Guid` from bytes in the order of .NET's `Guid.ToByteArray`.
+  fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    if let Some(value) = self.converted() {
+      return ValueDeserializer::converted(&value?, self.options).deserialize_bytes(visitor)
+    }
+
+    match Guid::try_from(self.object) {
+      Ok(guid) => visitor.visit_bytes(&guid.to_bytes_le()),
+      _ => self.deserialize_any(visitor),
+    }
+  }
+
+  fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    self.deserialize_bytes(visitor)
+  }
+
+  fn deserialize_enum<V>(
+    self,
+    name: &'static str,
+    variants: &'static [&'static str],
     visitor: V,
   ) -> Result<V::Value, Self::Error>
   where
     V: Visitor<'de>,
   {
-    use serde::de::value::MapDeserializer;
+    if let Some(value) = self.converted() {
+      return ValueDeserializer::converted(&value?, self.options).deserialize_enum(name, variants, visitor)
+    }
 
-    let Object { class: _, library: _, members } = self.object;
+    match self.nullable() {
+      Some((true, value)) => return self.value_deserializer(value).deserialize_enum(name, variants, visitor),
+      Some((false, _)) => return self.deserialize_any(visitor),
+      None => (),
+    }
 
-    MapDeserializer::new(members.iter().map(|(key, value)| (*key, ValueDeserializer::new(value))))
-      .deserialize_map(visitor)
+    if let Some(value) = self.underlying_value() {
+      return self.value_deserializer(value).deserialize_enum(name, variants, visitor)
+    }
+
+    match class_variant(self.object.class, variants) {
+      Some(variant) => visitor.visit_enum(ClassEnumDeserializer { variant, object: self }),
+      None => self.deserialize_any(visitor),
+    }
   }
 
   forward_to_deserialize_any! {
       bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
-      bytes byte_buf option unit unit_struct newtype_struct seq tuple
-      tuple_struct map enum identifier ignored_any
+      unit unit_struct newtype_struct seq tuple_struct map identifier ignored_any
+  }
+}
+
+/// Find the enum variant named like the class, either by its full name, e.g. `Contoso.Circle`,
+/// or by its name without the namespace, e.g. `Circle`.
+#[cfg(feature = "serde")]
+fn class_variant(class: &str, variants: &'static [&'static str]) -> Option<&'static str> {
+  let full_name = class.split(['`', '[']).next().unwrap_or(class);
+  let name = full_name.rsplit(['.', '+']).next().unwrap_or(full_name);
+
+  variants
+    .iter()
+    .find(|variant| **variant == full_name)
+    .or_else(|| variants.iter().find(|variant| **variant == name))
+    .copied()
+}
+
+/// Deserializer of an enum variant chosen by the class name of an object.
+#[cfg(feature = "serde")]
+struct ClassEnumDeserializer<'de, 'o> {
+  variant: &'static str,
+  object: ObjectDeserializer<'de, 'o>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'o> de::EnumAccess<'de> for ClassEnumDeserializer<'de, 'o> {
+  type Error = Error;
+  type Variant = ObjectDeserializer<'de, 'o>;
+
+  fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+  where
+    V: de::DeserializeSeed<'de>,
+  {
+    let variant = seed.deserialize(self.variant.into_deserializer())?;
+    Ok((variant, self.object))
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::VariantAccess<'de> for ObjectDeserializer<'de, '_> {
+  type Error = Error;
+
+  fn unit_variant(self) -> Result<(), Self::Error> {
+    Ok(())
+  }
+
+  fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+  where
+    T: de::DeserializeSeed<'de>,
+  {
+    seed.deserialize(self)
+  }
+
+  fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    de::Deserializer::deserialize_tuple(self, len, visitor)
+  }
+
+  fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error>
+  where
+    V: Visitor<'de>,
+  {
+    de::Deserializer::deserialize_map(self.member_map(None), visitor)
+  }
+}
+
+/// Format the known BCL value types as strings.
+///
+/// Returns `None` if the class is not a known value type.
+#[cfg(feature = "serde")]
+fn display_string(class_name: &str, object: &Object<'_>) -> Option<Result<String, Error>> {
+  use serde::de::Error;
+
+  let s = match class_name {
+    "System.Decimal" => Decimal::try_from(object).map(|v| v.to_string()),
+    "System.Guid" => Guid::try_from(object).map(|v| v.to_string()),
+    "System.DateTimeOffset" => DateTimeOffset::try_from(object).map(|v| v.to_string()),
+    "System.Version" => Version::try_from(object).map(|v| v.to_string()),
+    "System.Uri" => Uri::try_from(object).map(|v| v.to_string()),
+    "System.Globalization.CultureInfo" => CultureInfo::try_from(object).map(|v| v.to_string()),
+    "System.Drawing.Color" => Color::try_from(object).map(|v| v.to_string()),
+    _ => return None,
+  };
+
+  Some(s.map_err(Error::custom))
+}
+
+/// Entries of a serialized map.
+#[cfg(feature = "serde")]
+type MapEntries<'de, 'o> = Vec<(ValueDeserializer<'de, 'o>, ValueDeserializer<'de, 'o>)>;
+
+/// Extract the entries of the known dictionary classes.
+///
+/// Returns `None` if the class is not a known dictionary class.
+#[cfg(feature = "serde")]
+fn map_entries<'de, 'o>(
+  class_name: &str,
+  members: &'o HashMap<&'de str, Value<'de>>,
+  options: &'o Options,
+) -> Result<Option<MapEntries<'de, 'o>>, Error> {
+  use serde::de::Error;
+
+  let pairs = match class_name {
+    "System.Collections.Generic.Dictionary" => array_member(members, "KeyValuePairs")?,
+    "System.Collections.Generic.SortedDictionary" => match members.get("_set") {
+      Some(Value::Object(set)) => array_member(&set.members, "Items")?,
+      _ => return Err(Error::custom("invalid SortedDictionary: missing _set")),
+    },
+    "System.Collections.Concurrent.ConcurrentDictionary" => {
+      if members.contains_key("m_serializationArray") {
+        array_member(members, "m_serializationArray")?
+      } else {
+        array_member(members, "_serializationArray")?
+      }
+    },
+    "System.Collections.Hashtable" => {
+      let (keys, values) = (array_member(members, "Keys")?, array_member(members, "Values")?);
+      if keys.len() != values.len() {
+        return Err(Error::custom("invalid Hashtable: Keys and Values differ in length"))
+      }
+
+      let entries = keys.iter().zip(values);
+      return Ok(Some(
+        entries.map(|(k, v)| (ValueDeserializer::new(k, options), ValueDeserializer::new(v, options))).collect(),
+      ))
+    },
+    "System.Collections.Generic.SortedList" => {
+      let (Some(Value::Array(keys)), Some(Value::Array(values)), Some(Value::Int32(size))) =
+        (members.get("keys"), members.get("values"), members.get("_size"))
+      else {
+        return Err(Error::custom("invalid SortedList: expected keys, values and _size"))
+      };
+
+      let size = usize::try_from(*size).map_err(|_| Error::custom("invalid SortedList: negative _size"))?;
+      if keys.len() < size || values.len() < size {
+        return Err(Error::custom("invalid SortedList: _size exceeds capacity"))
+      }
+
+      let entries = keys.iter().zip(values).take(size);
+      return Ok(Some(
+        entries.map(|(k, v)| (ValueDeserializer::new(k, options), ValueDeserializer::new(v, options))).collect(),
+      ))
+    },
+    _ => return Ok(None),
+  };
+
+  pairs
+    .iter()
+    .map(|pair| match pair {
+      Value::Object(Object { members, .. }) => match (members.get("key"), members.get("value")) {
+        (Some(key), Some(value)) => Ok((ValueDeserializer::new(key, options), ValueDeserializer::new(value, options))),
+        _ => Err(Error::custom("invalid key-value pair")),
+      },
+      _ => Err(Error::custom("invalid key-value pair")),
+    })
+    .collect::<Result<_, _>>()
+    .map(Some)
+}
+
+/// Extract the items of the known collection classes backed by an array, in logical order.
+///
+/// Stacks are presented from bottom to top, so pushing the items in order recreates the stack. A
+/// `Tuple`, `ValueTuple` or `KeyValuePair` is presented as a sequence of its items, e.g. for a Rust
+/// tuple.
+///
+/// Returns `None` if the class is not a known collection class.
+#[cfg(feature = "serde")]
+fn seq_items<'de, 'o>(
+  class_name: &str,
+  members: &'o HashMap<&'de str, Value<'de>>,
+) -> Result<Option<SeqItems<'de, 'o>>, Error> {
+  use serde::de::Error;
+
+  match class_name {
+    "System.Collections.Generic.HashSet" => seq_member(members, "Elements").map(Some),
+    "System.Collections.Generic.SortedSet" | "System.Collections.Generic.TreeSet" => {
+      seq_member(members, "Items").map(Some)
+    },
+    "System.Collections.Generic.LinkedList" => seq_member(members, "Data").map(Some),
+    "System.Collections.Queue" | "System.Collections.Generic.Queue" => {
+      let (Some(array), Some(Value::Int32(head)), Some(Value::Int32(size))) =
+        (members.get("_array").and_then(SeqItems::of_array), members.get("_head"), members.get("_size"))
+      else {
+        return Err(Error::custom("invalid Queue: expected _array, _head and _size"))
+      };
+
+      array.ring_buffer(*head, *size).map(Some)
+    },
+    "System.Collections.Stack" | "System.Collections.Generic.Stack" => {
+      let (Some(array), Some(Value::Int32(size))) =
+        (members.get("_array").and_then(SeqItems::of_array), members.get("_size"))
+      else {
+        return Err(Error::custom("invalid Stack: expected _array and _size"))
+      };
+
+      array.ring_buffer(0, *size).map(Some)
+    },
+    "System.Collections.Generic.KeyValuePair" => {
+      let (Some(key), Some(value)) = (members.get("key"), members.get("value")) else {
+        return Err(Error::custom("invalid KeyValuePair: expected key and value"))
+      };
+
+      Ok(Some(SeqItems::Values(vec![key, value])))
+    },
+    "System.Tuple" => {
+      tuple_items(members, "m_Item", "m_Rest", "System.Tuple").map(|items| Some(SeqItems::Values(items)))
+    },
+    "System.ValueTuple" => {
+      tuple_items(members, "Item", "Rest", "System.ValueTuple").map(|items| Some(SeqItems::Values(items)))
+    },
+    _ => Ok(None),
+  }
+}
+
+/// The items of a collection, borrowed from an array or copied from a byte array.
+#[cfg(feature = "serde")]
+enum SeqItems<'de, 'o> {
+  Values(Vec<&'o Value<'de>>),
+  Bytes(Vec<u8>),
+}
+
+#[cfg(feature = "serde")]
+impl<'de, 'o> SeqItems<'de, 'o> {
+  /// Get the items of an array or a byte array.
+  fn of_array(value: &'o Value<'de>) -> Option<Self> {
+    match value {
+      Value::Array(values) => Some(Self::Values(values.iter().collect())),
+      Value::Bytes(bytes) => Some(Self::Bytes(bytes.to_vec())),
+      _ => None,
+    }
+  }
+
+  /// Get the items of a ring buffer like [`ring_buffer`].
+  fn ring_buffer(self, head: i32, size: i32) -> Result<Self, Error> {
+    match self {
+      Self::Values(values) => ring_buffer(&values, head, size).map(Self::Values),
+      Self::Bytes(bytes) => ring_buffer(&bytes, head, size).map(Self::Bytes),
+    }
+  }
+}
+
+/// Get the items of an array or byte array member, treating a missing or null member as empty.
+#[cfg(feature = "serde")]
+fn seq_member<'de, 'o>(members: &'o HashMap<&'de str, Value<'de>>, name: &str) -> Result<SeqItems<'de, 'o>, Error> {
+  use serde::de::Error;
+
+  match members.get(name) {
+    Some(Value::Null) | None => Ok(SeqItems::Values(Vec::new())),
+    Some(value) => SeqItems::of_array(value).ok_or_else(|| Error::custom(format!("expected array in member {}", name))),
+  }
+}
+
+/// Get the items of a `System.Tuple` or `System.ValueTuple`.
+///
+/// Tuples with more than seven items nest the remaining items in a tuple in the `Rest` member,
+/// which is flattened.
+#[cfg(feature = "serde")]
+fn tuple_items<'de, 'o>(
+  mut members: &'o HashMap<&'de str, Value<'de>>,
+  item_prefix: &str,
+  rest: &str,
+  class: &str,
+) -> Result<Vec<&'o Value<'de>>, Error> {
+  use serde::de::Error;
+
+  let mut items = Vec::new();
+
+  loop {
+    items.extend((1..=7).map_while(|i| members.get(format!("{}{}", item_prefix, i).as_str())));
+
+    match members.get(rest) {
+      Some(Value::Object(Object { class: rest_class, members: rest_members, .. })) if rest_class.starts_with(class) => {
+        members = rest_members
+      },
+      Some(_) => return Err(Error::custom(format!("invalid {}: expected a tuple in {}", class, rest))),
+      None => return Ok(items),
+    }
+  }
+}
+
+/// Get `size` items of a ring buffer starting at index `head`, wrapping around at the end of the array.
+#[cfg(feature = "serde")]
+fn ring_buffer<T: Copy>(array: &[T], head: i32, size: i32) -> Result<Vec<T>, Error> {
+  use serde::de::Error;
+
+  let (Ok(head), Ok(size)) = (usize::try_from(head), usize::try_from(size)) else {
+    return Err(Error::custom("invalid collection: negative head or size"))
+  };
+  if size > array.len() || (size > 0 && head >= array.len()) {
+    return Err(Error::custom("invalid collection: head or size exceeds capacity"))
+  }
+
+  Ok((0..size).map(|i| array[(head + i) % array.len()]).collect())
+}
+
+/// Get an array member, treating a missing or null member as an empty array.
+#[cfg(feature = "serde")]
+fn array_member<'de, 'o>(members: &'o HashMap<&'de str, Value<'de>>, name: &str) -> Result<&'o [Value<'de>], Error> {
+  use serde::de::Error;
+
+  match members.get(name) {
+    Some(Value::Array(values)) => Ok(values),
+    Some(Value::Null) | None => Ok(&[]),
+    Some(_) => Err(Error::custom(format!("expected array in member {}", name))),
   }
 }
 
@@ -189,24 +720,24 @@ impl Expected for ExpectedInList {
 
 #[cfg(feature = "serde")]
 #[derive(Debug)]
-pub(crate) struct ListDeserializer<I> {
-  array_deserializer: ArrayDeserializer<I>,
+pub(crate) struct ListDeserializer<'o, I> {
+  array_deserializer: ArrayDeserializer<'o, I>,
   count: usize,
   size: usize,
 }
 
 #[cfg(feature = "serde")]
-impl<I> ListDeserializer<I>
+impl<'o, I> ListDeserializer<'o, I>
 where
   I: Iterator,
 {
-  pub fn new(iter: I, size: usize) -> Self {
-    Self { array_deserializer: ArrayDeserializer::new(iter), count: 0, size }
+  pub fn new(iter: I, size: usize, options: &'o Options) -> Self {
+    Self { array_deserializer: ArrayDeserializer::new(iter, options), count: 0, size }
   }
 }
 
 #[cfg(feature = "serde")]
-impl<'de, 'o, I> ListDeserializer<I>
+impl<'de, 'o, I> ListDeserializer<'o, I>
 where
   'de: 'o,
   I: Iterator<Item = &'o Value<'de>>,
@@ -225,7 +756,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, 'o, I> de::Deserializer<'de> for ListDeserializer<I>
+impl<'de, 'o, I> de::Deserializer<'de> for ListDeserializer<'o, I>
 where
   'de: 'o,
   I: Iterator<Item = &'o Value<'de>>,
@@ -249,7 +780,7 @@ where
 }
 
 #[cfg(feature = "serde")]
-impl<'de, 'o, I> de::SeqAccess<'de> for ListDeserializer<I>
+impl<'de, 'o, I> de::SeqAccess<'de> for ListDeserializer<'o, I>
 where
   'de: 'o,
   I: Iterator<Item = &'o Value<'de>>,
@@ -0,0 +1,271 @@
+use std::{error, fmt, str::FromStr};
+
+use super::{Object, Value};
+
+/// A path selecting values in a [`Value`] graph.
+///
+/// A path is a sequence of segments, optionally starting with `$` for the root value:
+///
+/// - `Name` or `.Name`: the member `Name` of an object. Names containing `.`, `[` or `]` can be
+///   written as `['Name']`.
+/// - `.*` or `[*]`: all members of an object or all elements of an array.
+/// - `[1]`, `[-1]`: an element of an array, negative indices count from the end. Indices out of range
+///   select nothing.
+/// - `[1:3]`, `[:-1]`, `[2:]`: a slice of an array.
+/// - `[class=Contoso.Order]`: only objects of the given class, by its full name or its generic
+///   type definition, e.g. ``System.Collections.Generic.List`1``.
+/// - `..`: the value itself and all values it contains, e.g. `..Name` or `..[class=Contoso.Order]`.
+///
+/// `List<T>` and `ArrayList` objects are treated as arrays of their elements, byte arrays and
+/// `List<byte>` objects as arrays of [`Value::Byte`] values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path {
+  segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+  Member(String),
+  Wildcard,
+  Index(isize),
+  Slice(Option<isize>, Option<isize>),
+  Class(String),
+  Descendants,
+}
+
+/// An error while parsing a [`Path`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathError {
+  /// The byte position of the error in the path.
+  pub position: usize,
+  message: &'static str,
+}
+
+impl fmt::Display for PathError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} at position {}", self.message, self.position)
+  }
+}
+
+impl error::Error for PathError {}
+
+impl FromStr for Path {
+  type Err = PathError;
+
+  fn from_str(path: &str) -> Result<Self, Self::Err> {
+    let error = |position, message| PathError { position, message };
+
+    let mut segments = Vec::new();
+    let mut pos = usize::from(path.starts_with('$'));
+
+    while pos < path.len() {
+      let rest = &path[pos..];
+
+      if let Some(rest) = rest.strip_prefix("..") {
+        segments.push(Segment::Descendants);
+        pos += 2;
+
+        if rest.starts_with('[') {
+          continue
+        }
+      } else if rest.starts_with('.') {
+        pos += 1;
+      } else if !rest.starts_with('[') && pos > usize::from(path.starts_with('$')) {
+        return Err(error(pos, "expected `.` or `[`"))
+      }
+
+      let rest = &path[pos..];
+      if let Some(bracket) = rest.strip_prefix('[') {
+        let len = bracket_len(bracket).ok_or_else(|| error(pos, "unclosed `[`"))?;
+        let content = bracket[..len].trim();
+
+        segments.push(bracket_segment(content).ok_or_else(|| error(pos + 1, "invalid bracket expression"))?);
+        pos += len + 2;
+      } else {
+        let len = rest.find(['.', '[']).unwrap_or(rest.len());
+        let name = rest[..len].trim();
+
+        segments.push(match name {
+          "" => return Err(error(pos, "expected a member name")),
+          "*" => Segment::Wildcard,
+          name => Segment::Member(name.to_owned()),
+        });
+        pos += len;
+      }
+    }
+
+    Ok(Self { segments })
+  }
+}
+
+/// Get the length of the contents of a bracket expression, allowing nested brackets and quotes.
+fn bracket_len(bracket: &str) -> Option<usize> {
+  let mut depth = 0usize;
+  let mut quote = None;
+
+  for (i, c) in bracket.char_indices() {
+    match (quote, c) {
+      (Some(q), c) if c == q => quote = None,
+      (Some(_), _) => (),
+      (None, '\'' | '"') => quote = Some(c),
+      (None, '[') => depth += 1,
+      (None, ']') if depth == 0 => return Some(i),
+      (None, ']') => depth -= 1,
+      _ => (),
+    }
+  }
+
+  None
+}
+
+fn bracket_segment(content: &str) -> Option<Segment> {
+  if content == "*" {
+    return Some(Segment::Wildcard)
+  }
+
+  if let Some(class) = content.strip_prefix("class").map(str::trim_start).and_then(|rest| rest.strip_prefix('=')) {
+    return Some(Segment::Class(class.trim().to_owned()))
+  }
+
+  for quote in ['\'', '"'] {
+    if let Some(name) = content.strip_prefix(quote).and_then(|content| content.strip_suffix(quote)) {
+      return Some(Segment::Member(name.to_owned()))
+    }
+  }
+
+  let index = |s: &str| if s.trim().is_empty() { Ok(None) } else { s.trim().parse().map(Some) };
+  match content.split_once(':') {
+    Some((start, end)) => Some(Segment::Slice(index(start).ok()?, index(end).ok()?)),
+    None => content.parse().ok().map(Segment::Index),
+  }
+}
+
+impl Path {
+  /// Parse a path.
+  pub fn parse(path: &str) -> Result<Self, PathError> {
+    path.parse()
+  }
+
+  /// Select all values matching the path.
+  pub fn select<'v, 'i>(&self, value: &'v Value<'i>) -> Vec<&'v Value<'i>> {
+    let mut values = vec![value];
+
+    for segment in &self.segments {
+      let mut selected = Vec::new();
+
+      for value in values {
+        match segment {
+          Segment::Member(name) => {
+            if let Value::Object(object) = value
+              && let Some(member) = object.members.get(name.as_str())
+            {
+              selected.push(member);
+            }
+          },
+          Segment::Wildcard => selected.extend(children(value)),
+          Segment::Index(index) => {
+            if let Some(elements) = value.elements()
+              && let Some(index) = resolve_index(*index, elements.len())
+              && let Some(element) = elements.get(index)
+            {
+              selected.push(*element);
+            }
+          },
+          Segment::Slice(start, end) => {
+            if let Some(elements) = value.elements() {
+              let len = elements.len();
+              let start = start.map_or(0, |start| resolve_bound(start, len));
+              let end = end.map_or(len, |end| resolve_bound(end, len));
+              selected.extend(elements.get(start..end).unwrap_or_default().iter().copied());
+            }
+          },
+          Segment::Class(class) => {
+            if let Value::Object(object) = value
+              && is_class(object, class)
+            {
+              selected.push(value);
+            }
+          },
+          Segment::Descendants => descendants(value, &mut selected),
+        }
+      }
+
+      values = selected;
+    }
+
+    values
+  }
+}
+
+/// Resolve a possibly negative index, or `None` if it is before the start.
+fn resolve_index(index: isize, len: usize) -> Option<usize> {
+  if index < 0 { len.checked_sub(index.unsigned_abs()) } else { usize::try_from(index).ok() }
+}
+
+/// Resolve a possibly negative slice bound, clamping it to `0..=len`.
+fn resolve_bound(bound: isize, len: usize) -> usize {
+  if bound < 0 { len.saturating_sub(bound.unsigned_abs()) } else { bound.unsigned_abs().min(len) }
+}
+
+fn is_class(object: &Object<'_>, class: &str) -> bool {
+  object.class == class || object.class.split_once('[').is_some_and(|(name, _)| name == class)
+}
+
+/// Get the members of an object or the elements of an array, members sorted by name.
+fn children<'v, 'i>(value: &'v Value<'i>) -> Vec<&'v Value<'i>> {
+  if let Some(elements) = value.elements() {
+    return elements
+  }
+
+  match value {
+    Value::Object(object) => {
+      let mut members = object.members.iter().collect::<Vec<_>>();
+      members.sort_unstable_by_key(|(name, _)| **name);
+      members.into_iter().map(|(_, member)| member).collect()
+    },
+    _ => Vec::new(),
+  }
+}
+
+fn descendants<'v, 'i>(value: &'v Value<'i>, selected: &mut Vec<&'v Value<'i>>) {
+  selected.push(value);
+
+  for child in children(value) {
+    descendants(child, selected);
+  }
+}
+
+impl<'i> Value<'i> {
+  /// Select all values matching a [`Path`].
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// use std::collections::HashMap;
+  ///
+  /// use nrbf::{Value, value::Object};
+  ///
+  /// let object = |class, members: Vec<(&'static str, Value<'static>)>| {
+  ///   Value::Object(Object { class, library: Some("Contoso"), members: HashMap::from_iter(members) })
+  /// };
+  /// let customer = |name| object("Contoso.Customer", vec![("Name", Value::String(name))]);
+  /// let order = |name| object("Contoso.Order", vec![("Customer", customer(name))]);
+  ///
+  /// let value = object("Contoso.Shop", vec![("Orders", Value::Array(vec![order("Alice"), order("Bob")]))]);
+  ///
+  /// assert_eq!(value.select("Orders[*].Customer.Name"), Ok(vec![&Value::String("Alice"), &Value::String("Bob")]));
+  /// assert_eq!(value.select("..[class=Contoso.Customer].Name").map(|names| names.len()), Ok(2));
+  /// assert_eq!(value.get_path("$.Orders[-1].Customer.Name"), Some(&Value::String("Bob")));
+  /// assert!(value.select("Orders[").is_err());
+  /// ```
+  pub fn select(&self, path: &str) -> Result<Vec<&Self>, PathError> {
+    Ok(path.parse::<Path>()?.select(self))
+  }
+
+  /// Get the first value matching a [`Path`].
+  ///
+  /// Returns `None` if no value matches or the path is invalid.
+  pub fn get_path(&self, path: &str) -> Option<&Self> {
+    path.parse::<Path>().ok()?.select(self).into_iter().next()
+  }
+}
@@ -0,0 +1,269 @@
+use super::{ConversionError, Object, Value};
+
+const OBJ_REF: &str = "System.Runtime.Remoting.ObjRef";
+const CONSTRUCTION_CALL: &str = "System.Runtime.Remoting.Messaging.ConstructionCall";
+const CONSTRUCTION_RESPONSE: &str = "System.Runtime.Remoting.Messaging.ConstructionResponse";
+
+/// A reference to a remote object, i.e. a serialized `System.Runtime.Remoting.ObjRef`.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use nrbf::{Value, value::{Object, ObjRef}};
+///
+/// let object = Object {
+///   class: "System.Runtime.Remoting.ObjRef",
+///   library: None,
+///   members: HashMap::from_iter([
+///     ("uri", Value::String("/4f1c7a3e_2b9d/x0z9ha1k_1.rem")),
+///     ("objrefFlags", Value::Int32(0)),
+///     ("channelInfo", Value::Object(Object {
+///       class: "System.Runtime.Remoting.ChannelInfo",
+///       library: None,
+///       members: HashMap::from_iter([("channelData", Value::Array(vec![Value::Object(Object {
+///         class: "System.Runtime.Remoting.Channels.ChannelDataStore",
+///         library: None,
+///         members: HashMap::from_iter([
+///           ("_channelURIs", Value::Array(vec![Value::String("tcp://10.0.0.1:8080")])),
+///           ("_extraData", Value::Null),
+///         ]),
+///       })]))]),
+///     })),
+///   ]),
+/// };
+///
+/// let obj_ref = ObjRef::try_from(&object)?;
+/// assert_eq!(obj_ref.urls(), vec!["tcp://10.0.0.1:8080/4f1c7a3e_2b9d/x0z9ha1k_1.rem"]);
+/// # Ok::<(), nrbf::value::ConversionError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjRef<'i> {
+  /// The URI of the remote object, relative to its channels unless the reference is well-known.
+  pub uri: &'i str,
+  /// The `objrefFlags` member.
+  pub flags: i32,
+  /// Type information of the remote object, if present.
+  pub type_info: Option<TypeInfo<'i>>,
+  /// Channel information of the remote object, if present.
+  pub channel_info: Option<ChannelInfo<'i>>,
+  /// The envoy sinks, if present.
+  pub envoy_info: Option<Value<'i>>,
+}
+
+impl<'i> ObjRef<'i> {
+  /// Flag of references to well-known objects, which are serialized with a `url` member only.
+  pub const FLAG_WELL_KNOWN: i32 = 0x2;
+
+  /// Whether this is a reference to a well-known object.
+  pub fn is_well_known(&self) -> bool {
+    self.flags & Self::FLAG_WELL_KNOWN != 0
+  }
+
+  /// Get the absolute URLs under which the remote object is reachable.
+  ///
+  /// For well-known references, this is the URI itself. Otherwise, the URI is appended to each channel URI.
+  pub fn urls(&self) -> Vec<String> {
+    if self.is_well_known() {
+      return vec![self.uri.to_owned()]
+    }
+
+    let Some(channel_info) = &self.channel_info else { return vec![] };
+
+    channel_info
+      .channel_uris
+      .iter()
+      .map(|channel_uri| format!("{}/{}", channel_uri.trim_end_matches('/'), self.uri.trim_start_matches('/')))
+      .collect()
+  }
+
+  /// Find all object references contained in the given value.
+  pub fn collect(value: &Value<'i>) -> Vec<Self> {
+    let mut obj_refs = vec![];
+    collect_obj_refs(value, &mut obj_refs);
+    obj_refs
+  }
+}
+
+fn collect_obj_refs<'i>(value: &Value<'i>, obj_refs: &mut Vec<ObjRef<'i>>) {
+  match value {
+    Value::Object(object) => match ObjRef::try_from(object) {
+      Ok(obj_ref) => obj_refs.push(obj_ref),
+      Err(_) => object.members.values().for_each(|value| collect_obj_refs(value, obj_refs)),
+    },
+    Value::Array(values) => values.iter().for_each(|value| collect_obj_refs(value, obj_refs)),
+    _ => (),
+  }
+}
+
+impl<'i> TryFrom<&Object<'i>> for ObjRef<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class(OBJ_REF)?;
+
+    if let Some(url) = object.optional_str_member("url")? {
+      return Ok(Self { uri: url, flags: Self::FLAG_WELL_KNOWN, type_info: None, channel_info: None, envoy_info: None })
+    }
+
+    let flags = match object.optional_member("objrefFlags") {
+      Some(Value::Int32(flags)) => *flags,
+      Some(_) => return Err(ConversionError::InvalidMember("objrefFlags")),
+      None => 0,
+    };
+
+    Ok(Self {
+      uri: object.str_member("uri")?,
+      flags,
+      type_info: object.optional_object_member("typeInfo")?.map(TypeInfo::try_from).transpose()?,
+      channel_info: object.optional_object_member("channelInfo")?.map(ChannelInfo::try_from).transpose()?,
+      envoy_info: object.optional_member("envoyInfo").cloned(),
+    })
+  }
+}
+
+/// Type information of a remote object, i.e. a serialized `System.Runtime.Remoting.TypeInfo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeInfo<'i> {
+  /// The assembly-qualified type name of the remote object.
+  pub server_type: &'i str,
+  /// The assembly-qualified names of the base classes.
+  pub server_hierarchy: Vec<&'i str>,
+  /// The assembly-qualified names of the implemented interfaces.
+  pub interfaces_implemented: Vec<&'i str>,
+}
+
+impl<'i> TryFrom<&Object<'i>> for TypeInfo<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    Ok(Self {
+      server_type: object.str_member("serverType")?,
+      server_hierarchy: object.optional_str_array_member("serverHierarchy")?,
+      interfaces_implemented: object.optional_str_array_member("interfacesImplemented")?,
+    })
+  }
+}
+
+/// Channel information of a remote object, i.e. a serialized `System.Runtime.Remoting.ChannelInfo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelInfo<'i> {
+  /// The URIs of all channels found in the channel data.
+  pub channel_uris: Vec<&'i str>,
+  /// The raw channel data.
+  pub channel_data: Vec<Value<'i>>,
+}
+
+impl<'i> TryFrom<&Object<'i>> for ChannelInfo<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    let channel_data = object.optional_array_member("channelData")?;
+
+    let mut channel_uris = vec![];
+    for data in channel_data {
+      if let Value::Object(data_store) = data {
+        channel_uris.extend(data_store.optional_str_array_member("_channelURIs")?);
+      }
+    }
+
+    Ok(Self { channel_uris, channel_data: channel_data.to_vec() })
+  }
+}
+
+/// A remote activation request, i.e. a serialized `System.Runtime.Remoting.Messaging.ConstructionCall`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstructionCall<'i> {
+  /// The URI of the activator, if present.
+  pub uri: Option<&'i str>,
+  /// The method name, usually `.ctor`.
+  pub method_name: &'i str,
+  /// The assembly-qualified name of the type to activate.
+  pub type_name: &'i str,
+  /// The full name of the type to activate, if present.
+  pub activation_type_name: Option<&'i str>,
+  /// The constructor arguments.
+  pub args: Vec<Value<'i>>,
+  /// The activation attributes supplied by the caller.
+  pub call_site_activation_attributes: Vec<Value<'i>>,
+  /// The context properties.
+  pub context_properties: Vec<Value<'i>>,
+  /// The call context, if present.
+  pub call_context: Option<Value<'i>>,
+}
+
+impl<'i> TryFrom<&Object<'i>> for ConstructionCall<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class(CONSTRUCTION_CALL)?;
+
+    Ok(Self {
+      uri: object.optional_str_member("__Uri")?,
+      method_name: object.str_member("__MethodName")?,
+      type_name: object.str_member("__TypeName")?,
+      activation_type_name: object.optional_str_member("__ActivationTypeName")?,
+      args: object.optional_array_member("__Args")?.to_vec(),
+      call_site_activation_attributes: object.optional_array_member("__CallSiteActivationAttributes")?.to_vec(),
+      context_properties: object.optional_array_member("__ContextProperties")?.to_vec(),
+      call_context: object.optional_member("__CallContext").cloned(),
+    })
+  }
+}
+
+/// The response to a remote activation request, i.e. a serialized
+/// `System.Runtime.Remoting.Messaging.ConstructionResponse`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstructionResponse<'i> {
+  /// The URI of the activator, if present.
+  pub uri: Option<&'i str>,
+  /// The method name, if present.
+  pub method_name: Option<&'i str>,
+  /// The assembly-qualified name of the activated type, if present.
+  pub type_name: Option<&'i str>,
+  /// The reference to the activated object, if present.
+  pub return_value: Option<ObjRef<'i>>,
+  /// The output arguments.
+  pub out_args: Vec<Value<'i>>,
+  /// The call context, if present.
+  pub call_context: Option<Value<'i>>,
+  /// The exception thrown during activation, if present.
+  pub fault: Option<Value<'i>>,
+}
+
+impl<'i> TryFrom<&Object<'i>> for ConstructionResponse<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class(CONSTRUCTION_RESPONSE)?;
+
+    Ok(Self {
+      uri: object.optional_str_member("__Uri")?,
+      method_name: object.optional_str_member("__MethodName")?,
+      type_name: object.optional_str_member("__TypeName")?,
+      return_value: object.optional_object_member("__Return")?.map(ObjRef::try_from).transpose()?,
+      out_args: object.optional_array_member("__OutArgs")?.to_vec(),
+      call_context: object.optional_member("__CallContext").cloned(),
+      fault: object.optional_member("__fault").cloned(),
+    })
+  }
+}
+
+/// Strip the assembly name from an assembly-qualified type name.
+///
+/// Commas inside the brackets of generic type arguments are not treated as the assembly separator.
+pub(crate) fn full_type_name(type_name: &str) -> &str {
+  let mut depth = 0usize;
+
+  for (i, c) in type_name.char_indices() {
+    match c {
+      '[' => depth += 1,
+      ']' => depth = depth.saturating_sub(1),
+      ',' if depth == 0 => return type_name[..i].trim(),
+      _ => (),
+    }
+  }
+
+  type_name.trim()
+}
@@ -1,6 +1,11 @@
+use std::time::Duration;
+
+use super::TimeConversionError;
 use crate::data_type;
 
 /// A time span.
+///
+/// With `serde`, it deserializes as its ticks or into a `std::time::Duration`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct TimeSpan(pub(crate) data_type::TimeSpan);
 
@@ -11,3 +16,29 @@ impl TimeSpan {
     self.0.into()
   }
 }
+
+/// Converts non-negative time spans.
+impl TryFrom<TimeSpan> for Duration {
+  type Error = TimeConversionError;
+
+  fn try_from(time_span: TimeSpan) -> Result<Self, Self::Error> {
+    let ticks = u64::try_from(time_span.value()).map_err(|_| TimeConversionError::OutOfRange)?;
+    Ok(Duration::new(ticks / 10_000_000, (ticks % 10_000_000) as u32 * 100))
+  }
+}
+
+#[cfg(feature = "chrono")]
+impl From<TimeSpan> for chrono::TimeDelta {
+  fn from(time_span: TimeSpan) -> Self {
+    let ticks = time_span.value();
+    Self::microseconds(ticks / 10) + Self::nanoseconds(ticks % 10 * 100)
+  }
+}
+
+#[cfg(feature = "time")]
+impl From<TimeSpan> for time::Duration {
+  fn from(time_span: TimeSpan) -> Self {
+    let ticks = time_span.value();
+    Self::new(ticks / 10_000_000, (ticks % 10_000_000) as i32 * 100)
+  }
+}
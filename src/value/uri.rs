@@ -0,0 +1,35 @@
+use std::fmt;
+
+use super::{ConversionError, Object};
+
+/// A URI, i.e. a serialized `System.Uri`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Uri<'i> {
+  /// The URI string.
+  pub uri: &'i str,
+  /// Whether the URI is absolute.
+  pub is_absolute: bool,
+}
+
+impl fmt::Display for Uri<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.uri)
+  }
+}
+
+impl<'i> TryFrom<&Object<'i>> for Uri<'i> {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'i>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Uri")?;
+
+    if let Some(uri) = object.optional_str_member("AbsoluteUri")? {
+      return Ok(Self { uri, is_absolute: true })
+    }
+
+    match object.optional_str_member("RelativeUri")? {
+      Some(uri) => Ok(Self { uri, is_absolute: false }),
+      None => Err(ConversionError::MissingMember("AbsoluteUri")),
+    }
+  }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+use super::{ConversionError, Object, Value};
+
+/// A version number, i.e. a serialized `System.Version`.
+///
+/// Undefined components are `None`, which .NET represents as `-1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Version {
+  /// The major component.
+  pub major: i32,
+  /// The minor component.
+  pub minor: i32,
+  /// The build component, if defined.
+  pub build: Option<i32>,
+  /// The revision component, if defined.
+  pub revision: Option<i32>,
+}
+
+/// Formats the defined components like .NET, e.g. `4.0.30319`.
+impl fmt::Display for Version {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}.{}", self.major, self.minor)?;
+
+    if let Some(build) = self.build {
+      write!(f, ".{}", build)?;
+
+      if let Some(revision) = self.revision {
+        write!(f, ".{}", revision)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl TryFrom<&Object<'_>> for Version {
+  type Error = ConversionError;
+
+  fn try_from(object: &Object<'_>) -> Result<Self, Self::Error> {
+    object.expect_class("System.Version")?;
+
+    let component = |name| match object.member(name)? {
+      Value::Int32(v) => Ok(*v),
+      _ => Err(ConversionError::InvalidMember(name)),
+    };
+
+    Ok(Self {
+      major: component("_Major")?,
+      minor: component("_Minor")?,
+      build: Some(component("_Build")?).filter(|v| *v >= 0),
+      revision: Some(component("_Revision")?).filter(|v| *v >= 0),
+    })
+  }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `List<string>` with the items `"a"` and `"b"` and unused capacity.
+#[rustfmt::skip]
+const TAGS: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    127, "System.Collections.Generic.List`1[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    0x03, 0x00, 0x00, 0x00,
+    6, "_items",
+    5, "_size",
+    8, "_version",
+    6, 0, 0,
+    8, 8,
+    9,
+      0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+  17,
+    0x02, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00,
+    6,
+      0x03, 0x00, 0x00, 0x00,
+      1, "a",
+    6,
+      0x04, 0x00, 0x00, 0x00,
+      1, "b",
+    13,
+      2,
+  11,
+);
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library, members: HashMap::from_iter(members) })
+}
+
+#[test]
+fn numeric_accessors() {
+  assert_eq!(Value::SByte(-1).as_i32(), Some(-1));
+  assert_eq!(Value::UInt16(65535).as_i32(), Some(65535));
+  assert_eq!(Value::Int16(-2).as_i64(), Some(-2));
+  assert_eq!(Value::UInt32(u32::MAX).as_i64(), Some(u32::MAX.into()));
+  assert_eq!(Value::Int64(1).as_i32(), None);
+  assert_eq!(Value::UInt64(u64::MAX).as_i64(), None);
+  assert_eq!(Value::UInt64(u64::MAX).as_u64(), Some(u64::MAX));
+  assert_eq!(Value::Int32(1).as_u64(), None);
+  assert_eq!(Value::Single(0.5).as_f64(), Some(0.5));
+  assert_eq!(Value::Double(0.5).as_i64(), None);
+  assert_eq!(Value::Boolean(true).as_bool(), Some(true));
+  assert_eq!(Value::Bytes(&[1, 2]).as_bytes(), Some(&[1, 2][..]));
+}
+
+#[test]
+fn unboxed() {
+  let boxed = object("System.Int64", None, vec![("m_value", Value::Int64(7))]);
+  assert_eq!(boxed.unboxed(), &Value::Int64(7));
+  assert_eq!(boxed.as_i64(), Some(7));
+  assert_eq!(boxed.as_object().map(|object| object.class), Some("System.Int64"));
+
+  let status = object("Contoso.Status", Some("Contoso"), vec![("value__", Value::Int32(2))]);
+  assert_eq!(status.as_i32(), Some(2));
+
+  let not_boxed = object("Contoso.Wrapper", Some("Contoso"), vec![("m_value", Value::Int32(1))]);
+  assert_eq!(not_boxed.as_i32(), None);
+}
+
+#[test]
+fn index() {
+  let list = object(
+    "System.Collections.Generic.List`1[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    None,
+    vec![
+      ("_items", Value::Array(vec![Value::String("a"), Value::String("b"), Value::Null, Value::Null])),
+      ("_size", Value::Int32(2)),
+      ("_version", Value::Int32(2)),
+    ],
+  );
+  let customer = object("Contoso.Customer", Some("Contoso"), vec![("Name", Value::String("Alice")), ("Tags", list)]);
+
+  let bytes = RemotingMessage::Value(customer).to_bytes();
+  let Ok(RemotingMessage::Value(customer)) = RemotingMessage::parse(&bytes) else { panic!("expected a value") };
+
+  assert_eq!(customer["Name"].as_str(), Some("Alice"));
+  assert_eq!(customer["Tags"].as_array().map(<[_]>::len), Some(2));
+  assert_eq!(customer["Tags"][1].as_str(), Some("b"));
+  assert!(customer["Tags"][2].is_null());
+  assert!(customer["Missing"].is_null());
+  assert!(customer["Name"]["Length"].is_null());
+  assert!(customer[0].is_null());
+
+  let object = customer.as_object().unwrap();
+  assert_eq!(object["Name"], Value::String("Alice"));
+  assert!(object["Missing"].is_null());
+}
+
+#[test]
+fn index_fixture() {
+  let Ok(RemotingMessage::Value(tags)) = RemotingMessage::parse(TAGS) else { panic!("expected a value") };
+
+  assert_eq!(tags.as_array().map(<[_]>::len), Some(2));
+  assert_eq!(tags[1].as_str(), Some("b"));
+  assert!(tags[2].is_null());
+  assert!(tags["_items"][2].is_null());
+  assert_eq!(tags["_size"].as_i32(), Some(2));
+}
+
+#[test]
+fn index_bytes() {
+  let list = object(
+    "System.Collections.Generic.List`1[[System.Byte, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    None,
+    vec![("_items", Value::Bytes(&[7, 8, 9, 0])), ("_size", Value::Int32(3)), ("_version", Value::Int32(3))],
+  );
+  let packet =
+    object("Contoso.Packet", Some("Contoso"), vec![("Data", list), ("Checksum", Value::Bytes(&[0xAB, 0xCD]))]);
+
+  let bytes = RemotingMessage::Value(packet).to_bytes();
+  let Ok(RemotingMessage::Value(packet)) = RemotingMessage::parse(&bytes) else { panic!("expected a value") };
+
+  assert_eq!(packet["Data"].as_bytes(), Some(&[7, 8, 9][..]));
+  assert_eq!(packet["Data"].as_array(), None);
+  assert_eq!(packet["Data"][0], Value::Byte(7));
+  assert_eq!(packet["Data"][2].as_i32(), Some(9));
+  assert!(packet["Data"][3].is_null());
+  assert_eq!(packet["Checksum"][1], Value::Byte(0xCD));
+  assert!(packet["Checksum"][2].is_null());
+}
@@ -26,3 +26,30 @@ fn binary_array_rectangular() {
 
   assert_eq!(RemotingMessage::parse(&input), Ok(output));
 }
+
+#[test]
+fn binary_array_rectangular_bytes() {
+  #[rustfmt::skip]
+  let input = [
+    0,
+      0x01, 0x00, 0x00, 0x00,
+      0xFF, 0xFF, 0xFF, 0xFF,
+      0x01, 0x00, 0x00, 0x00,
+      0x00, 0x00, 0x00, 0x00,
+    7,
+      0x01, 0x00, 0x00, 0x00,
+      2,
+      0x02, 0x00, 0x00, 0x00,
+      0x02, 0x00, 0x00, 0x00,
+      0x03, 0x00, 0x00, 0x00,
+      0,
+      2,
+      1, 2, 3,
+      4, 5, 6,
+    11,
+  ];
+
+  let output = RemotingMessage::Value(Value::Array(vec![Value::Bytes(&[1, 2, 3]), Value::Bytes(&[4, 5, 6])]));
+
+  assert_eq!(RemotingMessage::parse(&input), Ok(output));
+}
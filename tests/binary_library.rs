@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Contoso.Order` preceded by the libraries of both its own class and the class of its member.
+#[rustfmt::skip]
+const INPUT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    13, "Contoso.Model",
+  12,
+    0x03, 0x00, 0x00, 0x00,
+    15, "Contoso.Billing",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    13, "Contoso.Order",
+    0x01, 0x00, 0x00, 0x00,
+    5, "Total",
+    4,
+    13, "Contoso.Money",
+    0x03, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    5,
+      0x04, 0x00, 0x00, 0x00,
+      13, "Contoso.Money",
+      0x01, 0x00, 0x00, 0x00,
+      5, "Units",
+      0,
+      9,
+      0x03, 0x00, 0x00, 0x00,
+      0x2A, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+  11,
+);
+
+#[test]
+fn consecutive_binary_libraries() {
+  let money = Object {
+    class: "Contoso.Money",
+    library: Some("Contoso.Billing"),
+    members: HashMap::from_iter([("Units", Value::Int64(42))]),
+  };
+  let output = RemotingMessage::Value(Value::Object(Object {
+    class: "Contoso.Order",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([("Total", Value::Object(money))]),
+  }));
+
+  assert_eq!(RemotingMessage::parse(INPUT), Ok(output));
+}
+
+#[test]
+fn redefined_binary_library() {
+  #[rustfmt::skip]
+  let input = concat_bytes!(
+    0,
+      0x01, 0x00, 0x00, 0x00,
+      0xFF, 0xFF, 0xFF, 0xFF,
+      0x01, 0x00, 0x00, 0x00,
+      0x00, 0x00, 0x00, 0x00,
+    12,
+      0x02, 0x00, 0x00, 0x00,
+      13, "Contoso.Model",
+    12,
+      0x02, 0x00, 0x00, 0x00,
+      15, "Contoso.Billing",
+    6,
+      0x01, 0x00, 0x00, 0x00,
+      0,
+    11,
+  );
+
+  assert_eq!(RemotingMessage::parse(input).unwrap_err().to_string(), "duplicate library ID");
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Contoso.Customer` with members of each member type, as written by `BinaryFormatter`.
+#[rustfmt::skip]
+const CUSTOMER: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    13, "Contoso.Model",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    16, "Contoso.Customer",
+    0x06, 0x00, 0x00, 0x00,
+    3, "Age",
+    4, "Data",
+    5, "Email",
+    4, "Name",
+    6, "Scores",
+    4, "Tags",
+    0, 7, 2, 1, 7, 5,
+    8,
+    2,
+    8,
+    0x02, 0x00, 0x00, 0x00,
+    0x1E, 0x00, 0x00, 0x00,
+    9,
+      0x03, 0x00, 0x00, 0x00,
+    10,
+    6,
+      0x04, 0x00, 0x00, 0x00,
+      5, "Alice",
+    9,
+      0x05, 0x00, 0x00, 0x00,
+    9,
+      0x06, 0x00, 0x00, 0x00,
+  15,
+    0x03, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    2,
+    0x01, 0x02,
+  15,
+    0x05, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    8,
+    0x01, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+  16,
+    0x06, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00,
+    6,
+      0x07, 0x00, 0x00, 0x00,
+      1, "a",
+  11,
+);
+
+/// A boxed `System.Int32`.
+#[rustfmt::skip]
+const INT32: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    12, "System.Int32",
+    0x01, 0x00, 0x00, 0x00,
+    7, "m_value",
+    0,
+    8,
+    0x2A, 0x00, 0x00, 0x00,
+  11,
+);
+
+#[test]
+fn write_class_with_member_types() {
+  let customer = RemotingMessage::Value(Value::Object(Object {
+    class: "Contoso.Customer",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([
+      ("Age", Value::Int32(30)),
+      ("Data", Value::Bytes(&[1, 2])),
+      ("Email", Value::Null),
+      ("Name", Value::String("Alice")),
+      ("Scores", Value::Array(vec![Value::Int32(1), Value::Int32(2)])),
+      ("Tags", Value::Array(vec![Value::String("a")])),
+    ]),
+  }));
+
+  assert_eq!(customer.to_bytes(), CUSTOMER);
+  assert_eq!(RemotingMessage::parse(CUSTOMER), Ok(customer));
+}
+
+#[test]
+fn write_boxed_primitive() {
+  assert_eq!(RemotingMessage::Value(Value::Int32(42)).to_bytes(), INT32);
+  assert_eq!(
+    RemotingMessage::parse(INT32),
+    Ok(RemotingMessage::Value(Value::Object(Object {
+      class: "System.Int32",
+      library: None,
+      members: HashMap::from_iter([("m_value", Value::Int32(42))]),
+    })))
+  );
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use nrbf::{Options, RemotingMessage, Value, value::Object};
+use serde::Deserialize;
+
+const SHAPES: &str = "Contoso.Shapes, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn object<'i>(class: &'i str, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library: Some(SHAPES), members: HashMap::from_iter(members) })
+}
+
+fn drawing_bytes() -> Vec<u8> {
+  let shapes = Value::Array(vec![
+    object("Contoso.Shapes.Circle", vec![("radius", Value::Double(1.5))]),
+    object("Contoso.Shapes.Rectangle", vec![("width", Value::Int32(2)), ("height", Value::Int32(3))]),
+    object("Contoso.Shapes.Empty", vec![]),
+  ]);
+
+  RemotingMessage::Value(object("Contoso.Drawing", vec![("shapes", shapes)])).to_bytes()
+}
+
+#[test]
+fn externally_tagged() {
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Circle {
+    radius: f64,
+  }
+
+  #[derive(Debug, PartialEq, Deserialize)]
+  enum Shape {
+    Circle(Circle),
+    #[serde(rename = "Contoso.Shapes.Rectangle")]
+    Rectangle {
+      width: i32,
+      height: i32,
+    },
+    Empty,
+  }
+
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Drawing {
+    shapes: Vec<Shape>,
+  }
+
+  assert_eq!(
+    nrbf::from_slice(&drawing_bytes()),
+    Ok(Drawing {
+      shapes: vec![Shape::Circle(Circle { radius: 1.5 }), Shape::Rectangle { width: 2, height: 3 }, Shape::Empty]
+    })
+  );
+}
+
+#[test]
+fn internally_tagged() {
+  #[derive(Debug, PartialEq, Deserialize)]
+  #[serde(tag = "$type")]
+  enum Shape {
+    #[serde(rename = "Contoso.Shapes.Circle")]
+    Circle { radius: f64 },
+    #[serde(rename = "Contoso.Shapes.Rectangle")]
+    Rectangle { width: i32, height: i32 },
+    #[serde(rename = "Contoso.Shapes.Empty")]
+    Empty,
+  }
+
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Drawing {
+    shapes: Vec<Shape>,
+  }
+
+  let bytes = drawing_bytes();
+  let options = Options::new().with_type_tag("$type");
+
+  assert_eq!(
+    nrbf::from_slice_with(&bytes, &options),
+    Ok(Drawing { shapes: vec![Shape::Circle { radius: 1.5 }, Shape::Rectangle { width: 2, height: 3 }, Shape::Empty] })
+  );
+  assert!(nrbf::from_slice::<Drawing>(&bytes).is_err());
+}
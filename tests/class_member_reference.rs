@@ -0,0 +1,59 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Contoso.Order` with a class member referencing an object written after it and a null system
+/// class member.
+#[rustfmt::skip]
+const INPUT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    13, "Contoso.Model",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    13, "Contoso.Order",
+    0x02, 0x00, 0x00, 0x00,
+    8, "Customer",
+    7, "Version",
+    4, 3,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    14, "System.Version",
+    0x02, 0x00, 0x00, 0x00,
+    9,
+      0x03, 0x00, 0x00, 0x00,
+    10,
+  5,
+    0x03, 0x00, 0x00, 0x00,
+    16, "Contoso.Customer",
+    0x01, 0x00, 0x00, 0x00,
+    4, "Name",
+    1,
+    0x02, 0x00, 0x00, 0x00,
+    6,
+      0x04, 0x00, 0x00, 0x00,
+      5, "Alice",
+  11,
+);
+
+#[test]
+fn class_member_reference() {
+  let customer = Object {
+    class: "Contoso.Customer",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([("Name", Value::String("Alice"))]),
+  };
+  let output = RemotingMessage::Value(Value::Object(Object {
+    class: "Contoso.Order",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([("Customer", Value::Object(customer)), ("Version", Value::Null)]),
+  }));
+
+  assert_eq!(RemotingMessage::parse(INPUT), Ok(output));
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Contoso.Pair` of two `Contoso.Customer` structs, the second one reusing the metadata of the
+/// first one with a `ClassWithId` record.
+#[rustfmt::skip]
+const INPUT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    13, "Contoso.Model",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    12, "Contoso.Pair",
+    0x02, 0x00, 0x00, 0x00,
+    5, "First",
+    6, "Second",
+    4, 4,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    5,
+      0x03, 0x00, 0x00, 0x00,
+      16, "Contoso.Customer",
+      0x01, 0x00, 0x00, 0x00,
+      4, "Name",
+      1,
+      0x02, 0x00, 0x00, 0x00,
+      6,
+        0x04, 0x00, 0x00, 0x00,
+        5, "Alice",
+    1,
+      0x05, 0x00, 0x00, 0x00,
+      0x03, 0x00, 0x00, 0x00,
+      6,
+        0x06, 0x00, 0x00, 0x00,
+        3, "Bob",
+  11,
+);
+
+fn customer(name: &str) -> Value<'_> {
+  Value::Object(Object {
+    class: "Contoso.Customer",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([("Name", Value::String(name))]),
+  })
+}
+
+#[test]
+fn class_with_id() {
+  let output = RemotingMessage::Value(Value::Object(Object {
+    class: "Contoso.Pair",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([("First", customer("Alice")), ("Second", customer("Bob"))]),
+  }));
+
+  assert_eq!(RemotingMessage::parse(INPUT), Ok(output));
+}
+
+#[test]
+fn class_with_id_round_trip() {
+  let message = RemotingMessage::parse(INPUT).unwrap();
+
+  assert_eq!(message.to_bytes(), INPUT);
+}
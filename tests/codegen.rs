@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, codegen::Generator, value::Object};
+
+const MODEL: &str = "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+/// A `Contoso.Customer` with a null `Email` declared as a string.
+#[rustfmt::skip]
+const CUSTOMER: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    68, "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    5, "Email",
+    4, "Name",
+    1, 1,
+    0x02, 0x00, 0x00, 0x00,
+    10,
+    6,
+      0x03, 0x00, 0x00, 0x00,
+      5, "Carol",
+  11,
+);
+
+/// A `Contoso.Account` with null members declared as a class, an object and an `int[]`.
+#[rustfmt::skip]
+const ACCOUNT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    68, "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    15, "Contoso.Account",
+    0x04, 0x00, 0x00, 0x00,
+    7, "Manager",
+    4, "Note",
+    5, "Owner",
+    6, "Scores",
+    4, 2, 4, 7,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    8,
+    0x02, 0x00, 0x00, 0x00,
+    10,
+    10,
+    5,
+      0x04, 0x00, 0x00, 0x00,
+      16, "Contoso.Customer",
+      0x02, 0x00, 0x00, 0x00,
+      5, "Email",
+      4, "Name",
+      1, 1,
+      0x02, 0x00, 0x00, 0x00,
+      10,
+      6,
+        0x05, 0x00, 0x00, 0x00,
+        5, "Carol",
+    10,
+  11,
+);
+
+fn object<'i>(class: &'i str, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library: Some(MODEL), members: HashMap::from_iter(members) })
+}
+
+fn order<'i>(id: i32, customer: Value<'i>, shapes: Vec<Value<'i>>) -> Value<'i> {
+  object(
+    "Contoso.Order",
+    vec![
+      ("<Id>k__BackingField", Value::Int32(id)),
+      ("<Customer>k__BackingField", customer),
+      ("shapes", Value::Array(shapes)),
+      ("type", Value::String("retail")),
+    ],
+  )
+}
+
+#[test]
+fn generate() {
+  let circle = object("Contoso.Shapes.Circle", vec![("radius", Value::Double(1.5))]);
+  let square = object("Contoso.Shapes.Square", vec![("side", Value::Double(2.0))]);
+  let alice = object("Contoso.Customer", vec![("Name", Value::String("Alice")), ("Email", Value::Null)]);
+  let bob = object("Contoso.Customer", vec![("Name", Value::String("Bob"))]);
+
+  let mut generator = Generator::new();
+  generator.add_sample(&RemotingMessage::Value(order(1, alice, vec![circle])).to_bytes()).unwrap();
+  generator.add_sample(&RemotingMessage::Value(order(2, bob, vec![square])).to_bytes()).unwrap();
+  generator.add_value(&order(3, Value::Null, vec![]));
+  generator.add_sample(CUSTOMER).unwrap();
+
+  assert_eq!(
+    generator.generate(),
+    r#"use serde::Deserialize;
+
+/// `Contoso.Customer`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Customer {
+  #[serde(rename = "Email", default)]
+  pub email: Option<String>,
+  #[serde(rename = "Name")]
+  pub name: String,
+}
+
+/// `Contoso.Order`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Order {
+  #[serde(rename = "<Customer>k__BackingField")]
+  pub customer: Option<Customer>,
+  #[serde(rename = "<Id>k__BackingField")]
+  pub id: i32,
+  pub shapes: Vec<OrderShapesItem>,
+  pub r#type: String,
+}
+
+/// `Contoso.Shapes.Circle`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Circle {
+  pub radius: f64,
+}
+
+/// `Contoso.Shapes.Square`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Square {
+  pub side: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub enum OrderShapesItem {
+  #[serde(rename = "Contoso.Shapes.Circle")]
+  Circle(Circle),
+  #[serde(rename = "Contoso.Shapes.Square")]
+  Square(Square),
+}
+"#
+  );
+}
+
+#[test]
+fn generate_collections() {
+  let list = Object {
+    class: "System.Collections.Generic.List`1[[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    library: None,
+    members: HashMap::from_iter([
+      ("_items", Value::Array(vec![Value::Int32(1), Value::Int32(2), Value::Int32(0), Value::Int32(0)])),
+      ("_size", Value::Int32(2)),
+      ("_version", Value::Int32(2)),
+    ]),
+  };
+  let table = Object {
+    class: "System.Collections.Hashtable",
+    library: None,
+    members: HashMap::from_iter([
+      ("Keys", Value::Array(vec![Value::String("a")])),
+      ("Values", Value::Array(vec![Value::Int64(1)])),
+    ]),
+  };
+  let nullable = Object {
+    class: "System.Nullable`1[[System.Int16, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    library: None,
+    members: HashMap::from_iter([("hasValue", Value::Boolean(false)), ("value", Value::Int16(0))]),
+  };
+  // The queue wraps around, the stale string between its tail and head is not an item.
+  let queue = Object {
+    class: "System.Collections.Queue",
+    library: None,
+    members: HashMap::from_iter([
+      ("_array", Value::Array(vec![Value::Int32(3), Value::String("stale"), Value::Int32(1), Value::Int32(2)])),
+      ("_head", Value::Int32(2)),
+      ("_tail", Value::Int32(1)),
+      ("_size", Value::Int32(3)),
+    ]),
+  };
+  let stack = Object {
+    class: "System.Collections.Stack",
+    library: None,
+    members: HashMap::from_iter([
+      ("_array", Value::Array(vec![Value::Double(1.0), Value::String("stale")])),
+      ("_size", Value::Int32(1)),
+    ]),
+  };
+  let bytes = Object {
+    class: "System.Collections.Generic.List`1[[System.Byte, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    library: None,
+    members: HashMap::from_iter([
+      ("_items", Value::Bytes(&[1, 2, 0, 0])),
+      ("_size", Value::Int32(2)),
+      ("_version", Value::Int32(2)),
+    ]),
+  };
+
+  let mut generator = Generator::new();
+  generator.add_value(&object(
+    "Contoso.Stats",
+    vec![
+      ("counts", Value::Object(list)),
+      ("totals", Value::Object(table)),
+      ("limit", Value::Object(nullable)),
+      ("data", Value::Bytes(&[1, 2])),
+      ("queue", Value::Object(queue)),
+      ("stack", Value::Object(stack)),
+      ("bytes", Value::Object(bytes)),
+    ],
+  ));
+
+  assert_eq!(
+    generator.generate(),
+    r#"use std::collections::HashMap;
+
+use serde::Deserialize;
+
+/// `Contoso.Stats`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Stats {
+  pub bytes: Vec<u8>,
+  pub counts: Vec<i32>,
+  pub data: Vec<u8>,
+  pub limit: Option<i16>,
+  pub queue: Vec<i32>,
+  pub stack: Vec<f64>,
+  pub totals: HashMap<String, i64>,
+}
+"#
+  );
+}
+
+#[test]
+fn generate_declared_types() {
+  let mut generator = Generator::new();
+  generator.add_sample(ACCOUNT).unwrap();
+
+  assert_eq!(
+    generator.generate(),
+    r#"use serde::Deserialize;
+
+/// `Contoso.Account`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Account {
+  #[serde(rename = "Manager")]
+  pub manager: Option<Customer>,
+  #[serde(rename = "Note")]
+  pub note: Option<serde::de::IgnoredAny>,
+  #[serde(rename = "Owner")]
+  pub owner: Customer,
+  #[serde(rename = "Scores")]
+  pub scores: Option<Vec<i32>>,
+}
+
+/// `Contoso.Customer`
+///
+/// Library: `Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Customer {
+  #[serde(rename = "Email")]
+  pub email: Option<String>,
+  #[serde(rename = "Name")]
+  pub name: String,
+}
+"#
+  );
+}
@@ -0,0 +1,301 @@
+#![cfg(feature = "serde")]
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Stack<int>` with the items `1` and `2` and unused capacity.
+#[rustfmt::skip]
+const GENERIC_STACK: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    127, "System.Collections.Generic.Stack`1[[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    0x03, 0x00, 0x00, 0x00,
+    6, "_array",
+    5, "_size",
+    8, "_version",
+    7, 0, 0,
+    8,
+    8,
+    8,
+    9,
+      0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+  15,
+    0x02, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00,
+    8,
+    0x01, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  11,
+);
+
+fn object<'i>(class: &'i str, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library: None, members: HashMap::from_iter(members) })
+}
+
+fn strings<'i>(items: &[Option<&'i str>]) -> Value<'i> {
+  Value::Array(items.iter().map(|item| item.map_or(Value::Null, Value::String)).collect())
+}
+
+#[test]
+fn hashtable() {
+  let hashtable = object(
+    "System.Collections.Hashtable",
+    vec![
+      ("LoadFactor", Value::Single(0.72)),
+      ("Version", Value::Int32(2)),
+      ("Comparer", Value::Null),
+      ("HashCodeProvider", Value::Null),
+      ("HashSize", Value::Int32(3)),
+      ("Keys", strings(&[Some("one"), Some("two")])),
+      ("Values", Value::Array(vec![Value::Int32(1), Value::Int32(2)])),
+    ],
+  );
+  let bytes = RemotingMessage::Value(hashtable).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(BTreeMap::from_iter([("one", 1), ("two", 2)])));
+}
+
+#[test]
+fn array_list() {
+  let array_list = object(
+    "System.Collections.ArrayList",
+    vec![
+      ("_items", strings(&[Some("a"), Some("b"), Some("c"), None])),
+      ("_size", Value::Int32(3)),
+      ("_version", Value::Int32(3)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(array_list).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(vec!["a", "b", "c"]));
+}
+
+#[test]
+fn queue() {
+  // Enqueued "a" to "e", then dequeued "a" and "b", then enqueued "f" and "g", wrapping around.
+  let queue = object(
+    "System.Collections.Queue",
+    vec![
+      ("_array", strings(&[Some("f"), Some("g"), None, Some("c"), Some("d"), Some("e")])),
+      ("_head", Value::Int32(3)),
+      ("_tail", Value::Int32(2)),
+      ("_size", Value::Int32(5)),
+      ("_growFactor", Value::Int32(200)),
+      ("_version", Value::Int32(7)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(queue).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(vec!["c", "d", "e", "f", "g"]));
+}
+
+#[test]
+fn queue_invalid() {
+  let queue = object(
+    "System.Collections.Queue",
+    vec![
+      ("_array", strings(&[Some("a"), None])),
+      ("_head", Value::Int32(0)),
+      ("_tail", Value::Int32(1)),
+      ("_size", Value::Int32(3)),
+      ("_growFactor", Value::Int32(200)),
+      ("_version", Value::Int32(1)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(queue).to_bytes();
+
+  assert!(nrbf::from_slice::<Vec<&str>>(&bytes).is_err());
+}
+
+#[test]
+fn stack() {
+  let stack = object(
+    "System.Collections.Stack",
+    vec![
+      ("_array", Value::Array(vec![Value::Int32(1), Value::Int32(2), Value::Int32(3), Value::Null])),
+      ("_size", Value::Int32(3)),
+      ("_version", Value::Int32(3)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(stack).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn hash_set() {
+  let hash_set = object(
+    "System.Collections.Generic.HashSet`1[[System.Int32, mscorlib]]",
+    vec![
+      ("Version", Value::Int32(3)),
+      ("Comparer", Value::Null),
+      ("Capacity", Value::Int32(3)),
+      ("Elements", Value::Array(vec![Value::Int32(3), Value::Int32(1), Value::Int32(2)])),
+    ],
+  );
+  let bytes = RemotingMessage::Value(hash_set).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(HashSet::from([1, 2, 3])));
+}
+
+#[test]
+fn byte_collections() {
+  let hash_set = object(
+    "System.Collections.Generic.HashSet`1[[System.Byte, mscorlib]]",
+    vec![
+      ("Version", Value::Int32(2)),
+      ("Comparer", Value::Null),
+      ("Capacity", Value::Int32(2)),
+      ("Elements", Value::Bytes(&[2, 1])),
+    ],
+  );
+  let bytes = RemotingMessage::Value(hash_set).to_bytes();
+  assert_eq!(nrbf::from_slice(&bytes), Ok(BTreeSet::from([1u8, 2])));
+
+  let queue = object(
+    "System.Collections.Generic.Queue`1[[System.Byte, mscorlib]]",
+    vec![
+      ("_array", Value::Bytes(&[4, 0, 2, 3])),
+      ("_head", Value::Int32(2)),
+      ("_tail", Value::Int32(1)),
+      ("_size", Value::Int32(3)),
+      ("_version", Value::Int32(5)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(queue).to_bytes();
+  assert_eq!(nrbf::from_slice(&bytes), Ok(VecDeque::from([2u8, 3, 4])));
+}
+
+#[test]
+fn hash_set_empty() {
+  let hash_set = object(
+    "System.Collections.Generic.HashSet`1[[System.Int32, mscorlib]]",
+    vec![("Version", Value::Int32(0)), ("Comparer", Value::Null), ("Capacity", Value::Int32(0))],
+  );
+  let bytes = RemotingMessage::Value(hash_set).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(HashSet::<i32>::new()));
+}
+
+#[test]
+fn sorted_set() {
+  let sorted_set = object(
+    "System.Collections.Generic.SortedSet`1[[System.String, mscorlib]]",
+    vec![
+      ("Count", Value::Int32(2)),
+      ("Comparer", Value::Null),
+      ("Version", Value::Int32(2)),
+      ("Items", strings(&[Some("a"), Some("b")])),
+    ],
+  );
+  let bytes = RemotingMessage::Value(sorted_set).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(BTreeSet::from(["a", "b"])));
+}
+
+#[test]
+fn linked_list() {
+  let linked_list = object(
+    "System.Collections.Generic.LinkedList`1[[System.String, mscorlib]]",
+    vec![
+      ("Version", Value::Int32(3)),
+      ("Count", Value::Int32(3)),
+      ("Data", strings(&[Some("a"), Some("b"), Some("c")])),
+    ],
+  );
+  let bytes = RemotingMessage::Value(linked_list).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(vec!["a", "b", "c"]));
+}
+
+#[test]
+fn generic_queue() {
+  let queue = object(
+    "System.Collections.Generic.Queue`1[[System.Int32, mscorlib]]",
+    vec![
+      ("_array", Value::Array(vec![Value::Int32(4), Value::Int32(0), Value::Int32(2), Value::Int32(3)])),
+      ("_head", Value::Int32(2)),
+      ("_tail", Value::Int32(1)),
+      ("_size", Value::Int32(3)),
+      ("_version", Value::Int32(6)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(queue).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(VecDeque::from([2, 3, 4])));
+}
+
+#[test]
+fn generic_stack() {
+  let stack = object(
+    "System.Collections.Generic.Stack`1[[System.Int32, mscorlib]]",
+    vec![
+      ("_array", Value::Array(vec![Value::Int32(1), Value::Int32(2), Value::Int32(0), Value::Int32(0)])),
+      ("_size", Value::Int32(2)),
+      ("_version", Value::Int32(2)),
+    ],
+  );
+  let bytes = RemotingMessage::Value(stack).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(vec![1, 2]));
+}
+
+#[test]
+fn tuple() {
+  let tuple = object(
+    "System.Tuple`2[[System.String],[System.Int32]]",
+    vec![("m_Item1", Value::String("one")), ("m_Item2", Value::Int32(1))],
+  );
+  let bytes = RemotingMessage::Value(tuple).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok((String::from("one"), 1)));
+}
+
+#[test]
+fn tuple_rest() {
+  let rest = object(
+    "System.Tuple`2[[System.Int32],[System.Int32]]",
+    vec![("m_Item1", Value::Int32(8)), ("m_Item2", Value::Int32(9))],
+  );
+  let mut members: Vec<_> = ["m_Item1", "m_Item2", "m_Item3", "m_Item4", "m_Item5", "m_Item6", "m_Item7"]
+    .into_iter()
+    .zip((1..=7).map(Value::Int32))
+    .collect();
+  members.push(("m_Rest", rest));
+  let bytes = RemotingMessage::Value(object("System.Tuple`8", members)).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok((1, 2, 3, 4, 5, 6, 7, 8, 9)));
+}
+
+#[test]
+fn value_tuple_and_key_value_pair() {
+  let value_tuple = object(
+    "System.ValueTuple`2[[System.String],[System.Boolean]]",
+    vec![("Item1", Value::String("yes")), ("Item2", Value::Boolean(true))],
+  );
+  let bytes = RemotingMessage::Value(value_tuple).to_bytes();
+  assert_eq!(nrbf::from_slice(&bytes), Ok(("yes", true)));
+
+  let pair = object(
+    "System.Collections.Generic.KeyValuePair`2[[System.String],[System.Int32]]",
+    vec![("key", Value::String("two")), ("value", Value::Int32(2))],
+  );
+  let bytes = RemotingMessage::Value(pair).to_bytes();
+  assert_eq!(nrbf::from_slice(&bytes), Ok(("two", 2)));
+}
+
+#[test]
+fn generic_stack_fixture() {
+  assert_eq!(nrbf::from_slice(GENERIC_STACK), Ok(vec![1, 2]));
+}
@@ -0,0 +1,142 @@
+use nrbf::{
+  compat::{Change, compare},
+  schema::{ClassMetadata, Library, MemberMetadata, MemberType, PrimitiveType, Schema},
+};
+
+fn class<'i>(
+  object_id: u32,
+  name: &'i str,
+  library_id: u32,
+  members: Vec<(&'i str, MemberType<'i>)>,
+) -> ClassMetadata<'i> {
+  ClassMetadata {
+    object_id,
+    name,
+    library_id: Some(library_id),
+    members: members.into_iter().map(|(name, ty)| MemberMetadata { name, member_type: Some(ty) }).collect(),
+    reused_by: vec![],
+  }
+}
+
+#[test]
+fn compare_member_types() {
+  let old = Schema {
+    libraries: vec![Library { id: 2, name: "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null" }],
+    classes: vec![
+      class(
+        1,
+        "Contoso.Order",
+        2,
+        vec![
+          ("id", MemberType::Primitive(PrimitiveType::Int32)),
+          ("customer", MemberType::Class { name: "Contoso.Customer", library_id: 2 }),
+        ],
+      ),
+      class(3, "Contoso.Customer", 2, vec![("name", MemberType::String)]),
+    ],
+    arrays: vec![],
+  };
+  let new = Schema {
+    libraries: vec![Library { id: 5, name: "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null" }],
+    classes: vec![
+      class(
+        1,
+        "Contoso.Order",
+        5,
+        vec![
+          ("id", MemberType::Primitive(PrimitiveType::Int64)),
+          ("customer", MemberType::Class { name: "Contoso.Customer", library_id: 5 }),
+        ],
+      ),
+      class(3, "Contoso.Customer", 5, vec![("name", MemberType::String)]),
+    ],
+    arrays: vec![],
+  };
+
+  assert_eq!(
+    compare(&old, &new),
+    vec![Change::MemberTypeChanged {
+      class: "Contoso.Order",
+      member: "id",
+      old_type: MemberType::Primitive(PrimitiveType::Int32),
+      new_type: MemberType::Primitive(PrimitiveType::Int64),
+    }]
+  );
+  assert!(compare(&old, &old).is_empty());
+}
+
+#[test]
+fn compare_renamed_classes() {
+  let old = Schema {
+    libraries: vec![Library { id: 1, name: "Contoso.Model, Version=1.0.0.0" }],
+    classes: vec![
+      class(2, "Contoso.Order", 1, vec![("id", MemberType::String), ("note", MemberType::String)]),
+      class(3, "Contoso.Address", 1, vec![("street", MemberType::String), ("city", MemberType::String)]),
+      class(5, "Contoso.Customer", 1, vec![("name", MemberType::String)]),
+    ],
+    arrays: vec![],
+  };
+  let new = Schema {
+    libraries: vec![
+      Library { id: 1, name: "Contoso.Orders, Version=2.0.0.0" },
+      Library { id: 4, name: "Contoso.Model, Version=1.1.0.0" },
+    ],
+    classes: vec![
+      class(2, "Contoso.Orders.Order", 1, vec![("note", MemberType::String), ("id", MemberType::String)]),
+      // Same members but a different simple name.
+      class(3, "Contoso.Location", 4, vec![("city", MemberType::String), ("street", MemberType::String)]),
+      // Same simple name but different members.
+      class(5, "Contoso.Orders.Customer", 1, vec![("name", MemberType::String), ("email", MemberType::String)]),
+    ],
+    arrays: vec![],
+  };
+
+  let changes = compare(&old, &new);
+  assert_eq!(
+    changes,
+    vec![
+      Change::AssemblyVersionChanged { assembly: "Contoso.Model", old_version: "1.0.0.0", new_version: "1.1.0.0" },
+      Change::ClassRenamed {
+        old_class: "Contoso.Order",
+        old_assembly: "Contoso.Model",
+        new_class: "Contoso.Orders.Order",
+        new_assembly: "Contoso.Orders",
+      },
+      Change::ClassRemoved { class: "Contoso.Address", assembly: "Contoso.Model" },
+      Change::ClassRemoved { class: "Contoso.Customer", assembly: "Contoso.Model" },
+      Change::ClassAdded { class: "Contoso.Location", assembly: "Contoso.Model" },
+      Change::ClassAdded { class: "Contoso.Orders.Customer", assembly: "Contoso.Orders" },
+    ]
+  );
+  assert_eq!(changes.iter().map(Change::is_breaking).collect::<Vec<_>>(), vec![false, true, true, true, false, false]);
+  assert_eq!(
+    changes[1].to_string(),
+    "class Contoso.Order in Contoso.Model renamed to Contoso.Orders.Order in Contoso.Orders"
+  );
+  assert_eq!(changes[2].to_string(), "class Contoso.Address in Contoso.Model removed");
+  assert_eq!(changes[4].to_string(), "class Contoso.Location in Contoso.Model added");
+}
+
+#[test]
+fn compare_generic_classes() {
+  let list = |version| {
+    format!(
+      "System.Collections.Generic.List`1[[Contoso.Order, Contoso.Model, Version={}, Culture=neutral, PublicKeyToken=null]]",
+      version
+    )
+  };
+  let (old_list, new_list) = (list("1.0.0.0"), list("2.0.0.0"));
+
+  let system_class = |name| ClassMetadata {
+    object_id: 1,
+    name,
+    library_id: None,
+    members: vec![MemberMetadata { name: "_size", member_type: Some(MemberType::Primitive(PrimitiveType::Int32)) }],
+    reused_by: vec![],
+  };
+
+  let old = Schema { classes: vec![system_class(&old_list)], ..Default::default() };
+  let new = Schema { classes: vec![system_class(&new_list)], ..Default::default() };
+
+  assert!(compare(&old, &new).is_empty());
+}
@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  dataset::{DataColumn, DataRowState, DataSet, DataSetError, DataTable, Field},
+  value::Object,
+};
+
+/// A `DataSet` serialized with `SerializationFormat.Xml`.
+#[rustfmt::skip]
+const XML_DATA_SET: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    78, "System.Data, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    19, "System.Data.DataSet",
+    0x02, 0x00, 0x00, 0x00,
+    9, "XmlSchema",
+    11, "XmlDiffGram",
+    1, 1,
+    0x02, 0x00, 0x00, 0x00,
+    6,
+      0x03, 0x00, 0x00, 0x00,
+      0xFB, 0x09, XML_SCHEMA,
+    6,
+      0x04, 0x00, 0x00, 0x00,
+      0xBD, 0x08, XML_DIFFGRAM,
+  11,
+);
+
+const SYSTEM_DATA: &str = "System.Data, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089";
+
+const XML_SCHEMA: &str = r#"<?xml version="1.0" encoding="utf-16"?>
+<xs:schema id="Shop" xmlns="" xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:msdata="urn:schemas-microsoft-com:xml-msdata">
+  <xs:element name="Shop" msdata:IsDataSet="true" msdata:MainDataTable="Order_x0020_Items" msdata:UseCurrentLocale="true">
+    <xs:complexType>
+      <xs:choice minOccurs="0" maxOccurs="unbounded">
+        <xs:element name="Order_x0020_Items">
+          <xs:complexType>
+            <xs:sequence>
+              <xs:element name="Id" type="xs:int" />
+              <xs:element name="Name" minOccurs="0">
+                <xs:simpleType>
+                  <xs:restriction base="xs:string">
+                    <xs:maxLength value="50" />
+                  </xs:restriction>
+                </xs:simpleType>
+              </xs:element>
+              <xs:element name="Price" type="xs:decimal" minOccurs="0" />
+              <xs:element name="Key" msdata:DataType="System.Guid, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089" type="xs:string" minOccurs="0" />
+              <xs:element name="Data" type="xs:base64Binary" minOccurs="0" />
+            </xs:sequence>
+          </xs:complexType>
+        </xs:element>
+      </xs:choice>
+    </xs:complexType>
+  </xs:element>
+</xs:schema>"#;
+
+const XML_DIFFGRAM: &str = r#"<diffgr:diffgram xmlns:msdata="urn:schemas-microsoft-com:xml-msdata" xmlns:diffgr="urn:schemas-microsoft-com:xml-diffgram-v1">
+  <Shop>
+    <Order_x0020_Items diffgr:id="Order Items1" msdata:rowOrder="0">
+      <Id>1</Id>
+      <Name>Tea &amp; Biscuits</Name>
+      <Price>4.50</Price>
+      <Key>12345678-1234-5678-1234-56789abcdef0</Key>
+      <Data>AQID</Data>
+    </Order_x0020_Items>
+    <Order_x0020_Items diffgr:id="Order Items2" msdata:rowOrder="1" diffgr:hasChanges="modified">
+      <Id>2</Id>
+      <Name>Coffee</Name>
+      <Price>3.20</Price>
+    </Order_x0020_Items>
+    <Order_x0020_Items diffgr:id="Order Items4" msdata:rowOrder="3" diffgr:hasChanges="inserted">
+      <Id>4</Id>
+    </Order_x0020_Items>
+  </Shop>
+  <diffgr:before>
+    <Order_x0020_Items diffgr:id="Order Items2" msdata:rowOrder="1">
+      <Id>2</Id>
+      <Name>Coffee</Name>
+      <Price>2.90</Price>
+    </Order_x0020_Items>
+    <Order_x0020_Items diffgr:id="Order Items3" msdata:rowOrder="2">
+      <Id>3</Id>
+      <Name>Juice</Name>
+    </Order_x0020_Items>
+  </diffgr:before>
+</diffgr:diffgram>"#;
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Object<'i> {
+  Object { class, library, members: HashMap::from_iter(members) }
+}
+
+fn bit_array(bits: &[bool]) -> Value<'static> {
+  let mut array = vec![0i32; bits.len().div_ceil(32)];
+  for (i, _) in bits.iter().enumerate().filter(|(_, bit)| **bit) {
+    array[i / 32] |= 1 << (i % 32);
+  }
+
+  Value::Object(object(
+    "System.Collections.BitArray",
+    None,
+    vec![
+      ("m_array", Value::Array(array.into_iter().map(Value::Int32).collect())),
+      ("m_length", Value::Int32(bits.len() as i32)),
+      ("_version", Value::Int32(0)),
+    ],
+  ))
+}
+
+fn array_list(items: Vec<Value<'static>>) -> Value<'static> {
+  Value::Object(object(
+    "System.Collections.ArrayList",
+    None,
+    vec![("_size", Value::Int32(items.len() as i32)), ("_items", Value::Array(items)), ("_version", Value::Int32(0))],
+  ))
+}
+
+/// Members written by `DataTable.SerializeTableSchema`.
+fn table_schema() -> Vec<(&'static str, Value<'static>)> {
+  vec![
+    ("DataTable.TableName", Value::String("Customers")),
+    ("DataTable.Columns.Count", Value::Int32(2)),
+    ("DataTable.DataColumn_0.ColumnName", Value::String("Id")),
+    ("DataTable.DataColumn_0.AllowDBNull", Value::Boolean(false)),
+    (
+      "DataTable.DataColumn_0.DataType",
+      Value::Object(object(
+        "System.UnitySerializationHolder",
+        None,
+        vec![
+          ("Data", Value::String("System.Int32")),
+          ("UnityType", Value::Int32(4)),
+          (
+            "AssemblyName",
+            Value::String("mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089"),
+          ),
+        ],
+      )),
+    ),
+    ("DataTable.DataColumn_1.ColumnName", Value::String("Name")),
+    ("DataTable.DataColumn_1.AllowDBNull", Value::Boolean(true)),
+    (
+      "DataTable.DataColumn_1.DataType_AssemblyQualifiedName",
+      Value::String("System.String, System.Private.CoreLib, Version=8.0.0.0, Culture=neutral"),
+    ),
+  ]
+}
+
+/// Members written by `DataTable.SerializeTableData` for an unchanged, an added, a modified and a
+/// deleted row.
+fn table_data() -> Vec<(&'static str, Value<'static>)> {
+  #[rustfmt::skip]
+  let row_states = [
+    false, false, false,
+    false, true, false,
+    true, false, false,
+    true, true, false,
+  ];
+
+  vec![
+    ("DataTable_0.Rows.Count", Value::Int32(4)),
+    ("DataTable_0.Records.Count", Value::Int32(5)),
+    ("DataTable_0.RowStates", bit_array(&row_states)),
+    (
+      "DataTable_0.Records",
+      array_list(vec![
+        Value::Array([1, 2, 3, 3, 4].into_iter().map(Value::Int32).collect()),
+        Value::Array(vec![
+          Value::String("Alice"),
+          Value::Null,
+          Value::String("Bob"),
+          Value::String("Robert"),
+          Value::String("Carol"),
+        ]),
+      ]),
+    ),
+    ("DataTable_0.NullBits", array_list(vec![bit_array(&[false; 5]), bit_array(&[false, true, false, false, false])])),
+  ]
+}
+
+fn assert_customers(table: &DataTable) {
+  assert_eq!(table.name, "Customers");
+  assert_eq!(
+    table.columns,
+    [
+      DataColumn { name: "Id".into(), data_type: "System.Int32".into(), allow_null: false },
+      DataColumn { name: "Name".into(), data_type: "System.String".into(), allow_null: true },
+    ]
+  );
+  assert_eq!(table.column_index("Name"), Some(1));
+
+  let states = table.rows.iter().map(|row| row.state).collect::<Vec<_>>();
+  assert_eq!(states, [DataRowState::Unchanged, DataRowState::Added, DataRowState::Modified, DataRowState::Deleted]);
+
+  assert_eq!(table.rows[0].values(), [Field::Int32(1), Field::String("Alice".into())]);
+  assert_eq!(table.rows[1].values(), [Field::Int32(2), Field::Null]);
+  assert_eq!(table.rows[1].original, None);
+  assert_eq!(table.rows[2].values(), [Field::Int32(3), Field::String("Robert".into())]);
+  assert_eq!(table.rows[2].original, Some(vec![Field::Int32(3), Field::String("Bob".into())]));
+  assert_eq!(table.rows[3].current, None);
+  assert_eq!(table.rows[3].values(), [Field::Int32(4), Field::String("Carol".into())]);
+}
+
+#[test]
+fn binary_data_set() {
+  let table = object("System.Data.DataTable", Some(SYSTEM_DATA), table_schema());
+  let mut table_bytes = RemotingMessage::Value(Value::Object(table)).to_bytes();
+  // `MemoryStream.GetBuffer` returns the whole buffer, including unused capacity.
+  table_bytes.resize(table_bytes.len().next_power_of_two(), 0);
+
+  let mut members = vec![
+    (
+      "DataSet.RemotingFormat",
+      Value::Object(object("System.Data.SerializationFormat", Some(SYSTEM_DATA), vec![("value__", Value::Int32(1))])),
+    ),
+    ("DataSet.DataSetName", Value::String("Shop")),
+    ("DataSet.Tables.Count", Value::Int32(1)),
+    ("DataSet.Tables_0", Value::Bytes(&table_bytes)),
+  ];
+  members.extend(table_data());
+  let data_set = object("System.Data.DataSet", Some(SYSTEM_DATA), members);
+
+  let data_set = DataSet::try_from(&data_set).unwrap();
+  assert_eq!(data_set.name, "Shop");
+  assert_eq!(data_set.tables.len(), 1);
+  assert_customers(data_set.table("Customers").unwrap());
+}
+
+#[test]
+fn binary_data_table() {
+  let mut members = table_schema();
+  members.extend(table_data());
+  let table = object("System.Data.DataTable", Some(SYSTEM_DATA), members);
+
+  assert_customers(&DataTable::try_from(&table).unwrap());
+}
+
+#[test]
+fn xml_data_set() {
+  let data_set = object(
+    "System.Data.DataSet",
+    Some(SYSTEM_DATA),
+    vec![("XmlSchema", Value::String(XML_SCHEMA)), ("XmlDiffGram", Value::String(XML_DIFFGRAM))],
+  );
+
+  let data_set = DataSet::try_from(&data_set).unwrap();
+  assert_eq!(data_set.name, "Shop");
+
+  let table = data_set.table("Order Items").unwrap();
+  let data_types = table.columns.iter().map(|column| column.data_type.as_str()).collect::<Vec<_>>();
+  assert_eq!(data_types, ["System.Int32", "System.String", "System.Decimal", "System.Guid", "System.Byte[]"]);
+  assert!(!table.columns[0].allow_null);
+
+  let states = table.rows.iter().map(|row| row.state).collect::<Vec<_>>();
+  assert_eq!(states, [DataRowState::Unchanged, DataRowState::Modified, DataRowState::Deleted, DataRowState::Added]);
+
+  assert_eq!(
+    table.rows[0].values(),
+    [
+      Field::Int32(1),
+      Field::String("Tea & Biscuits".into()),
+      Field::Decimal(rust_decimal::Decimal::new(450, 2)),
+      Field::String("12345678-1234-5678-1234-56789abcdef0".into()),
+      Field::Bytes(vec![1, 2, 3]),
+    ]
+  );
+  assert_eq!(table.rows[1].values()[2], Field::Decimal(rust_decimal::Decimal::new(320, 2)));
+  assert_eq!(table.rows[1].original.as_ref().unwrap()[2], Field::Decimal(rust_decimal::Decimal::new(290, 2)));
+  assert_eq!(table.rows[2].values()[..2], [Field::Int32(3), Field::String("Juice".into())]);
+  assert_eq!(table.rows[3].values(), [Field::Int32(4), Field::Null, Field::Null, Field::Null, Field::Null]);
+}
+
+#[test]
+fn xml_data_table() {
+  let table = object(
+    "System.Data.DataTable",
+    Some(SYSTEM_DATA),
+    vec![("XmlSchema", Value::String(XML_SCHEMA)), ("XmlDiffGram", Value::Null)],
+  );
+
+  let table = DataTable::try_from(&table).unwrap();
+  assert_eq!(table.name, "Order Items");
+  assert_eq!(table.columns.len(), 5);
+  assert!(table.rows.is_empty());
+}
+
+#[test]
+fn errors() {
+  let other = object("App.Customer", Some("App"), vec![]);
+  assert_eq!(DataSet::try_from(&other), Err(DataSetError::UnexpectedClass("App.Customer".into())));
+
+  let mut members = table_schema();
+  members.retain(|(name, _)| *name != "DataTable.DataColumn_1.ColumnName");
+  members.extend(table_data());
+  let table = object("System.Data.DataTable", Some(SYSTEM_DATA), members);
+  assert_eq!(DataTable::try_from(&table), Err(DataSetError::MissingMember("DataTable.DataColumn_1.ColumnName".into())));
+
+  let table = object("System.Data.DataTable", Some(SYSTEM_DATA), vec![("XmlSchema", Value::String("<xs:schema>"))]);
+  assert!(matches!(DataTable::try_from(&table), Err(DataSetError::InvalidXml(_))));
+}
+
+#[test]
+fn xml_data_set_fixture() {
+  let Ok(RemotingMessage::Value(Value::Object(data_set))) = RemotingMessage::parse(XML_DATA_SET) else {
+    panic!("expected an object");
+  };
+
+  let data_set = DataSet::try_from(&data_set).unwrap();
+  assert_eq!(data_set.name, "Shop");
+  assert_eq!(data_set.table("Order Items").map(|table| table.rows.len()), Some(4));
+}
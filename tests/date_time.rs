@@ -0,0 +1,197 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  value::{DateTime, DateTimeKind, TimeConversionError, TimeSpan},
+};
+
+#[rustfmt::skip]
+const DATE_TIMES: &[u8] = concat_bytes!(
+  0,
+    b"\x01\x00\x00\x00",
+    b"\xFF\xFF\xFF\xFF",
+    b"\x01\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+  15,
+    b"\x01\x00\x00\x00",
+    b"\x03\x00\x00\x00",
+    13,
+    b"\x87\xE7\xE6\x5A\x11\xBC\xCB\x48",
+    b"\x87\xE7\xE6\x5A\x11\xBC\xCB\x08",
+    b"\x87\xE7\xE6\x5A\x11\xBC\xCB\x88",
+  11,
+);
+
+/// A local time in the ambiguous hour at the end of daylight saving time.
+#[rustfmt::skip]
+const LOCAL_AMBIGUOUS_DST: &[u8] = concat_bytes!(
+  0,
+    b"\x01\x00\x00\x00",
+    b"\xFF\xFF\xFF\xFF",
+    b"\x01\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+  15,
+    b"\x01\x00\x00\x00",
+    b"\x01\x00\x00\x00",
+    13,
+    b"\x87\xE7\xE6\x5A\x11\xBC\xCB\xC8",
+  11,
+);
+
+#[rustfmt::skip]
+const TIME_SPANS: &[u8] = concat_bytes!(
+  0,
+    b"\x01\x00\x00\x00",
+    b"\xFF\xFF\xFF\xFF",
+    b"\x01\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+  15,
+    b"\x01\x00\x00\x00",
+    b"\x02\x00\x00\x00",
+    12,
+    b"\xD7\x37\xE5\x00\x00\x00\x00\x00",
+    b"\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF",
+  11,
+);
+
+const TICKS: i64 = 633806955301234567;
+
+fn date_times() -> [DateTime; 3] {
+  let Ok(RemotingMessage::Value(Value::Array(values))) = RemotingMessage::parse(DATE_TIMES) else {
+    panic!("expected an array")
+  };
+
+  let date_times = values
+    .into_iter()
+    .map(|value| match value {
+      Value::DateTime(date_time) => date_time,
+      value => panic!("expected a date-time, got {value:?}"),
+    })
+    .collect::<Vec<_>>();
+
+  date_times.try_into().unwrap()
+}
+
+fn time_spans() -> [TimeSpan; 2] {
+  let Ok(RemotingMessage::Value(Value::Array(values))) = RemotingMessage::parse(TIME_SPANS) else {
+    panic!("expected an array")
+  };
+
+  let time_spans = values
+    .into_iter()
+    .map(|value| match value {
+      Value::TimeSpan(time_span) => time_span,
+      value => panic!("expected a time span, got {value:?}"),
+    })
+    .collect::<Vec<_>>();
+
+  time_spans.try_into().unwrap()
+}
+
+#[test]
+fn date_time() {
+  let [utc, unspecified, local] = date_times();
+
+  assert_eq!(utc.ticks(), TICKS);
+  assert_eq!(utc.kind(), Some(DateTimeKind::Utc));
+  assert_eq!(utc.to_string(), "2009-06-15T20:45:30.1234567Z");
+
+  assert_eq!(unspecified.ticks(), TICKS);
+  assert_eq!(unspecified.kind(), None);
+  assert_eq!(unspecified.to_string(), "2009-06-15T20:45:30.1234567");
+
+  assert_eq!(local.ticks(), TICKS);
+  assert_eq!(local.kind(), Some(DateTimeKind::Local));
+  assert_eq!(local.to_string(), "2009-06-15T20:45:30.1234567");
+}
+
+#[test]
+fn date_time_local_ambiguous_dst() {
+  let Ok(RemotingMessage::Value(Value::Array(values))) = RemotingMessage::parse(LOCAL_AMBIGUOUS_DST) else {
+    panic!("expected an array")
+  };
+  let [Value::DateTime(local)] = values.as_slice() else { panic!("expected a date-time, got {values:?}") };
+
+  assert_eq!(local.ticks(), TICKS);
+  assert_eq!(local.kind(), Some(DateTimeKind::Local));
+  assert_eq!(local.to_string(), "2009-06-15T20:45:30.1234567");
+  assert_eq!(SystemTime::try_from(*local), Err(TimeConversionError::LocalTime));
+}
+
+#[test]
+fn system_time_conversion() {
+  let [utc, unspecified, local] = date_times();
+  let expected = UNIX_EPOCH + Duration::new(1245098730, 123456700);
+
+  assert_eq!(SystemTime::try_from(utc), Ok(expected));
+  assert_eq!(SystemTime::try_from(unspecified), Ok(expected));
+  assert_eq!(SystemTime::try_from(local), Err(TimeConversionError::LocalTime));
+}
+
+#[test]
+fn duration_conversion() {
+  let [positive, negative] = time_spans();
+
+  assert_eq!(positive.value(), 15_022_039);
+  assert_eq!(Duration::try_from(positive), Ok(Duration::new(1, 502_203_900)));
+  assert_eq!(Duration::try_from(negative), Err(TimeConversionError::OutOfRange));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_conversion() {
+  use chrono::{NaiveDate, TimeDelta, Utc};
+
+  let [utc, unspecified, local] = date_times();
+  let expected = NaiveDate::from_ymd_opt(2009, 6, 15).unwrap().and_hms_nano_opt(20, 45, 30, 123456700).unwrap();
+
+  assert_eq!(chrono::NaiveDateTime::from(unspecified), expected);
+  assert_eq!(chrono::DateTime::<Utc>::try_from(utc), Ok(expected.and_utc()));
+  assert_eq!(chrono::DateTime::<Utc>::try_from(local), Err(TimeConversionError::LocalTime));
+
+  let [positive, negative] = time_spans();
+  assert_eq!(TimeDelta::from(positive), TimeDelta::nanoseconds(1_502_203_900));
+  assert_eq!(TimeDelta::from(negative), TimeDelta::nanoseconds(-100));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_conversion() {
+  use time::{Date, Month, OffsetDateTime, PrimitiveDateTime};
+
+  let [utc, unspecified, local] = date_times();
+  let expected = Date::from_calendar_date(2009, Month::June, 15).unwrap().with_hms_nano(20, 45, 30, 123456700).unwrap();
+
+  assert_eq!(PrimitiveDateTime::try_from(unspecified), Ok(expected));
+  assert_eq!(OffsetDateTime::try_from(utc), Ok(expected.assume_utc()));
+  assert_eq!(OffsetDateTime::try_from(local), Err(TimeConversionError::LocalTime));
+
+  let [positive, negative] = time_spans();
+  assert_eq!(time::Duration::from(positive), time::Duration::nanoseconds(1_502_203_900));
+  assert_eq!(time::Duration::from(negative), time::Duration::nanoseconds(-100));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize() {
+  assert_eq!(
+    nrbf::from_slice(DATE_TIMES),
+    Ok(vec![
+      "2009-06-15T20:45:30.1234567Z".to_owned(),
+      "2009-06-15T20:45:30.1234567".to_owned(),
+      "2009-06-15T20:45:30.1234567".to_owned(),
+    ])
+  );
+  assert_eq!(nrbf::from_slice(DATE_TIMES), Ok(vec![TICKS; 3]));
+  assert_eq!(nrbf::from_slice(DATE_TIMES), Ok(serde_json::json!([TICKS, TICKS, TICKS])));
+  assert_eq!(
+    nrbf::from_slice::<Vec<SystemTime>>(DATE_TIMES).unwrap_err().to_string(),
+    "local time with unknown offset"
+  );
+  let expected = UNIX_EPOCH + Duration::new(1245098730, 123456700);
+  assert_eq!(nrbf::from_slice(DATE_TIMES), Ok((expected, expected, "2009-06-15T20:45:30.1234567".to_owned())));
+
+  assert_eq!(nrbf::from_slice(TIME_SPANS), Ok([15_022_039i64, -1]));
+  assert_eq!(nrbf::from_slice::<Vec<Duration>>(TIME_SPANS).unwrap_err().to_string(), "value out of range");
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  value::{ConversionError, Decimal, Object},
+};
+
+/// A boxed `-12.34m`.
+#[rustfmt::skip]
+const DECIMAL: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    14, "System.Decimal",
+    0x04, 0x00, 0x00, 0x00,
+    5, "flags",
+    2, "hi",
+    2, "lo",
+    3, "mid",
+    0, 0, 0, 0,
+    8,
+    8,
+    8,
+    8,
+    0x00, 0x00, 0x02, 0x80,
+    0x00, 0x00, 0x00, 0x00,
+    0xD2, 0x04, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  11,
+);
+
+fn decimal(members: Vec<(&'static str, Value<'static>)>) -> Object<'static> {
+  Object { class: "System.Decimal", library: None, members: HashMap::from_iter(members) }
+}
+
+#[test]
+fn rust_decimal_conversion() {
+  let n = rust_decimal::Decimal::new(-1234567, 4);
+  let decimal = Decimal::from(n);
+
+  assert_eq!(decimal.to_rust_decimal(), n);
+  assert_eq!(decimal.to_string(), "-123.4567");
+  assert_eq!(rust_decimal::Decimal::from(decimal), n);
+}
+
+#[test]
+fn object_conversion() {
+  let framework = decimal(vec![
+    ("flags", Value::Int32(0x80040000u32 as i32)),
+    ("hi", Value::Int32(0)),
+    ("mid", Value::Int32(1)),
+    ("lo", Value::Int32(-1)),
+  ]);
+  assert_eq!(Decimal::try_from(&framework).unwrap().to_string(), "-858993.4591");
+
+  let lo64 = decimal(vec![
+    ("_flags", Value::Int32(0x00040000)),
+    ("_hi32", Value::UInt32(0)),
+    ("_lo64", Value::UInt64(0x1_FFFF_FFFF)),
+  ]);
+  assert_eq!(Decimal::try_from(&lo64).unwrap().to_string(), "858993.4591");
+
+  let lo32 = decimal(vec![
+    ("_flags", Value::Int32(0)),
+    ("_hi32", Value::Int32(-1)),
+    ("_mid32", Value::Int32(-1)),
+    ("_lo32", Value::Int32(-1)),
+  ]);
+  assert_eq!(Decimal::try_from(&lo32).unwrap().to_rust_decimal(), rust_decimal::Decimal::MAX);
+}
+
+#[test]
+fn object_conversion_errors() {
+  let invalid_scale = decimal(vec![
+    ("flags", Value::Int32(29 << 16)),
+    ("hi", Value::Int32(0)),
+    ("mid", Value::Int32(0)),
+    ("lo", Value::Int32(1)),
+  ]);
+  assert_eq!(Decimal::try_from(&invalid_scale), Err(ConversionError::InvalidMember("flags")));
+
+  let missing_mid = decimal(vec![("_flags", Value::Int32(0)), ("_hi32", Value::Int32(0)), ("_lo32", Value::Int32(1))]);
+  assert_eq!(Decimal::try_from(&missing_mid), Err(ConversionError::MissingMember("_mid32")));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize() {
+  use serde::Deserialize;
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Invoice {
+    total: rust_decimal::Decimal,
+    tax: rust_decimal::Decimal,
+  }
+
+  let invoice = Value::Object(Object {
+    class: "App.Invoice",
+    library: Some("App"),
+    members: HashMap::from([
+      ("total", Value::Decimal(Decimal::from(rust_decimal::Decimal::new(10995, 2)))),
+      (
+        "tax",
+        Value::Object(decimal(vec![
+          ("flags", Value::Int32(0x00020000)),
+          ("hi", Value::Int32(0)),
+          ("mid", Value::Int32(0)),
+          ("lo", Value::Int32(1759)),
+        ])),
+      ),
+    ]),
+  });
+  let bytes = nrbf::RemotingMessage::Value(invoice).to_bytes();
+
+  assert_eq!(
+    nrbf::from_slice(&bytes),
+    Ok(Invoice { total: rust_decimal::Decimal::new(10995, 2), tax: rust_decimal::Decimal::new(1759, 2) })
+  );
+}
+
+#[test]
+fn decimal_fixture() {
+  let Ok(RemotingMessage::Value(Value::Object(decimal))) = RemotingMessage::parse(DECIMAL) else {
+    panic!("expected an object");
+  };
+
+  assert_eq!(Decimal::try_from(&decimal).unwrap().to_string(), "-12.34");
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "derive")]
+
+use std::collections::HashMap;
+
+use nrbf::{
+  RemotingMessage, Value,
+  value::{FromValue, FromValueError, NrbfClass, Object},
+};
+
+const MODEL: &str = "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library, members: HashMap::from_iter(members) })
+}
+
+#[derive(Debug, PartialEq, NrbfClass)]
+#[nrbf(class = "Contoso.Customer", assembly = "Contoso.Model")]
+struct Customer<'i> {
+  #[nrbf(property)]
+  name: &'i str,
+  #[nrbf(property = "EMail")]
+  email: Option<String>,
+}
+
+#[derive(Debug, PartialEq, NrbfClass)]
+#[nrbf(class = "Contoso.Order", assembly = "Contoso.Model")]
+struct Order<'i> {
+  #[nrbf(rename = "_id")]
+  id: i32,
+  #[nrbf(property)]
+  customer: Customer<'i>,
+  #[nrbf(property)]
+  lines: Vec<f64>,
+  #[nrbf(default)]
+  priority: i16,
+  #[nrbf(with = "cents")]
+  total: i64,
+  #[nrbf(skip)]
+  cache: Vec<u8>,
+}
+
+fn cents(value: &Value<'_>) -> Result<i64, FromValueError> {
+  f64::from_value(value).map(|total| (total * 100.0).round() as i64)
+}
+
+fn customer<'i>(email: Value<'i>) -> Value<'i> {
+  object(
+    "Contoso.Customer",
+    Some(MODEL),
+    vec![("<Name>k__BackingField", Value::String("Alice")), ("<EMail>k__BackingField", email)],
+  )
+}
+
+fn order(customer: Value<'_>) -> Value<'_> {
+  object(
+    "Contoso.Order",
+    Some(MODEL),
+    vec![
+      ("_id", Value::Int32(7)),
+      ("<Customer>k__BackingField", customer),
+      ("<Lines>k__BackingField", Value::Array(vec![Value::Double(1.5), Value::Double(2.25)])),
+      ("total", Value::Double(3.75)),
+    ],
+  )
+}
+
+#[test]
+fn derive_nrbf_class() {
+  let bytes = RemotingMessage::Value(order(customer(Value::Null))).to_bytes();
+  let Ok(RemotingMessage::Value(value)) = RemotingMessage::parse(&bytes) else { panic!("expected a value") };
+
+  assert_eq!(Order::CLASS, "Contoso.Order");
+  assert_eq!(Order::ASSEMBLY, Some("Contoso.Model"));
+  assert_eq!(
+    Order::from_value(&value),
+    Ok(Order {
+      id: 7,
+      customer: Customer { name: "Alice", email: None },
+      lines: vec![1.5, 2.25],
+      priority: 0,
+      total: 375,
+      cache: vec![],
+    })
+  );
+}
+
+#[test]
+fn derive_nrbf_class_errors() {
+  let other_library = object("Contoso.Customer", Some("Contoso.Other"), vec![]);
+  assert_eq!(
+    Customer::from_value(&other_library),
+    Err(FromValueError::UnexpectedLibrary { expected: "Contoso.Model", found: Some(String::from("Contoso.Other")) })
+  );
+
+  let err = Order::from_value(&order(customer(Value::Int32(1)))).unwrap_err();
+  assert_eq!(
+    err.to_string(),
+    "member <Customer>k__BackingField: member <EMail>k__BackingField: expected String, found Int32"
+  );
+
+  let err = Order::from_value(&order(object("Contoso.Address", Some(MODEL), vec![]))).unwrap_err();
+  assert_eq!(
+    err,
+    FromValueError::Member {
+      name: "<Customer>k__BackingField",
+      error: Box::new(FromValueError::UnexpectedClass {
+        expected: "Contoso.Customer",
+        found: String::from("Contoso.Address")
+      }),
+    }
+  );
+
+  assert_eq!(Order::from_value(&Value::Null).unwrap_err().to_string(), "expected Contoso.Order, found null");
+}
+
+#[test]
+fn from_value() {
+  let array = Value::Array(vec![Value::Int32(1), Value::Null, Value::Int64(3)]);
+  assert_eq!(Vec::<Option<i32>>::from_value(&array).unwrap_err().to_string(), "element 2: expected Int32, found Int64");
+  assert_eq!(Vec::<u8>::from_value(&Value::Bytes(&[1, 2])), Ok(vec![1, 2]));
+  assert_eq!(<&[u8]>::from_value(&Value::Bytes(&[1, 2])), Ok(&[1, 2][..]));
+}
@@ -0,0 +1,197 @@
+#![cfg(feature = "serde")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Dictionary<string, int>` with the entries `one`, `two` and `three`.
+#[rustfmt::skip]
+const DICTIONARY: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    0xE1, 0x01, "System.Collections.Generic.Dictionary`2[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089],[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    0x04, 0x00, 0x00, 0x00,
+    7, "Version",
+    8, "Comparer",
+    8, "HashSize",
+    13, "KeyValuePairs",
+    0, 3, 0, 3,
+    8,
+    0x92, 0x01, "System.Collections.Generic.GenericEqualityComparer`1[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    8,
+    0xE5, 0x01, "System.Collections.Generic.KeyValuePair`2[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089],[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]][]",
+    0x03, 0x00, 0x00, 0x00,
+    9,
+      0x02, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00,
+    9,
+      0x03, 0x00, 0x00, 0x00,
+  4,
+    0x02, 0x00, 0x00, 0x00,
+    0x92, 0x01, "System.Collections.Generic.GenericEqualityComparer`1[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    0x00, 0x00, 0x00, 0x00,
+  7,
+    0x03, 0x00, 0x00, 0x00,
+    0,
+    0x01, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00,
+    3,
+    0xE3, 0x01, "System.Collections.Generic.KeyValuePair`2[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089],[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    4,
+      0x04, 0x00, 0x00, 0x00,
+      0xE3, 0x01, "System.Collections.Generic.KeyValuePair`2[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089],[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+      0x02, 0x00, 0x00, 0x00,
+      3, "key",
+      5, "value",
+      1, 0,
+      8,
+      6,
+        0x05, 0x00, 0x00, 0x00,
+        3, "one",
+      0x01, 0x00, 0x00, 0x00,
+    1,
+      0x06, 0x00, 0x00, 0x00,
+      0x04, 0x00, 0x00, 0x00,
+      6,
+        0x07, 0x00, 0x00, 0x00,
+        3, "two",
+      0x02, 0x00, 0x00, 0x00,
+    1,
+      0x08, 0x00, 0x00, 0x00,
+      0x04, 0x00, 0x00, 0x00,
+      6,
+        0x09, 0x00, 0x00, 0x00,
+        5, "three",
+      0x03, 0x00, 0x00, 0x00,
+  11,
+);
+
+const SYSTEM: &str = "System, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089";
+const TYPE_ARGS: &str = "[[System.String, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089],[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]";
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library, members: HashMap::from_iter(members) })
+}
+
+fn key_value_pairs(class: &str) -> Value<'_> {
+  Value::Array(
+    [("one", 1), ("two", 2), ("three", 3)]
+      .into_iter()
+      .map(|(key, value)| object(class, None, vec![("key", Value::String(key)), ("value", Value::Int32(value))]))
+      .collect(),
+  )
+}
+
+fn round_trip<T>(value: Value<'_>, f: impl FnOnce(&[u8]) -> T) -> T {
+  let bytes = RemotingMessage::Value(value).to_bytes();
+  f(&bytes)
+}
+
+fn expected() -> BTreeMap<String, i32> {
+  BTreeMap::from_iter([("one".into(), 1), ("two".into(), 2), ("three".into(), 3)])
+}
+
+#[test]
+fn dictionary() {
+  let pair_class = format!("System.Collections.Generic.KeyValuePair`2{}", TYPE_ARGS);
+  let class = format!("System.Collections.Generic.Dictionary`2{}", TYPE_ARGS);
+  let dictionary = object(
+    &class,
+    None,
+    vec![
+      ("Version", Value::Int32(3)),
+      ("Comparer", Value::Null),
+      ("HashSize", Value::Int32(7)),
+      ("KeyValuePairs", key_value_pairs(&pair_class)),
+    ],
+  );
+
+  round_trip(dictionary, |bytes| {
+    assert_eq!(nrbf::from_slice::<BTreeMap<String, i32>>(bytes), Ok(expected()));
+    assert_eq!(nrbf::from_slice::<HashMap<&str, i32>>(bytes).map(|map| map.len()), Ok(3));
+  });
+}
+
+#[test]
+fn dictionary_empty() {
+  let class = format!("System.Collections.Generic.Dictionary`2{}", TYPE_ARGS);
+  let dictionary =
+    object(&class, None, vec![("Version", Value::Int32(0)), ("Comparer", Value::Null), ("HashSize", Value::Int32(0))]);
+
+  round_trip(dictionary, |bytes| assert_eq!(nrbf::from_slice::<HashMap<String, i32>>(bytes), Ok(HashMap::new())));
+}
+
+#[test]
+fn sorted_dictionary() {
+  let pair_class = format!("System.Collections.Generic.KeyValuePair`2{}", TYPE_ARGS);
+  let set_class = format!("System.Collections.Generic.TreeSet`1[{}]", TYPE_ARGS);
+  let class = format!("System.Collections.Generic.SortedDictionary`2{}", TYPE_ARGS);
+  let dictionary = object(
+    &class,
+    Some(SYSTEM),
+    vec![(
+      "_set",
+      object(
+        &set_class,
+        Some(SYSTEM),
+        vec![
+          ("Count", Value::Int32(3)),
+          ("Comparer", Value::Null),
+          ("Version", Value::Int32(3)),
+          ("Items", key_value_pairs(&pair_class)),
+        ],
+      ),
+    )],
+  );
+
+  round_trip(dictionary, |bytes| assert_eq!(nrbf::from_slice(bytes), Ok(expected())));
+}
+
+#[test]
+fn sorted_list() {
+  let class = format!("System.Collections.Generic.SortedList`2{}", TYPE_ARGS);
+  let sorted_list = object(
+    &class,
+    Some(SYSTEM),
+    vec![
+      ("keys", Value::Array(vec![Value::String("one"), Value::String("three"), Value::String("two"), Value::Null])),
+      ("values", Value::Array(vec![Value::Int32(1), Value::Int32(3), Value::Int32(2), Value::Int32(0)])),
+      ("_size", Value::Int32(3)),
+      ("version", Value::Int32(3)),
+      ("comparer", Value::Null),
+      ("keyList", Value::Null),
+      ("valueList", Value::Null),
+    ],
+  );
+
+  round_trip(sorted_list, |bytes| assert_eq!(nrbf::from_slice(bytes), Ok(expected())));
+}
+
+#[test]
+fn concurrent_dictionary() {
+  let pair_class = format!("System.Collections.Generic.KeyValuePair`2{}", TYPE_ARGS);
+  let class = format!("System.Collections.Concurrent.ConcurrentDictionary`2{}", TYPE_ARGS);
+  let dictionary = object(
+    &class,
+    None,
+    vec![
+      ("m_serializationArray", key_value_pairs(&pair_class)),
+      ("m_serializationConcurrencyLevel", Value::Int32(4)),
+      ("m_serializationCapacity", Value::Int32(31)),
+    ],
+  );
+
+  round_trip(dictionary, |bytes| assert_eq!(nrbf::from_slice(bytes), Ok(expected())));
+}
+
+#[test]
+fn dictionary_fixture() {
+  assert_eq!(nrbf::from_slice::<BTreeMap<String, i32>>(DICTIONARY), Ok(expected()));
+  assert_eq!(nrbf::from_slice::<HashMap<&str, i32>>(DICTIONARY).map(|map| map["two"]), Ok(2));
+}
@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  value::{Bitmap, Color, ConversionError, Font, FontStyle, GraphicsUnit, Object, Point, Rectangle, Size},
+};
+
+/// A `Color` created with `Color.FromArgb(0xFF, 0x33, 0x66, 0x99)`.
+#[rustfmt::skip]
+const COLOR: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    81, "System.Drawing, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b03f5f7f11d50a3a",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    20, "System.Drawing.Color",
+    0x04, 0x00, 0x00, 0x00,
+    4, "name",
+    5, "value",
+    10, "knownColor",
+    5, "state",
+    1, 0, 0, 0,
+    9,
+    7,
+    7,
+    0x02, 0x00, 0x00, 0x00,
+    10,
+    0x99, 0x66, 0x33, 0xFF, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00,
+    0x02, 0x00,
+  11,
+);
+
+const LIBRARY: &str = "System.Drawing, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b03f5f7f11d50a3a";
+
+fn object<'i>(class: &'i str, members: Vec<(&'i str, Value<'i>)>) -> Object<'i> {
+  Object { class, library: Some(LIBRARY), members: HashMap::from_iter(members) }
+}
+
+fn color(name: Option<&'static str>, value: i64, known_color: i16, state: i16) -> Object<'static> {
+  object(
+    "System.Drawing.Color",
+    vec![
+      ("name", name.map_or(Value::Null, Value::String)),
+      ("value", Value::Int64(value)),
+      ("knownColor", Value::Int16(known_color)),
+      ("state", Value::Int16(state)),
+    ],
+  )
+}
+
+#[test]
+fn bitmap_borrows_data() {
+  let png = b"\x89PNG\r\n\x1a\n";
+  let message =
+    RemotingMessage::Value(Value::Object(object("System.Drawing.Bitmap", vec![("Data", Value::Bytes(png))])));
+  let bytes = message.to_bytes();
+
+  let Ok(RemotingMessage::Value(Value::Object(bitmap))) = RemotingMessage::parse(&bytes) else {
+    panic!("expected an object");
+  };
+  let bitmap = Bitmap::try_from(&bitmap).unwrap();
+
+  assert_eq!(bitmap.data, png);
+  assert!(bytes.as_ptr_range().contains(&bitmap.data.as_ptr()));
+}
+
+#[test]
+fn color_conversion() {
+  let red = Color::try_from(&color(None, 0, 141, 0x1)).unwrap();
+  assert_eq!(red.known_color_name(), Some("Red"));
+  assert_eq!(red.to_string(), "Red");
+
+  let argb = Color::try_from(&color(None, 0xFF00FF00, 0, 0x2)).unwrap();
+  assert_eq!(argb, Color { name: None, argb: Some(0xFF00FF00), known_color: None });
+  assert_eq!(argb.to_string(), "ff00ff00");
+
+  let named = Color::try_from(&color(Some("Custom"), 0, 0, 0x8)).unwrap();
+  assert_eq!(named.to_string(), "Custom");
+
+  let empty = Color::try_from(&color(None, 0, 0, 0)).unwrap();
+  assert!(empty.is_empty());
+  assert_eq!(empty.to_string(), "0");
+}
+
+#[test]
+fn geometry() {
+  let point = object("System.Drawing.Point", vec![("x", Value::Int32(1)), ("y", Value::Int32(-2))]);
+  assert_eq!(Point::try_from(&point), Ok(Point { x: 1, y: -2 }));
+
+  let size = object("System.Drawing.Size", vec![("width", Value::Int32(640)), ("height", Value::Int32(480))]);
+  assert_eq!(Size::try_from(&size), Ok(Size { width: 640, height: 480 }));
+
+  let rectangle = object(
+    "System.Drawing.Rectangle",
+    vec![("x", Value::Int32(1)), ("y", Value::Int32(2)), ("width", Value::Int32(640)), ("height", Value::Int32(480))],
+  );
+  let rectangle = Rectangle::try_from(&rectangle).unwrap();
+  assert_eq!(rectangle.location(), Point { x: 1, y: 2 });
+  assert_eq!(rectangle.size(), Size { width: 640, height: 480 });
+
+  assert_eq!(
+    Rectangle::try_from(&point),
+    Err(ConversionError::UnexpectedClass {
+      expected: "System.Drawing.Rectangle",
+      found: String::from("System.Drawing.Point")
+    })
+  );
+}
+
+#[test]
+fn font() {
+  let enum_value = |class, value| Value::Object(object(class, vec![("value__", Value::Int32(value))]));
+
+  let font = object(
+    "System.Drawing.Font",
+    vec![
+      ("Name", Value::String("Segoe UI")),
+      ("Size", Value::Single(9.0)),
+      ("Style", enum_value("System.Drawing.FontStyle", 3)),
+      ("Unit", enum_value("System.Drawing.GraphicsUnit", 3)),
+    ],
+  );
+
+  assert_eq!(
+    Font::try_from(&font),
+    Ok(Font { name: "Segoe UI", size: 9.0, style: FontStyle::BOLD | FontStyle::ITALIC, unit: GraphicsUnit::Point })
+  );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn drawing_deserialize() {
+  let png = b"\x89PNG\r\n\x1a\n";
+  let message =
+    RemotingMessage::Value(Value::Object(object("System.Drawing.Bitmap", vec![("Data", Value::Bytes(png))])));
+  let bytes = message.to_bytes();
+  assert_eq!(nrbf::from_slice::<&[u8]>(&bytes), Ok(&png[..]));
+
+  let message = RemotingMessage::Value(Value::Object(color(None, 0, 141, 0x1)));
+  assert_eq!(nrbf::from_slice::<String>(&message.to_bytes()), Ok(String::from("Red")));
+}
+
+#[test]
+fn color_fixture() {
+  let Ok(RemotingMessage::Value(Value::Object(color))) = RemotingMessage::parse(COLOR) else {
+    panic!("expected an object");
+  };
+
+  let color = Color::try_from(&color).unwrap();
+  assert_eq!(color, Color { name: None, argb: Some(0xFF336699), known_color: None });
+  assert_eq!(color.to_string(), "ff336699");
+}
@@ -0,0 +1,232 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{MethodCall, RemotingMessage, Value, value::Object};
+use serde::Deserialize;
+use serde_repr::Deserialize_repr;
+
+/// An `App.Pixel` with the enum member `color` written inline.
+#[rustfmt::skip]
+const PIXEL: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    58, "App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    9, "App.Pixel",
+    0x02, 0x00, 0x00, 0x00,
+    5, "color",
+    3, "raw",
+    4, 0,
+    9, "App.Color",
+    0x02, 0x00, 0x00, 0x00,
+    8,
+    0x02, 0x00, 0x00, 0x00,
+    5,
+      0x03, 0x00, 0x00, 0x00,
+      9, "App.Color",
+      0x01, 0x00, 0x00, 0x00,
+      7, "value__",
+      0,
+      8,
+      0x02, 0x00, 0x00, 0x00,
+      0x02, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00,
+  11,
+);
+
+const LIBRARY: &str = "App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+#[derive(Debug, Deserialize_repr, PartialEq)]
+#[repr(i32)]
+enum Color {
+  Red   = 0,
+  Green = 1,
+  Blue  = 2,
+}
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library, members: HashMap::from_iter(members) })
+}
+
+fn color(value: i32) -> Value<'static> {
+  object("App.Color", Some(LIBRARY), vec![("value__", Value::Int32(value))])
+}
+
+fn nullable<'i>(value: Option<Value<'i>>) -> Value<'i> {
+  object(
+    "System.Nullable`1[[App.Color, App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null]]",
+    None,
+    vec![("hasValue", Value::Boolean(value.is_some())), ("value", value.unwrap_or(Value::Int32(0)))],
+  )
+}
+
+#[test]
+fn enum_in_object() {
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Pixel {
+    color: Color,
+    colors: Vec<Color>,
+    raw: i32,
+  }
+
+  let pixel = object(
+    "App.Pixel",
+    Some(LIBRARY),
+    vec![("color", color(2)), ("colors", Value::Array(vec![color(0), color(1)])), ("raw", color(1))],
+  );
+  let bytes = RemotingMessage::Value(pixel).to_bytes();
+
+  assert_eq!(
+    nrbf::from_slice(&bytes),
+    Ok(Pixel { color: Color::Blue, colors: vec![Color::Red, Color::Green], raw: 1 })
+  );
+}
+
+#[test]
+fn enum_from_name() {
+  #[derive(Debug, Deserialize, PartialEq)]
+  enum Color {
+    Red,
+    Green,
+  }
+
+  let bytes = RemotingMessage::Value(Value::String("Green")).to_bytes();
+  assert_eq!(nrbf::from_slice(&bytes), Ok(Color::Green));
+
+  // Variants are not chosen by index.
+  let bytes = RemotingMessage::Value(color(1)).to_bytes();
+  assert!(nrbf::from_slice::<Color>(&bytes).is_err());
+}
+
+#[test]
+fn enum_from_boxed_primitive() {
+  let bytes = RemotingMessage::Value(Value::Int32(0)).to_bytes();
+
+  assert_eq!(nrbf::from_slice(&bytes), Ok(Color::Red));
+}
+
+#[test]
+fn enum_invalid() {
+  let bytes = RemotingMessage::Value(color(3)).to_bytes();
+  assert!(nrbf::from_slice::<Color>(&bytes).is_err());
+
+  let bytes = RemotingMessage::Value(color(-1)).to_bytes();
+  assert!(nrbf::from_slice::<Color>(&bytes).is_err());
+}
+
+#[test]
+fn enum_in_args() {
+  let method_call = MethodCall {
+    method_name: "Paint",
+    type_name: "App.Canvas, App",
+    call_context: None,
+    args: Some(vec![color(1), Value::Int32(2)]),
+  };
+
+  assert_eq!(method_call.deserialize_args(), Ok((Color::Green, Color::Blue)));
+}
+
+#[test]
+fn nullable_in_object() {
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Settings {
+    background: Option<Color>,
+    foreground: Option<Color>,
+    opacity: Option<i32>,
+    border: Option<i32>,
+  }
+
+  let settings = object(
+    "App.Settings",
+    Some(LIBRARY),
+    vec![
+      ("background", nullable(Some(color(2)))),
+      ("foreground", nullable(None)),
+      ("opacity", nullable(Some(Value::Int32(80)))),
+      ("border", Value::Null),
+    ],
+  );
+  let bytes = RemotingMessage::Value(settings).to_bytes();
+
+  assert_eq!(
+    nrbf::from_slice(&bytes),
+    Ok(Settings { background: Some(Color::Blue), foreground: None, opacity: Some(80), border: None })
+  );
+}
+
+/// An enum with values not starting at 0 and with gaps, like `enum Status { Active = 1, Disabled = 5 }`.
+#[derive(Debug, Deserialize_repr, PartialEq)]
+#[repr(i32)]
+enum Status {
+  Active   = 1,
+  Disabled = 5,
+}
+
+fn status(value: i32) -> Value<'static> {
+  object("App.Status", Some(LIBRARY), vec![("value__", Value::Int32(value))])
+}
+
+#[test]
+fn enum_by_discriminant() {
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Account {
+    status: Status,
+    history: Vec<Status>,
+    pending: Option<Status>,
+  }
+
+  let account = object(
+    "App.Account",
+    Some(LIBRARY),
+    vec![
+      ("status", status(1)),
+      ("history", Value::Array(vec![status(5), status(1)])),
+      ("pending", nullable(Some(status(5)))),
+    ],
+  );
+  let bytes = RemotingMessage::Value(account).to_bytes();
+
+  assert_eq!(
+    nrbf::from_slice(&bytes),
+    Ok(Account {
+      status: Status::Active,
+      history: vec![Status::Disabled, Status::Active],
+      pending: Some(Status::Disabled)
+    })
+  );
+
+  let method_call = MethodCall {
+    method_name: "SetStatus",
+    type_name: "App.Accounts, App",
+    call_context: None,
+    args: Some(vec![status(5), Value::Int32(1)]),
+  };
+  assert_eq!(method_call.deserialize_args(), Ok((Status::Disabled, Status::Active)));
+
+  for value in [0, 2, 6] {
+    let bytes = RemotingMessage::Value(status(value)).to_bytes();
+    assert_eq!(
+      nrbf::from_slice::<Status>(&bytes).map_err(|err| err.to_string()),
+      Err(format!("invalid value: {}, expected 1 or 5", value))
+    );
+  }
+}
+
+#[test]
+fn enum_fixture() {
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Pixel {
+    color: Color,
+    raw: i32,
+  }
+
+  assert_eq!(nrbf::from_slice(PIXEL), Ok(Pixel { color: Color::Blue, raw: 1 }));
+}
@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+
+use const_str::concat_bytes;
+use nrbf::{
+  MethodReturn, RemotingMessage, Value,
+  http::{Error, Request, Response, Warning},
+};
+
+#[rustfmt::skip]
+const METHOD_RETURN: &[u8] = concat_bytes!(
+  0,
+    b"\x00\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+    b"\x01\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+  22,
+    b"\x11\x08\x00\x00",
+    18, 16, "Address received",
+  11,
+);
+
+#[rustfmt::skip]
+const METHOD_CALL: &[u8] = concat_bytes!(
+  0,
+    b"\x00\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+    b"\x01\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+  21,
+    b"\x12\x00\x00\x00",
+    18, 4, "Ping",
+    18, 16, "Server.Ping, App",
+    b"\x01\x00\x00\x00",
+    8, b"\x2A\x00\x00\x00",
+  11,
+);
+
+#[rustfmt::skip]
+const REQUEST: &[u8] = concat_bytes!(
+  "POST /Server.rem HTTP/1.1\r\n",
+  "User-Agent: Mozilla/4.0+(compatible; MSIE 6.0; Windows 10.0.19045.0; MS .NET Remoting; MS .NET CLR 4.0.30319.42000 )\r\n",
+  "Content-Type: application/octet-stream\r\n",
+  "__RequestVerb: POST\r\n",
+  "SOAPAction: \"Server.Ping, App#Ping\"\r\n",
+  "Host: localhost:8080\r\n",
+  "Content-Length: 56\r\n",
+  "\r\n",
+  METHOD_CALL,
+);
+
+#[test]
+fn request() {
+  let request = Request::parse(REQUEST).unwrap();
+
+  assert_eq!(request.method, "POST");
+  assert_eq!(request.uri, "/Server.rem");
+  assert_eq!(request.header("content-type"), Some("application/octet-stream"));
+  assert_eq!(request.soap_action(), Some("Server.Ping, App#Ping"));
+  assert_eq!(request.warnings, vec![]);
+  assert_eq!(request.body, Cow::Borrowed(METHOD_CALL));
+
+  let Ok(RemotingMessage::MethodCall(method_call)) = request.message() else { panic!("expected a method call") };
+  assert_eq!(method_call.method_name, "Ping");
+  assert_eq!(method_call.type_name, "Server.Ping, App");
+  assert_eq!(method_call.args, Some(vec![Value::Int32(42)]));
+}
+
+#[test]
+fn request_unexpected_verb() {
+  let request = concat_bytes!("GET /Server.rem?wsdl HTTP/1.1\r\n", "Host: localhost:8080\r\n", "\r\n");
+
+  assert_eq!(Request::parse(request), Err(Error::UnexpectedRequestVerb("GET")));
+}
+
+#[test]
+fn request_unexpected_content_type() {
+  let request = concat_bytes!(
+    "POST /Server.rem HTTP/1.1\r\n",
+    "Content-Type: text/xml; charset=\"utf-8\"\r\n",
+    "SOAPAction: \"http://schemas.microsoft.com/clr/nsassem/Server/App#Ping\"\r\n",
+    "\r\n",
+  );
+
+  assert_eq!(Request::parse(request), Err(Error::UnexpectedContentType("text/xml; charset=\"utf-8\"")));
+}
+
+#[test]
+fn response() {
+  #[rustfmt::skip]
+  let response = concat_bytes!(
+    "HTTP/1.1 200 OK\r\n",
+    "Content-Type: application/octet-stream\r\n",
+    "Server: MS .NET Remoting, MS .NET CLR 4.0.30319.42000\r\n",
+    "Content-Length: 41\r\n",
+    "\r\n",
+    METHOD_RETURN,
+  );
+
+  let response = Response::parse(response).unwrap();
+
+  assert_eq!(response.status_code, 200);
+  assert_eq!(response.reason, "OK");
+  assert_eq!(response.warnings, vec![]);
+  assert_eq!(
+    response.message(),
+    Ok(RemotingMessage::MethodReturn(MethodReturn::new(Some(Value::String("Address received")))))
+  );
+}
+
+#[test]
+fn response_chunked() {
+  #[rustfmt::skip]
+  let response = concat_bytes!(
+    "HTTP/1.1 200 OK\r\n",
+    "Content-Type: application/octet-stream\r\n",
+    "Transfer-Encoding: chunked\r\n",
+    "\r\n",
+    "10\r\n",
+    b"\x00\x00\x00\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00",
+    "\r\n",
+    "19\r\n",
+    b"\x00\x16\x11\x08\x00\x00\x12\x10Address received\x0B",
+    "\r\n",
+    "0\r\n",
+    "\r\n",
+  );
+
+  let response = Response::parse(response).unwrap();
+
+  assert_eq!(response.warnings, vec![Warning::Chunked]);
+  assert_eq!(response.body, Cow::<[u8]>::Owned(METHOD_RETURN.to_vec()));
+  assert!(response.message().is_ok());
+}
+
+#[test]
+fn response_invalid_chunk() {
+  #[rustfmt::skip]
+  let response = concat_bytes!(
+    "HTTP/1.1 200 OK\r\n",
+    "Content-Type: application/octet-stream\r\n",
+    "Transfer-Encoding: chunked\r\n",
+    "\r\n",
+    "FF\r\n",
+    b"\x00\x00",
+  );
+
+  assert_eq!(Response::parse(response), Err(Error::InvalidChunk));
+}
+
+#[test]
+fn response_content_length_mismatch() {
+  #[rustfmt::skip]
+  let response = concat_bytes!(
+    "HTTP/1.1 500 Internal Server Error\r\n",
+    "Content-Type: application/octet-stream\r\n",
+    "Content-Length: 100\r\n",
+    "\r\n",
+    METHOD_RETURN,
+  );
+
+  let response = Response::parse(response).unwrap();
+
+  assert_eq!(response.status_code, 500);
+  assert_eq!(response.reason, "Internal Server Error");
+  assert_eq!(response.warnings, vec![Warning::ContentLengthMismatch { declared: 100, actual: 41 }]);
+  assert!(response.message().is_ok());
+}
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+#[rustfmt::skip]
+const INPUT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    125, "System.Collections.Generic.List`1[[System.Byte, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    0x03, 0x00, 0x00, 0x00,
+    6, "_items",
+    5, "_size",
+    8, "_version",
+    7, 0, 0,
+    2, 8, 8,
+    9,
+      0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+  15,
+    0x02, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00,
+    2,
+    0x01, 0x02, 0x00, 0x00,
+  11
+);
+
+fn list_of_bytes() -> RemotingMessage<'static> {
+  RemotingMessage::Value(Value::Object(Object {
+    class: "System.Collections.Generic.List`1[[System.Byte, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    library: None,
+    members: HashMap::from_iter([
+      ("_items", Value::Bytes(&[1, 2, 0, 0])),
+      ("_size", Value::Int32(2)),
+      ("_version", Value::Int32(2)),
+    ]),
+  }))
+}
+
+#[test]
+fn list_of_bytes_parse() {
+  assert_eq!(RemotingMessage::parse(INPUT), Ok(list_of_bytes()));
+}
+
+#[test]
+fn list_of_bytes_round_trip() {
+  let bytes = list_of_bytes().to_bytes();
+
+  assert_eq!(RemotingMessage::parse(&bytes), Ok(list_of_bytes()));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn list_of_bytes_deserialize() {
+  assert_eq!(nrbf::from_slice::<Vec<u8>>(INPUT), Ok(vec![1, 2]));
+  assert_eq!(nrbf::from_slice::<Vec<u8>>(&list_of_bytes().to_bytes()), Ok(vec![1, 2]));
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "serde")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use nrbf::{MemberNames, Options, RemotingMessage, Value, value::Object};
+use serde::Deserialize;
+
+const MODEL: &str = "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn order_bytes() -> Vec<u8> {
+  let order = Object {
+    class: "Contoso.Order",
+    library: Some(MODEL),
+    members: HashMap::from_iter([
+      ("<OrderId>k__BackingField", Value::Int32(7)),
+      ("<HTTPReferrer>k__BackingField", Value::String("https://example.com")),
+      ("Entity+<CreatedBy>k__BackingField", Value::String("alice")),
+      ("Entity+note", Value::String("inherited")),
+      ("note", Value::String("own")),
+    ]),
+  };
+
+  RemotingMessage::Value(Value::Object(order)).to_bytes()
+}
+
+#[test]
+fn member_names_apply() {
+  assert_eq!(MemberNames::Normalized.apply("<Name>k__BackingField"), "Name");
+  assert_eq!(MemberNames::Normalized.apply("Base+Derived+_items"), "_items");
+  assert_eq!(MemberNames::SnakeCase.apply("<HTTPServer2Url>k__BackingField"), "http_server2_url");
+  assert_eq!(MemberNames::SnakeCase.apply("m_value"), "m_value");
+  assert_eq!(MemberNames::SnakeCase.apply("camelCase"), "camel_case");
+}
+
+#[test]
+fn snake_case_struct() {
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Order<'a> {
+    order_id: i32,
+    http_referrer: &'a str,
+    created_by: String,
+    note: String,
+  }
+
+  let bytes = order_bytes();
+  let options = Options::new().with_member_names(MemberNames::SnakeCase);
+
+  assert_eq!(
+    nrbf::from_slice_with(&bytes, &options),
+    Ok(Order {
+      order_id: 7,
+      http_referrer: "https://example.com",
+      created_by: String::from("alice"),
+      note: String::from("own"),
+    })
+  );
+  assert!(nrbf::from_slice::<Order<'_>>(&bytes).is_err());
+}
+
+#[test]
+fn normalized_map() {
+  let bytes = order_bytes();
+  let options = Options::new().with_member_names(MemberNames::Normalized);
+
+  let map = nrbf::from_slice_with::<BTreeMap<String, serde::de::IgnoredAny>>(&bytes, &options);
+  assert_eq!(
+    map.map(|map| map.into_keys().collect::<Vec<_>>()),
+    Ok(vec![
+      String::from("CreatedBy"),
+      String::from("Entity+note"),
+      String::from("HTTPReferrer"),
+      String::from("OrderId"),
+      String::from("note"),
+    ])
+  );
+}
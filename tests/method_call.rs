@@ -50,3 +50,50 @@ fn method_call() {
 
   assert_eq!(RemotingMessage::parse(&input), Ok(output))
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn method_call_deserialize_args() {
+  use serde::Deserialize;
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  #[serde(rename_all = "PascalCase")]
+  struct Address<'a> {
+    street: &'a str,
+    city: &'a str,
+    state: &'a str,
+    zip: &'a str,
+  }
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Args<'a> {
+    #[serde(borrow)]
+    address: Address<'a>,
+    count: i32,
+  }
+
+  let method_call = MethodCall {
+    method_name: "SendAddress",
+    type_name: "DOJRemotingMetadata.MyServer, DOJRemotingMetadata",
+    call_context: None,
+    args: Some(vec![
+      Value::Object(Object {
+        class: "DOJRemotingMetadata.Address",
+        library: Some("DOJRemotingMetadata"),
+        members: HashMap::from_iter([
+          ("Street", Value::String("One Microsoft Way")),
+          ("City", Value::String("Redmond")),
+          ("State", Value::String("WA")),
+          ("Zip", Value::String("98054")),
+        ]),
+      }),
+      Value::Int32(2),
+    ]),
+  };
+
+  let address = Address { street: "One Microsoft Way", city: "Redmond", state: "WA", zip: "98054" };
+
+  assert_eq!(method_call.deserialize_args::<(Address, i32)>(), Ok((address, 2)));
+  assert_eq!(method_call.deserialize_args::<Args>().map(|args| args.count), Ok(2));
+  assert!(method_call.deserialize_args::<(Address,)>().is_err());
+}
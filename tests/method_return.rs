@@ -13,7 +13,25 @@ fn method_return() {
     return_value: Some(Value::String("Address received")),
     call_context: None,
     args: None,
+    exception: None,
   });
 
   assert_eq!(RemotingMessage::parse(&input), Ok(output))
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn method_return_deserialize_return() {
+  use nrbf::DotNetFault;
+
+  let method_return = MethodReturn::new(Some(Value::String("Address received")));
+  assert_eq!(method_return.deserialize_return(), Ok("Address received"));
+
+  let method_return = MethodReturn::new(None);
+  assert_eq!(method_return.deserialize_return(), Ok(()));
+  assert_eq!(method_return.deserialize_return(), Ok(None::<i32>));
+
+  let fault = DotNetFault::new("System.ArgumentException", "invalid address");
+  let method_return = MethodReturn::from_exception(fault.to_exception());
+  assert!(method_return.deserialize_return::<()>().is_err());
+}
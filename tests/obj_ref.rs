@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  value::{ConstructionCall, ConstructionResponse, ConversionError, ObjRef, Object},
+};
+
+/// A well-known `ObjRef` for `tcp://10.0.0.1:8080/Accounts.rem`.
+#[rustfmt::skip]
+const WELL_KNOWN_OBJ_REF: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  4,
+    0x01, 0x00, 0x00, 0x00,
+    30, "System.Runtime.Remoting.ObjRef",
+    0x02, 0x00, 0x00, 0x00,
+    3, "url",
+    11, "objrefFlags",
+    1, 0,
+    8,
+    6,
+      0x02, 0x00, 0x00, 0x00,
+      32, "tcp://10.0.0.1:8080/Accounts.rem",
+    0x00, 0x00, 0x00, 0x00,
+  11,
+);
+
+const SERVER_TYPE: &str = "Server.Account, App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn object<'i>(class: &'i str, members: impl IntoIterator<Item = (&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library: None, members: HashMap::from_iter(members) })
+}
+
+fn obj_ref() -> Value<'static> {
+  object(
+    "System.Runtime.Remoting.ObjRef",
+    [
+      ("uri", Value::String("/6a3f2e1d_9c8b_4a7f/Kq2Zr+Pp_1.rem")),
+      ("objrefFlags", Value::Int32(0)),
+      (
+        "typeInfo",
+        object(
+          "System.Runtime.Remoting.TypeInfo",
+          [
+            ("serverType", Value::String(SERVER_TYPE)),
+            (
+              "serverHierarchy",
+              Value::Array(vec![Value::String(
+                "System.MarshalByRefObject, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089",
+              )]),
+            ),
+            ("interfacesImplemented", Value::Null),
+          ],
+        ),
+      ),
+      ("envoyInfo", Value::Null),
+      (
+        "channelInfo",
+        object(
+          "System.Runtime.Remoting.ChannelInfo",
+          [(
+            "channelData",
+            Value::Array(vec![
+              object(
+                "System.Runtime.Remoting.Channels.CrossAppDomainData",
+                [
+                  ("_ContextID", Value::Int32(0)),
+                  ("_DomainID", Value::Int32(1)),
+                  ("_processGuid", Value::String("8f6d2c1b-3e4a-4b5c-9d8e-7f6a5b4c3d2e")),
+                ],
+              ),
+              object(
+                "System.Runtime.Remoting.Channels.ChannelDataStore",
+                [
+                  ("_channelURIs", Value::Array(vec![Value::String("tcp://10.0.0.1:8080")])),
+                  ("_extraData", Value::Null),
+                ],
+              ),
+            ]),
+          )],
+        ),
+      ),
+      ("fIsMarshalled", Value::Int32(0)),
+    ],
+  )
+}
+
+#[test]
+fn construction_response() {
+  let response = object(
+    "System.Runtime.Remoting.Messaging.ConstructionResponse",
+    [
+      ("__Uri", Value::Null),
+      ("__MethodName", Value::String(".ctor")),
+      ("__TypeName", Value::String(SERVER_TYPE)),
+      ("__Return", obj_ref()),
+      ("__OutArgs", Value::Array(vec![])),
+      ("__CallContext", Value::Null),
+    ],
+  );
+
+  let bytes = RemotingMessage::Value(response).to_bytes();
+  let Ok(RemotingMessage::Value(Value::Object(object))) = RemotingMessage::parse(&bytes) else {
+    panic!("expected an object")
+  };
+
+  let response = ConstructionResponse::try_from(&object).unwrap();
+  assert_eq!(response.method_name, Some(".ctor"));
+  assert_eq!(response.fault, None);
+
+  let obj_ref = response.return_value.unwrap();
+  assert!(!obj_ref.is_well_known());
+  assert_eq!(obj_ref.type_info.unwrap().server_type, SERVER_TYPE);
+  assert_eq!(obj_ref.channel_info.unwrap().channel_uris, vec!["tcp://10.0.0.1:8080"]);
+
+  assert_eq!(
+    ObjRef::collect(&Value::Object(object)).iter().flat_map(ObjRef::urls).collect::<Vec<_>>(),
+    vec!["tcp://10.0.0.1:8080/6a3f2e1d_9c8b_4a7f/Kq2Zr+Pp_1.rem"]
+  );
+}
+
+#[test]
+fn construction_call() {
+  let Value::Object(call) = object(
+    "System.Runtime.Remoting.Messaging.ConstructionCall",
+    [
+      ("__Uri", Value::Null),
+      ("__MethodName", Value::String(".ctor")),
+      ("__MethodSignature", Value::Null),
+      ("__TypeName", Value::String(SERVER_TYPE)),
+      ("__Args", Value::Array(vec![Value::String("alice"), Value::Int32(100)])),
+      ("__CallContext", Value::Null),
+      ("__CallSiteActivationAttributes", Value::Null),
+      ("__ActivationType", Value::Null),
+      ("__ContextProperties", Value::Array(vec![])),
+      ("__Activator", Value::Null),
+      ("__ActivationTypeName", Value::String("Server.Account")),
+    ],
+  ) else {
+    unreachable!()
+  };
+
+  let call = ConstructionCall::try_from(&call).unwrap();
+  assert_eq!(call.type_name, SERVER_TYPE);
+  assert_eq!(call.activation_type_name, Some("Server.Account"));
+  assert_eq!(call.args, vec![Value::String("alice"), Value::Int32(100)]);
+  assert_eq!(call.call_context, None);
+}
+
+#[test]
+fn obj_ref_well_known() {
+  let Value::Object(obj_ref_object) =
+    object("System.Runtime.Remoting.ObjRef", [("url", Value::String("tcp://10.0.0.1:8080/Accounts.rem"))])
+  else {
+    unreachable!()
+  };
+
+  let obj_ref = ObjRef::try_from(&obj_ref_object).unwrap();
+  assert!(obj_ref.is_well_known());
+  assert_eq!(obj_ref.urls(), vec!["tcp://10.0.0.1:8080/Accounts.rem"]);
+}
+
+#[test]
+fn obj_ref_errors() {
+  let Value::Object(obj_ref_object) = object("System.Object", []) else { unreachable!() };
+  assert_eq!(
+    ObjRef::try_from(&obj_ref_object),
+    Err(ConversionError::UnexpectedClass { expected: "System.Runtime.Remoting.ObjRef", found: "System.Object".into() })
+  );
+
+  let Value::Object(obj_ref_object) = object("System.Runtime.Remoting.ObjRef", [("objrefFlags", Value::Int32(0))])
+  else {
+    unreachable!()
+  };
+  assert_eq!(ObjRef::try_from(&obj_ref_object), Err(ConversionError::MissingMember("uri")));
+}
+
+#[test]
+fn obj_ref_fixture() {
+  let Ok(RemotingMessage::Value(Value::Object(obj_ref))) = RemotingMessage::parse(WELL_KNOWN_OBJ_REF) else {
+    panic!("expected an object");
+  };
+
+  let obj_ref = ObjRef::try_from(&obj_ref).unwrap();
+  assert!(obj_ref.is_well_known());
+  assert_eq!(obj_ref.urls(), vec!["tcp://10.0.0.1:8080/Accounts.rem"]);
+}
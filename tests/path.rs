@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  value::{Object, Path},
+};
+
+const MODEL: &str = "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+const LIST: &str = "System.Collections.Generic.List`1[[Contoso.Order, Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null]]";
+
+/// A `Contoso.Shop` with an array of two orders, each with a `Contoso.Customer` struct.
+#[rustfmt::skip]
+const SHOP: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    68, "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    12, "Contoso.Shop",
+    0x02, 0x00, 0x00, 0x00,
+    4, "Name",
+    6, "Orders",
+    1, 4,
+    15, "Contoso.Order[]",
+    0x02, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    6,
+      0x03, 0x00, 0x00, 0x00,
+      7, "Contoso",
+    9,
+      0x04, 0x00, 0x00, 0x00,
+  7,
+    0x04, 0x00, 0x00, 0x00,
+    0,
+    0x01, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    4,
+    13, "Contoso.Order",
+    0x02, 0x00, 0x00, 0x00,
+    9,
+      0x05, 0x00, 0x00, 0x00,
+    9,
+      0x06, 0x00, 0x00, 0x00,
+  5,
+    0x05, 0x00, 0x00, 0x00,
+    13, "Contoso.Order",
+    0x02, 0x00, 0x00, 0x00,
+    8, "Customer",
+    2, "Id",
+    4, 0,
+    16, "Contoso.Customer",
+    0x02, 0x00, 0x00, 0x00,
+    8,
+    0x02, 0x00, 0x00, 0x00,
+    5,
+      0x07, 0x00, 0x00, 0x00,
+      16, "Contoso.Customer",
+      0x01, 0x00, 0x00, 0x00,
+      4, "Name",
+      1,
+      0x02, 0x00, 0x00, 0x00,
+      6,
+        0x08, 0x00, 0x00, 0x00,
+        5, "Alice",
+    0x01, 0x00, 0x00, 0x00,
+  1,
+    0x06, 0x00, 0x00, 0x00,
+    0x05, 0x00, 0x00, 0x00,
+    1,
+      0x09, 0x00, 0x00, 0x00,
+      0x07, 0x00, 0x00, 0x00,
+      6,
+        0x0A, 0x00, 0x00, 0x00,
+        3, "Bob",
+    0x02, 0x00, 0x00, 0x00,
+  11,
+);
+
+fn object<'i>(class: &'i str, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library: Some(MODEL), members: HashMap::from_iter(members) })
+}
+
+fn order(id: i32, customer: &str) -> Value<'_> {
+  object(
+    "Contoso.Order",
+    vec![
+      ("<Id>k__BackingField", Value::Int32(id)),
+      ("Customer", object("Contoso.Customer", vec![("Name", Value::String(customer))])),
+    ],
+  )
+}
+
+fn shop() -> Value<'static> {
+  let orders = Object {
+    class: LIST,
+    library: None,
+    members: HashMap::from_iter([
+      ("_items", Value::Array(vec![order(1, "Alice"), order(2, "Bob"), order(3, "Carol"), Value::Null])),
+      ("_size", Value::Int32(3)),
+      ("_version", Value::Int32(3)),
+    ]),
+  };
+
+  object("Contoso.Shop", vec![("Orders", Value::Object(orders)), ("Owner.Name", Value::String("Dave"))])
+}
+
+fn ids<'v>(values: impl IntoIterator<Item = &'v Value<'v>>) -> Vec<i32> {
+  values
+    .into_iter()
+    .map(|value| match value {
+      Value::Int32(id) => *id,
+      value => panic!("expected Int32, found {:?}", value),
+    })
+    .collect()
+}
+
+#[test]
+fn select_indices_and_slices() {
+  let shop = shop();
+  let select = |path| ids(shop.select(path).unwrap());
+
+  assert_eq!(select("Orders[*].<Id>k__BackingField"), vec![1, 2, 3]);
+  assert_eq!(select("Orders[1].<Id>k__BackingField"), vec![2]);
+  assert_eq!(select("Orders[-1].<Id>k__BackingField"), vec![3]);
+  assert_eq!(select("Orders[3].<Id>k__BackingField"), vec![]);
+  assert_eq!(select("Orders[-3].<Id>k__BackingField"), vec![1]);
+  assert_eq!(select("Orders[-4].<Id>k__BackingField"), vec![]);
+  assert_eq!(select("Orders[-5:2].<Id>k__BackingField"), vec![1, 2]);
+  assert_eq!(select("Orders[1:].<Id>k__BackingField"), vec![2, 3]);
+  assert_eq!(select("Orders[:-1].<Id>k__BackingField"), vec![1, 2]);
+  assert_eq!(select("Orders[2:1].<Id>k__BackingField"), vec![]);
+  assert_eq!(select("$.Orders[*]['<Id>k__BackingField']"), vec![1, 2, 3]);
+}
+
+#[test]
+fn select_wildcards_and_descendants() {
+  let shop = shop();
+
+  assert_eq!(shop.get_path("['Owner.Name']"), Some(&Value::String("Dave")));
+  assert_eq!(shop.select("Orders[0].*").map(|values| values.len()), Ok(2));
+  assert_eq!(shop.select("..Name"), Ok(vec![&Value::String("Alice"), &Value::String("Bob"), &Value::String("Carol")]));
+  assert_eq!(ids(shop.select("..[class=Contoso.Order].<Id>k__BackingField").unwrap()), vec![1, 2, 3]);
+  assert_eq!(shop.select("..[class=System.Collections.Generic.List`1]").map(|values| values.len()), Ok(1));
+  assert_eq!(shop.select(&format!("Orders[class={}]", LIST)).map(|values| values.len()), Ok(1));
+  assert_eq!(shop.get_path("Orders[0].Missing"), None);
+}
+
+#[test]
+fn parse_errors() {
+  assert_eq!(Path::parse("Orders[").unwrap_err().to_string(), "unclosed `[` at position 6");
+  assert_eq!(Path::parse("Orders[x]").unwrap_err().to_string(), "invalid bracket expression at position 7");
+  assert_eq!(Path::parse("Orders.").unwrap_err().to_string(), "expected a member name at position 7");
+  assert_eq!(Path::parse("Orders[0]Name").unwrap_err().to_string(), "expected `.` or `[` at position 9");
+  assert_eq!(shop().get_path("Orders[x]"), None);
+}
+
+#[test]
+fn select_fixture() {
+  let Ok(RemotingMessage::Value(shop)) = RemotingMessage::parse(SHOP) else { panic!("expected a value") };
+
+  assert_eq!(shop.select("Orders[*].Customer.Name"), Ok(vec![&Value::String("Alice"), &Value::String("Bob")]));
+  assert_eq!(shop.get_path("$.Orders[-1].Id"), Some(&Value::Int32(2)));
+  assert_eq!(shop.select("..[class=Contoso.Customer]").map(|customers| customers.len()), Ok(2));
+}
+
+#[test]
+fn select_bytes() {
+  let list = Value::Object(Object {
+    class: "System.Collections.Generic.List`1[[System.Byte, mscorlib]]",
+    library: None,
+    members: HashMap::from_iter([
+      ("_items", Value::Bytes(&[7, 8, 9, 0])),
+      ("_size", Value::Int32(3)),
+      ("_version", Value::Int32(3)),
+    ]),
+  });
+  let packet = object("Contoso.Packet", vec![("Data", list), ("Checksum", Value::Bytes(&[0xAB, 0xCD]))]);
+
+  assert_eq!(packet.get_path("Data[-1]"), Some(&Value::Byte(9)));
+  assert_eq!(packet.select("Data[1:]"), Ok(vec![&Value::Byte(8), &Value::Byte(9)]));
+  assert_eq!(packet.select("Checksum[*]"), Ok(vec![&Value::Byte(0xAB), &Value::Byte(0xCD)]));
+  assert_eq!(packet.get_path("Checksum[2]"), None);
+  assert_eq!(packet.get_path("Checksum[-5]"), None);
+}
+
+#[test]
+fn select_out_of_range() {
+  let array = Value::Array(vec![Value::Int32(1), Value::Int32(2), Value::Int32(3)]);
+
+  assert_eq!(array.get_path("[-3]"), Some(&Value::Int32(1)));
+  assert_eq!(array.get_path("[-5]"), None);
+  assert_eq!(array.select("[-5:]").map(|values| values.len()), Ok(3));
+  assert_eq!(array.select("[:5]").map(|values| values.len()), Ok(3));
+}
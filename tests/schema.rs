@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  schema::{ArrayMetadata, BinaryArrayType, ClassMetadata, Library, MemberMetadata, MemberType, PrimitiveType, Schema},
+  value::Object,
+};
+
+#[rustfmt::skip]
+const INPUT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  16,
+    0x01, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    7, "Contoso",
+  5,
+    0x03, 0x00, 0x00, 0x00,
+    13, "Contoso.Point",
+    0x03, 0x00, 0x00, 0x00,
+    1, "x",
+    1, "y",
+    4, "tags",
+    0, 0, 6,
+    8, 8,
+    0x02, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00,
+    0x02, 0x00, 0x00, 0x00,
+    9,
+      0x05, 0x00, 0x00, 0x00,
+  17,
+    0x05, 0x00, 0x00, 0x00,
+    0x01, 0x00, 0x00, 0x00,
+    6,
+      0x06, 0x00, 0x00, 0x00,
+      1, "a",
+  1,
+    0x04, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00,
+    0x03, 0x00, 0x00, 0x00,
+    0x04, 0x00, 0x00, 0x00,
+    10,
+  11
+);
+
+fn point<'i>(x: i32, y: i32, tags: Value<'i>) -> Value<'i> {
+  Value::Object(Object {
+    class: "Contoso.Point",
+    library: Some("Contoso"),
+    members: HashMap::from_iter([("x", Value::Int32(x)), ("y", Value::Int32(y)), ("tags", tags)]),
+  })
+}
+
+#[test]
+fn class_with_id() {
+  let output = RemotingMessage::Value(Value::Array(vec![
+    point(1, 2, Value::Array(vec![Value::String("a")])),
+    point(3, 4, Value::Null),
+  ]));
+
+  assert_eq!(RemotingMessage::parse(INPUT), Ok(output));
+}
+
+#[test]
+fn schema() {
+  let point = ClassMetadata {
+    object_id: 3,
+    name: "Contoso.Point",
+    library_id: Some(2),
+    members: vec![
+      MemberMetadata { name: "x", member_type: Some(MemberType::Primitive(PrimitiveType::Int32)) },
+      MemberMetadata { name: "y", member_type: Some(MemberType::Primitive(PrimitiveType::Int32)) },
+      MemberMetadata { name: "tags", member_type: Some(MemberType::StringArray) },
+    ],
+    reused_by: vec![4],
+  };
+
+  let schema = nrbf::schema(INPUT).unwrap();
+  assert_eq!(
+    schema,
+    Schema {
+      libraries: vec![Library { id: 2, name: "Contoso" }],
+      classes: vec![point.clone()],
+      arrays: vec![
+        ArrayMetadata {
+          object_id: 1,
+          array_type: BinaryArrayType::Single,
+          lengths: vec![2],
+          lower_bounds: None,
+          element_type: MemberType::Object,
+        },
+        ArrayMetadata {
+          object_id: 5,
+          array_type: BinaryArrayType::Single,
+          lengths: vec![1],
+          lower_bounds: None,
+          element_type: MemberType::String,
+        },
+      ],
+    }
+  );
+
+  assert_eq!(schema.class(4), Some(&point));
+  assert_eq!(schema.library(2).map(|library| library.name), Some("Contoso"));
+}
+
+#[test]
+fn schema_binary_array() {
+  #[rustfmt::skip]
+  let input = [
+    0,
+      0x01, 0x00, 0x00, 0x00,
+      0xFF, 0xFF, 0xFF, 0xFF,
+      0x01, 0x00, 0x00, 0x00,
+      0x00, 0x00, 0x00, 0x00,
+    7,
+      0x01, 0x00, 0x00, 0x00,
+      2,
+      0x02, 0x00, 0x00, 0x00,
+      0x01, 0x00, 0x00, 0x00,
+      0x02, 0x00, 0x00, 0x00,
+      0,
+      9,
+      67, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+      42, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    11,
+  ];
+
+  let schema = nrbf::schema(&input).unwrap();
+  assert_eq!(
+    schema.arrays,
+    vec![ArrayMetadata {
+      object_id: 1,
+      array_type: BinaryArrayType::Rectangular,
+      lengths: vec![1, 2],
+      lower_bounds: None,
+      element_type: MemberType::Primitive(PrimitiveType::Int64),
+    }]
+  );
+  assert!(schema.classes.is_empty());
+}
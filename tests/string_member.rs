@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{RemotingMessage, Value, value::Object};
+
+/// A `Contoso.Customer` with a string member referencing a previous string and null members.
+#[rustfmt::skip]
+const INPUT: &[u8] = concat_bytes!(
+  0,
+    0x01, 0x00, 0x00, 0x00,
+    0xFF, 0xFF, 0xFF, 0xFF,
+    0x01, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00,
+  12,
+    0x02, 0x00, 0x00, 0x00,
+    13, "Contoso.Model",
+  5,
+    0x01, 0x00, 0x00, 0x00,
+    16, "Contoso.Customer",
+    0x04, 0x00, 0x00, 0x00,
+    4, "Name",
+    5, "Alias",
+    5, "Email",
+    6, "Scores",
+    1, 1, 1, 7,
+    8,
+    0x02, 0x00, 0x00, 0x00,
+    6,
+      0x03, 0x00, 0x00, 0x00,
+      5, "Alice",
+    9,
+      0x03, 0x00, 0x00, 0x00,
+    10,
+    10,
+  11,
+);
+
+#[test]
+fn string_member_reference_and_null() {
+  let output = RemotingMessage::Value(Value::Object(Object {
+    class: "Contoso.Customer",
+    library: Some("Contoso.Model"),
+    members: HashMap::from_iter([
+      ("Name", Value::String("Alice")),
+      ("Alias", Value::String("Alice")),
+      ("Email", Value::Null),
+      ("Scores", Value::Null),
+    ]),
+  }));
+
+  assert_eq!(RemotingMessage::parse(INPUT), Ok(output));
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use const_str::concat_bytes;
+use nrbf::{
+  RemotingMessage, Value,
+  value::{CultureInfo, DateTimeOffset, Guid, Object, Uri, Version},
+};
+
+const SYSTEM: &str = "System, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089";
+
+#[rustfmt::skip]
+const DATE_TIME_OFFSET: &[u8] = concat_bytes!(
+  0,
+    b"\x01\x00\x00\x00",
+    b"\xFF\xFF\xFF\xFF",
+    b"\x01\x00\x00\x00",
+    b"\x00\x00\x00\x00",
+  4,
+    b"\x01\x00\x00\x00",
+    21, "System.DateTimeOffset",
+    b"\x02\x00\x00\x00",
+    8, "DateTime",
+    13, "OffsetMinutes",
+    0, 0,
+    13, 7,
+    b"\x87\xE7\xE6\x5A\x11\xBC\xCB\x48",
+    b"\x5C\xFE",
+  11,
+);
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Object<'i> {
+  Object { class, library, members: HashMap::from_iter(members) }
+}
+
+fn guid() -> Object<'static> {
+  let mut members = vec![("_a", Value::Int32(0x12345678)), ("_b", Value::Int16(0x1234)), ("_c", Value::Int16(0x5678))];
+  members.extend(
+    ["_d", "_e", "_f", "_g", "_h", "_i", "_j", "_k"]
+      .into_iter()
+      .zip([0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0])
+      .map(|(n, v)| (n, Value::Byte(v))),
+  );
+
+  object("System.Guid", None, members)
+}
+
+#[test]
+fn guid_conversion() {
+  let guid = Guid::try_from(&guid()).unwrap();
+
+  assert_eq!(guid.to_string(), "12345678-1234-5678-1234-56789abcdef0");
+  assert_eq!(guid.to_bytes_le()[..4], [0x78, 0x56, 0x34, 0x12]);
+}
+
+#[test]
+fn date_time_offset_conversion() {
+  let Ok(RemotingMessage::Value(Value::Object(object))) = RemotingMessage::parse(DATE_TIME_OFFSET) else {
+    panic!("expected an object")
+  };
+
+  let date_time_offset = DateTimeOffset::try_from(&object).unwrap();
+  assert_eq!(date_time_offset.offset_minutes, -420);
+  assert_eq!(date_time_offset.date_time.ticks(), 633806955301234567);
+  assert_eq!(date_time_offset.to_string(), "2009-06-15T13:45:30.1234567-07:00");
+}
+
+#[test]
+fn version_conversion() {
+  let version = |build, revision| {
+    object(
+      "System.Version",
+      None,
+      vec![
+        ("_Major", Value::Int32(4)),
+        ("_Minor", Value::Int32(0)),
+        ("_Build", Value::Int32(build)),
+        ("_Revision", Value::Int32(revision)),
+      ],
+    )
+  };
+
+  assert_eq!(Version::try_from(&version(30319, 42000)).unwrap().to_string(), "4.0.30319.42000");
+  assert_eq!(Version::try_from(&version(30319, -1)).unwrap().to_string(), "4.0.30319");
+  assert_eq!(Version::try_from(&version(-1, -1)), Ok(Version { major: 4, minor: 0, build: None, revision: None }));
+}
+
+#[test]
+fn uri_conversion() {
+  let uri = object("System.Uri", Some(SYSTEM), vec![("AbsoluteUri", Value::String("tcp://localhost:8080/Server.rem"))]);
+  assert_eq!(Uri::try_from(&uri), Ok(Uri { uri: "tcp://localhost:8080/Server.rem", is_absolute: true }));
+
+  let uri = object("System.Uri", Some(SYSTEM), vec![("RelativeUri", Value::String("Server.rem"))]);
+  assert_eq!(Uri::try_from(&uri).unwrap().to_string(), "Server.rem");
+}
+
+#[test]
+fn culture_info_conversion() {
+  let culture_info = object(
+    "System.Globalization.CultureInfo",
+    None,
+    vec![
+      ("m_name", Value::String("de-CH")),
+      ("cultureID", Value::Int32(0x0807)),
+      ("m_useUserOverride", Value::Boolean(true)),
+      ("m_isReadOnly", Value::Boolean(false)),
+    ],
+  );
+
+  assert_eq!(
+    CultureInfo::try_from(&culture_info),
+    Ok(CultureInfo { name: "de-CH", lcid: Some(0x0807), use_user_override: true })
+  );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize() {
+  use serde::Deserialize;
+
+  #[derive(Debug, Deserialize, PartialEq)]
+  struct Session {
+    id: String,
+    raw_id: [u8; 16],
+    started: String,
+    endpoint: String,
+  }
+
+  let Ok(RemotingMessage::Value(Value::Object(started))) = RemotingMessage::parse(DATE_TIME_OFFSET) else {
+    panic!("expected an object")
+  };
+
+  let session = Value::Object(object(
+    "App.Session",
+    Some("App"),
+    vec![
+      ("id", Value::Object(guid())),
+      ("raw_id", Value::Object(guid())),
+      ("started", Value::Object(started)),
+      (
+        "endpoint",
+        Value::Object(object(
+          "System.Uri",
+          Some(SYSTEM),
+          vec![("AbsoluteUri", Value::String("tcp://localhost:8080/"))],
+        )),
+      ),
+    ],
+  ));
+  let bytes = RemotingMessage::Value(session).to_bytes();
+
+  assert_eq!(
+    nrbf::from_slice(&bytes),
+    Ok(Session {
+      id: "12345678-1234-5678-1234-56789abcdef0".into(),
+      raw_id: [0x78, 0x56, 0x34, 0x12, 0x34, 0x12, 0x78, 0x56, 0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0],
+      started: "2009-06-15T13:45:30.1234567-07:00".into(),
+      endpoint: "tcp://localhost:8080/".into(),
+    })
+  );
+}
@@ -0,0 +1,94 @@
+use std::{
+  net::TcpListener,
+  sync::{
+    Arc,
+    atomic::{AtomicI32, Ordering},
+  },
+  thread,
+};
+
+use nrbf::{
+  DotNetFault, Value,
+  tcp::{ClientError, RemotingClient, RemotingServer},
+};
+
+const TYPE_NAME: &str = "Server.ICalculator, App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn start_server(total: Arc<AtomicI32>) -> String {
+  let mut server = RemotingServer::new();
+  server
+    .register(TYPE_NAME, "Add", |args| match args {
+      [Value::Int32(a), Value::Int32(b)] => Ok(Value::Int32(a + b)),
+      _ => Err(DotNetFault::new("System.ArgumentException", "expected two integers")),
+    })
+    .register(TYPE_NAME, "Concat", |args| match args {
+      [Value::String(_), Value::Null] => Ok(args[0].clone()),
+      _ => Err(DotNetFault::new("System.ArgumentException", "expected a string and null")),
+    })
+    .register(TYPE_NAME, "Accumulate", move |args| match args {
+      [Value::Int32(n)] => {
+        total.fetch_add(*n, Ordering::SeqCst);
+        Ok(Value::Null)
+      },
+      _ => Err(DotNetFault::new("System.ArgumentException", "expected an integer")),
+    });
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+  thread::spawn(move || server.serve(listener));
+
+  format!("tcp://{}/Calculator.rem", address)
+}
+
+#[test]
+fn call() {
+  let uri = start_server(Arc::default());
+  let mut client = RemotingClient::new(&uri).unwrap();
+  assert_eq!(client.object_uri(), "/Calculator.rem");
+
+  let reply = client.call(TYPE_NAME, "Add", &[Value::Int32(2), Value::Int32(3)]).unwrap();
+  assert_eq!(reply.return_value().unwrap(), Value::Int32(5));
+
+  let reply = client.call(TYPE_NAME, "Concat", &[Value::String("Hello"), Value::Null]).unwrap();
+  assert_eq!(reply.return_value().unwrap(), Value::String("Hello"));
+}
+
+#[test]
+fn call_fault() {
+  let uri = start_server(Arc::default());
+  let mut client = RemotingClient::new(&uri).unwrap();
+
+  let reply = client.call(TYPE_NAME, "Add", &[Value::Int32(2)]).unwrap();
+  match reply.return_value() {
+    Err(ClientError::Fault(fault)) => {
+      assert_eq!(fault, DotNetFault::new("System.ArgumentException", "expected two integers"))
+    },
+    other => panic!("expected a fault, got {:?}", other),
+  }
+
+  let reply = client.call(TYPE_NAME, "Divide", &[Value::Int32(2), Value::Int32(3)]).unwrap();
+  match reply.return_value() {
+    Err(ClientError::Fault(fault)) => assert_eq!(fault.class_name, "System.Runtime.Remoting.RemotingException"),
+    other => panic!("expected a fault, got {:?}", other),
+  }
+}
+
+#[test]
+fn call_one_way() {
+  let total = Arc::new(AtomicI32::new(0));
+  let uri = start_server(Arc::clone(&total));
+  let mut client = RemotingClient::new(&uri).unwrap();
+
+  client.call_one_way(TYPE_NAME, "Accumulate", &[Value::Int32(40)]).unwrap();
+  let reply = client.call(TYPE_NAME, "Accumulate", &[Value::Int32(2)]).unwrap();
+  assert!(reply.return_value().is_ok());
+
+  assert_eq!(total.load(Ordering::SeqCst), 42);
+}
+
+#[test]
+fn invalid_uri() {
+  for uri in ["http://localhost:8080/Calculator.rem", "tcp://localhost/Calculator.rem", "tcp://localhost:8080/"] {
+    assert!(matches!(RemotingClient::new(uri), Err(ClientError::InvalidUri(_))), "{}", uri);
+  }
+}
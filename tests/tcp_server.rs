@@ -0,0 +1,174 @@
+use std::{
+  collections::HashMap,
+  io::{self, Cursor, Write},
+  net::{Shutdown, TcpListener, TcpStream},
+  sync::mpsc,
+  thread,
+  time::Duration,
+};
+
+use const_str::concat_bytes;
+use nrbf::{
+  DotNetFault, MethodCall, MethodReturn, RemotingMessage, Value,
+  tcp::{Header, Message, OperationType, RemotingServer},
+  value::Object,
+};
+
+const TYPE_NAME: &str = "Server.Calculator, App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn start_server() -> TcpStream {
+  let mut server = RemotingServer::new();
+  server
+    .register("Server.Calculator", "Add", |args| match args {
+      [Value::Int32(a), Value::Int32(b)] => Ok(Value::Int32(a + b)),
+      _ => Err(DotNetFault::new("System.ArgumentException", "expected two integers")),
+    })
+    .register(TYPE_NAME, "Echo", |args| Ok(args.first().cloned().unwrap_or(Value::Null)))
+    .register(TYPE_NAME, "Street", |args| match args {
+      [Value::Object(address)] => Ok(address.members.get("Street").cloned().unwrap_or(Value::Null)),
+      _ => Err(DotNetFault::new("System.ArgumentException", "expected an address")),
+    });
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+  thread::spawn(move || server.serve(listener));
+
+  TcpStream::connect(address).unwrap()
+}
+
+fn call(stream: &mut TcpStream, method_name: &str, args: Vec<Value<'_>>) -> Vec<u8> {
+  let method_call =
+    RemotingMessage::MethodCall(MethodCall { method_name, type_name: TYPE_NAME, call_context: None, args: Some(args) });
+
+  Message::request("tcp://127.0.0.1/Calculator.rem", method_call.to_bytes()).write_to(stream).unwrap();
+
+  let reply = Message::read_from(stream).unwrap();
+  assert_eq!(reply.operation_type, OperationType::Reply);
+  reply.body
+}
+
+fn method_return(body: &[u8]) -> MethodReturn<'_> {
+  match RemotingMessage::parse(body) {
+    Ok(RemotingMessage::MethodReturn(method_return)) => method_return,
+    other => panic!("expected a method return, got {:?}", other),
+  }
+}
+
+#[test]
+fn dispatch() {
+  let mut stream = start_server();
+
+  let body = call(&mut stream, "Add", vec![Value::Int32(2), Value::Int32(3)]);
+  assert_eq!(method_return(&body).return_value, Some(Value::Int32(5)));
+
+  let body = call(&mut stream, "Echo", vec![Value::String("Hello, world!")]);
+  assert_eq!(method_return(&body).return_value, Some(Value::String("Hello, world!")));
+
+  let address = Value::Object(Object {
+    class: "Server.Address",
+    library: Some("App, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null"),
+    members: HashMap::from_iter([("Street", Value::String("One Microsoft Way")), ("City", Value::String("Redmond"))]),
+  });
+  let body = call(&mut stream, "Street", vec![address]);
+  assert_eq!(method_return(&body).return_value, Some(Value::String("One Microsoft Way")));
+}
+
+#[test]
+fn dispatch_fault() {
+  let mut stream = start_server();
+
+  let body = call(&mut stream, "Add", vec![Value::String("2")]);
+  let exception = method_return(&body).exception.expect("expected an exception");
+  assert_eq!(
+    DotNetFault::from_exception(&exception),
+    Some(DotNetFault::new("System.ArgumentException", "expected two integers"))
+  );
+}
+
+#[test]
+fn dispatch_unknown_method() {
+  let mut stream = start_server();
+
+  let body = call(&mut stream, "Subtract", vec![Value::Int32(2), Value::Int32(3)]);
+  let exception = method_return(&body).exception.expect("expected an exception");
+  let fault = DotNetFault::from_exception(&exception).unwrap();
+  assert_eq!(fault.class_name, "System.Runtime.Remoting.RemotingException");
+}
+
+#[test]
+fn message_round_trip() {
+  let message = Message {
+    operation_type: OperationType::OneWayRequest,
+    headers: vec![
+      Header::RequestUri("Calculator.rem".into()),
+      Header::Custom { name: "__Id".into(), value: "42".into() },
+      Header::StatusCode(0),
+      Header::CloseConnection,
+    ],
+    body: vec![1, 2, 3],
+  };
+
+  let mut output = vec![];
+  message.write_to(&mut output).unwrap();
+
+  assert_eq!(Message::read_from(&mut Cursor::new(output)).unwrap(), message);
+}
+
+#[test]
+fn message_chunked() {
+  #[rustfmt::skip]
+  let input = concat_bytes!(
+    ".NET", 1, 0,
+    b"\x02\x00",
+    b"\x01\x00",
+    b"\x03\x00", 1, 0, b"\x04\x00\x00\x00", b"O\x00K\x00",
+    b"\x00\x00",
+    b"\x02\x00\x00\x00", b"\x01\x02", "\r\n",
+    b"\x01\x00\x00\x00", b"\x03", "\r\n",
+    b"\x00\x00\x00\x00",
+  );
+
+  let message = Message::read_from(&mut Cursor::new(input)).unwrap();
+  assert_eq!(message.operation_type, OperationType::Reply);
+  assert_eq!(message.status_phrase(), Some("OK"));
+  assert_eq!(message.body, vec![1, 2, 3]);
+}
+
+#[test]
+fn serve_reports_connection_errors() {
+  let (sender, receiver) = mpsc::channel();
+
+  let mut server = RemotingServer::new();
+  server.on_error(move |err| sender.send(err.kind()).unwrap());
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+  thread::spawn(move || server.serve(listener));
+
+  let mut stream = TcpStream::connect(address).unwrap();
+  stream.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+
+  assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(io::ErrorKind::InvalidData));
+}
+
+#[test]
+fn serve_reports_truncated_messages() {
+  let (sender, receiver) = mpsc::channel();
+
+  let mut server = RemotingServer::new();
+  server.on_error(move |err| sender.send(err.kind()).unwrap());
+
+  let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+  let address = listener.local_addr().unwrap();
+  thread::spawn(move || server.serve(listener));
+
+  let stream = TcpStream::connect(address).unwrap();
+  stream.shutdown(Shutdown::Write).unwrap();
+
+  let mut stream = TcpStream::connect(address).unwrap();
+  stream.write_all(b".NET\x01\x00\x00\x00").unwrap();
+  stream.shutdown(Shutdown::Write).unwrap();
+
+  assert_eq!(receiver.recv_timeout(Duration::from_secs(5)), Ok(io::ErrorKind::UnexpectedEof));
+  assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+}
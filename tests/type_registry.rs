@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use nrbf::{
+  TypeName, TypeRegistry, Value,
+  value::{ConversionError, Object},
+};
+
+const MODEL_V1: &str = "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+const MODEL_V2: &str = "Contoso.Model, Version=2.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn object<'i>(class: &'i str, library: Option<&'i str>, members: Vec<(&'i str, Value<'i>)>) -> Object<'i> {
+  Object { class, library, members: HashMap::from_iter(members) }
+}
+
+fn money(library: &str) -> Object<'_> {
+  object("Contoso.Money", Some(library), vec![("units", Value::Int64(1234)), ("scale", Value::Int32(2))])
+}
+
+#[test]
+fn type_name() {
+  assert_eq!(TypeName::from("Contoso.Money"), TypeName::new("Contoso.Money"));
+  assert_eq!(
+    TypeName::from(" Contoso.Box`1[[System.Int32, mscorlib]], Contoso.Model, Version=1.0.0.0"),
+    TypeName::new("Contoso.Box`1[[System.Int32, mscorlib]]").with_assembly("Contoso.Model").with_version("1.0.0.0")
+  );
+  assert_eq!(TypeName::from("Contoso.Money, Contoso.Model").to_string(), "Contoso.Money, Contoso.Model");
+
+  assert!(TypeName::new("Contoso.Money").matches(&money(MODEL_V1)));
+  assert!(TypeName::from("Contoso.Money, Contoso.Model, Version=1.0.0.0").matches(&money(MODEL_V1)));
+  assert!(!TypeName::from("Contoso.Money, Contoso.Model, Version=1.0.0.0").matches(&money(MODEL_V2)));
+  assert!(!TypeName::from("Contoso.Money, Contoso.Other").matches(&money(MODEL_V1)));
+
+  let generic = object("Contoso.Box`1[[System.Int32, mscorlib]]", Some(MODEL_V1), vec![]);
+  assert!(TypeName::new("Contoso.Box`1").matches(&generic));
+  assert!(!TypeName::new("Contoso.Box").matches(&generic));
+
+  let system = object("System.Int32", None, vec![]);
+  assert!(TypeName::from("System.Int32, mscorlib").matches(&system));
+}
+
+#[test]
+fn convert() {
+  let mut registry = TypeRegistry::new();
+  registry
+    .register("Contoso.Money, Contoso.Model, Version=1.0.0.0", |_| Ok(Value::String("v1")))
+    .register("Contoso.Money", |_| Ok(Value::String("any")))
+    .register("Contoso.Invalid", |_| Err(ConversionError::MissingMember("units")));
+
+  assert_eq!(registry.convert(&money(MODEL_V1)), Some(Ok(Value::String("v1"))));
+  assert_eq!(registry.convert(&money(MODEL_V2)), Some(Ok(Value::String("any"))));
+  assert_eq!(
+    registry.convert(&object("Contoso.Invalid", None, vec![])),
+    Some(Err(ConversionError::MissingMember("units")))
+  );
+  assert_eq!(registry.convert(&object("Contoso.Other", None, vec![])), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn from_slice_with() {
+  use nrbf::{Options, RemotingMessage};
+  use serde::Deserialize;
+
+  #[derive(Debug, PartialEq, Deserialize)]
+  struct Order {
+    id: i32,
+    total: f64,
+  }
+
+  let mut options = Options::new();
+  options.type_registry.register("Contoso.Money, Contoso.Model", |object| {
+    match (object.members.get("units"), object.members.get("scale")) {
+      (Some(Value::Int64(units)), Some(Value::Int32(scale))) => Ok(Value::Double(*units as f64 / 10f64.powi(*scale))),
+      _ => Err(ConversionError::InvalidMember("units")),
+    }
+  });
+  // A converter returning an object of its own class is not applied again.
+  options.type_registry.register("Contoso.Order", |object| {
+    let mut object = object.clone();
+    object.members.remove("comment");
+    Ok(Value::Object(object))
+  });
+
+  let order = object(
+    "Contoso.Order",
+    Some(MODEL_V1),
+    vec![("id", Value::Int32(7)), ("total", Value::Object(money(MODEL_V1))), ("comment", Value::Null)],
+  );
+  let bytes = RemotingMessage::Value(Value::Object(order)).to_bytes();
+
+  assert_eq!(nrbf::from_slice_with(&bytes, &options), Ok(Order { id: 7, total: 12.34 }));
+  assert_eq!(
+    nrbf::from_slice_with::<Vec<f64>>(
+      &RemotingMessage::Value(Value::Array(vec![Value::Object(money(MODEL_V2))])).to_bytes(),
+      &options
+    ),
+    Ok(vec![12.34])
+  );
+  assert!(nrbf::from_slice::<Order>(&bytes).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn method_call_and_return_with() {
+  use nrbf::{MethodCall, MethodReturn, Options};
+
+  let mut options = Options::new();
+  options.type_registry.register("Contoso.Money", |object| Ok(object.members["units"].clone()));
+
+  let method_call = MethodCall {
+    method_name: "Pay",
+    type_name: "Contoso.Bank, Contoso.Model",
+    call_context: None,
+    args: Some(vec![Value::String("Alice"), Value::Object(money(MODEL_V1))]),
+  };
+  assert_eq!(method_call.deserialize_args_with(&options), Ok(("Alice", 1234i64)));
+  assert!(method_call.deserialize_args::<(&str, i64)>().is_err());
+
+  let method_return =
+    MethodReturn::new(Some(Value::Object(money(MODEL_V2)))).with_args(vec![Value::Object(money(MODEL_V1))]);
+  assert_eq!(method_return.deserialize_return_with(&options), Ok(1234i64));
+  assert_eq!(method_return.deserialize_args_with(&options), Ok((1234i64,)));
+  assert!(method_return.deserialize_return::<i64>().is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn from_value_with() {
+  use nrbf::Options;
+  use serde::Deserialize;
+
+  #[derive(Deserialize)]
+  struct Money {
+    units: i64,
+    scale: i32,
+  }
+
+  let mut options = Options::new();
+  options.type_registry.register("Contoso.Money", |object| {
+    let money: Money = nrbf::from_value_with(&Value::Object(object.clone()), &Options::new())?;
+    Ok(Value::Double(money.units as f64 / 10f64.powi(money.scale)))
+  });
+  options.type_registry.register("Contoso.Broken", |object| {
+    let money: Money = nrbf::from_value_with(&Value::Object(object.clone()), &Options::new())?;
+    Ok(Value::Int64(money.units))
+  });
+
+  assert_eq!(nrbf::from_value_with(&Value::Object(money(MODEL_V1)), &options), Ok(12.34));
+  assert_eq!(
+    nrbf::from_value_with::<Vec<f64>>(&Value::Array(vec![Value::Object(money(MODEL_V2))]), &options),
+    Ok(vec![12.34])
+  );
+
+  let broken = object("Contoso.Broken", Some(MODEL_V1), vec![("units", Value::Int64(1))]);
+  assert!(matches!(options.type_registry.convert(&broken), Some(Err(ConversionError::Custom(_)))));
+  assert!(nrbf::from_value_with::<i64>(&Value::Object(broken), &options).is_err());
+}
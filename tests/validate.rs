@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use nrbf::{
+  MethodCall, RemotingMessage, Value,
+  validate::{ClassSchema, MemberSchema, Schema, ValueType, Violation, ViolationKind},
+  value::Object,
+};
+
+const MODEL: &str = "Contoso.Model, Version=1.0.0.0, Culture=neutral, PublicKeyToken=null";
+
+fn object<'i>(class: &'i str, members: Vec<(&'i str, Value<'i>)>) -> Value<'i> {
+  Value::Object(Object { class, library: Some(MODEL), members: HashMap::from_iter(members) })
+}
+
+fn order<'i>(customer: Value<'i>, lines: Vec<Value<'i>>) -> Value<'i> {
+  object("Contoso.Order", vec![("id", Value::Int32(7)), ("customer", customer), ("lines", Value::Array(lines))])
+}
+
+fn schema() -> Schema {
+  Schema::new()
+    .with_class(
+      "Contoso.Order",
+      ClassSchema::new()
+        .with_assembly("Contoso.Model")
+        .with_member("id", MemberSchema::new(ValueType::Int32))
+        .with_member("customer", MemberSchema::new(ValueType::Class(String::from("Contoso.Customer"))).nullable())
+        .with_member("lines", MemberSchema::new(ValueType::Array(Box::new(ValueType::Object))).with_max_len(2)),
+    )
+    .with_class(
+      "Contoso.Customer",
+      ClassSchema::new().with_member("name", MemberSchema::new(ValueType::String)).with_unknown_members(true),
+    )
+    .with_class("Contoso.Line", ClassSchema::new().with_member("amount", MemberSchema::new(ValueType::Double)))
+}
+
+#[test]
+fn validate_valid() {
+  let customer = object("Contoso.Customer", vec![("name", Value::String("Alice")), ("email", Value::Null)]);
+  let line = object("Contoso.Line", vec![("amount", Value::Double(1.5))]);
+
+  let message = RemotingMessage::Value(order(customer, vec![line]));
+  assert_eq!(nrbf::validate(&message, &schema()), vec![]);
+
+  let message = RemotingMessage::Value(order(Value::Null, vec![]));
+  assert_eq!(nrbf::validate(&message, &schema()), vec![]);
+}
+
+#[test]
+fn validate_all_violations() {
+  let customer = object("Contoso.Customer", vec![("name", Value::Int32(1))]);
+  let lines = vec![
+    object("Contoso.Line", vec![("amount", Value::Double(1.5)), ("note", Value::String("gift"))]),
+    object("Contoso.Line", vec![]),
+    object("Contoso.Discount", vec![("amount", Value::Double(0.5))]),
+  ];
+
+  let message = RemotingMessage::Value(order(customer, lines));
+  let violations = nrbf::validate(&message, &schema());
+
+  assert_eq!(
+    violations.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    vec![
+      "$.lines: array length 3 exceeds maximum of 2",
+      "$.customer.name: expected String, found Int32",
+      "$.lines[0]: unexpected member note",
+      "$.lines[1]: missing member amount",
+      "$.lines[2]: unexpected class Contoso.Discount",
+    ]
+  );
+  assert_eq!(
+    violations[4],
+    Violation {
+      path: String::from("$.lines[2]"),
+      kind: ViolationKind::UnexpectedClass(String::from("Contoso.Discount"))
+    }
+  );
+}
+
+#[test]
+fn validate_method_call() {
+  let method_call = MethodCall {
+    method_name: "Submit",
+    type_name: "Contoso.OrderService, Contoso.Server",
+    call_context: None,
+    args: Some(vec![Value::Int32(1), object("Contoso.Customer", vec![]), Value::Array(vec![Value::Int32(0); 4])]),
+  };
+
+  let schema = schema().with_max_array_len(3);
+  assert_eq!(
+    nrbf::validate(&RemotingMessage::MethodCall(method_call), &schema)
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<_>>(),
+    vec!["$.args[1]: missing member name", "$.args[2]: array length 4 exceeds maximum of 3"]
+  );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn validate_json_schema() {
+  let schema: Schema = serde_json::from_str(
+    r#"{
+      "classes": {
+        "Contoso.Line": {
+          "assembly": "Contoso.Other",
+          "members": {
+            "amount": { "type": "Double" },
+            "tags": { "type": { "Array": "String" }, "optional": true, "min_len": 1 }
+          }
+        }
+      }
+    }"#,
+  )
+  .unwrap();
+
+  let line = object("Contoso.Line", vec![("amount", Value::Null), ("tags", Value::Array(vec![]))]);
+  assert_eq!(
+    nrbf::validate(&RemotingMessage::Value(line), &schema).iter().map(ToString::to_string).collect::<Vec<_>>(),
+    vec![
+      format!("$: expected assembly Contoso.Other, found {}", MODEL),
+      String::from("$.amount: unexpected null"),
+      String::from("$.tags: array length 0 is below minimum of 1"),
+    ]
+  );
+}
+
+#[test]
+fn validate_boxed_and_generic_values() {
+  let int32 = |value| {
+    Value::Object(Object {
+      class: "System.Int32",
+      library: None,
+      members: HashMap::from_iter([("m_value", Value::Int32(value))]),
+    })
+  };
+  let nullable = |value: Option<Value<'static>>| {
+    Value::Object(Object {
+      class: "System.Nullable`1[[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+      library: None,
+      members: HashMap::from_iter([
+        ("hasValue", Value::Boolean(value.is_some())),
+        ("value", value.unwrap_or(Value::Int32(0))),
+      ]),
+    })
+  };
+  let list = Value::Object(Object {
+    class: "System.Collections.Generic.List`1[[System.Int32, mscorlib, Version=4.0.0.0, Culture=neutral, PublicKeyToken=b77a5c561934e089]]",
+    library: None,
+    members: HashMap::from_iter([("_items", Value::Bytes(&[])), ("_size", Value::Int32(0))]),
+  });
+
+  let schema = Schema::new()
+    .with_class(
+      "Contoso.Stats",
+      ClassSchema::new()
+        .with_member("count", MemberSchema::new(ValueType::Int32))
+        .with_member("limit", MemberSchema::new(ValueType::Int32).nullable())
+        .with_member("max", MemberSchema::new(ValueType::Int32))
+        .with_member("values", MemberSchema::new(ValueType::Class(String::from("System.Collections.Generic.List`1")))),
+    )
+    .with_class("System.Collections.Generic.List`1", ClassSchema::new().with_unknown_members(true));
+
+  let stats = |limit, max| {
+    object("Contoso.Stats", vec![("count", int32(3)), ("limit", limit), ("max", max), ("values", list.clone())])
+  };
+
+  let message = RemotingMessage::Value(stats(nullable(Some(Value::Int32(5))), nullable(Some(Value::Int32(9)))));
+  assert_eq!(nrbf::validate(&message, &schema), vec![]);
+
+  let message = RemotingMessage::Value(stats(nullable(None), nullable(None)));
+  assert_eq!(
+    nrbf::validate(&message, &schema).iter().map(ToString::to_string).collect::<Vec<_>>(),
+    vec!["$.max: unexpected null"]
+  );
+
+  let message = RemotingMessage::Value(stats(Value::Null, nullable(Some(Value::Int64(9)))));
+  assert_eq!(
+    nrbf::validate(&message, &schema).iter().map(ToString::to_string).collect::<Vec<_>>(),
+    vec!["$.max: expected Int32, found Int64"]
+  );
+}
+
+#[test]
+fn validate_unknown_enum_class() {
+  let status = |class| object(class, vec![("value__", Value::Int32(1))]);
+  let schema = Schema::new()
+    .with_class("Contoso.Status", ClassSchema::new().with_assembly("Contoso.Model"))
+    .with_class("Contoso.Account", ClassSchema::new().with_member("status", MemberSchema::new(ValueType::Int32)));
+
+  let message = RemotingMessage::Value(object("Contoso.Account", vec![("status", status("Contoso.Status"))]));
+  assert_eq!(nrbf::validate(&message, &schema), vec![]);
+
+  let message = RemotingMessage::Value(object("Contoso.Account", vec![("status", status("Contoso.Exploit"))]));
+  assert_eq!(
+    nrbf::validate(&message, &schema).iter().map(ToString::to_string).collect::<Vec<_>>(),
+    vec!["$.status: unexpected class Contoso.Exploit"]
+  );
+
+  let message = RemotingMessage::Value(status("Contoso.Exploit"));
+  assert_eq!(nrbf::validate(&message, &schema.with_unknown_classes(true)), vec![]);
+}